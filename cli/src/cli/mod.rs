@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand};
+use dg_xch_cli::commands::OutputFormat;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -12,9 +13,12 @@ pub struct Cli {
     pub fullnode_port: Option<u16>,
 
     #[arg(long, value_name = "Wallet Hostname")]
-    wallet_host: Option<String>,
+    pub wallet_host: Option<String>,
     #[arg(long, value_name = "Wallet Port")]
-    wallet_port: Option<u16>,
+    pub wallet_port: Option<u16>,
+
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
 
     #[command(subcommand)]
     pub action: RootCommands,
@@ -31,6 +35,24 @@ pub enum RootCommands {
         #[arg(short, long)]
         include_spent_coins: bool,
     },
+    #[command(about = "Gets the current blockchain state of the fullnode", long_about = None)]
+    GetBlockchainState,
+    #[command(about = "Gets a block record by height or header hash", long_about = None)]
+    GetBlock {
+        #[arg(long)]
+        height: Option<u32>,
+        #[arg(long)]
+        header_hash: Option<String>,
+    },
+    #[command(about = "Gets all items currently in the mempool", long_about = None)]
+    GetMempoolItems,
+    #[command(about = "Gets fee estimates for the given target times in seconds", long_about = None)]
+    GetFeeEstimate {
+        #[arg(short, long, num_args = 1.., value_delimiter = ',', default_values_t = [60, 120, 300])]
+        target_times: Vec<u64>,
+        #[arg(short, long)]
+        cost: Option<u64>,
+    },
     #[command(about = "Migrates a PlotNFT using a mnemonic", long_about = None)]
     MovePlotNFT {
         #[arg(short, long)]
@@ -70,6 +92,51 @@ pub enum RootCommands {
         #[command(subcommand)]
         action: WalletAction,
     },
+    #[command(about = "Interact with a running wallet", long_about = None)]
+    Wallet {
+        #[command(subcommand)]
+        action: WalletCommands,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum WalletCommands {
+    #[command(about = "Gets the balance of a wallet", long_about = None)]
+    Balance {
+        #[arg(short, long, default_value_t = 1)]
+        wallet_id: u32,
+    },
+    #[command(about = "Sends XCH to an address, or to every address in a CSV of address,amount rows", long_about = None)]
+    Send {
+        #[arg(short, long, default_value_t = 1)]
+        wallet_id: u32,
+        #[arg(short, long, conflicts_with = "csv", requires = "amount")]
+        address: Option<String>,
+        #[arg(short = 'm', long)]
+        amount: Option<u64>,
+        #[arg(long)]
+        csv: Option<String>,
+        #[arg(short, long, default_value_t = 0)]
+        fee: u64,
+    },
+    #[command(about = "Lists the transactions of a wallet", long_about = None)]
+    ListTransactions {
+        #[arg(short, long, default_value_t = 1)]
+        wallet_id: u32,
+        #[arg(long)]
+        start: Option<u32>,
+        #[arg(long)]
+        end: Option<u32>,
+    },
+    #[command(about = "Gets the sync status of the wallet", long_about = None)]
+    SyncStatus,
+    #[command(about = "Logs the wallet in to the key with the given fingerprint", long_about = None)]
+    Login {
+        #[arg(short, long)]
+        fingerprint: u32,
+        #[arg(short, long)]
+        skip_import: bool,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -84,3 +151,9 @@ pub enum WalletAction {
     #[command(about = "Creates a Cold wallet", long_about = None)]
     Cold,
 }
+
+#[test]
+fn test_cli_arguments() {
+    use clap::CommandFactory;
+    Cli::command().debug_assert();
+}
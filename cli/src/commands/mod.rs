@@ -1 +1,57 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::{Error, ErrorKind};
 
+#[derive(ValueEnum, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    #[default]
+    Table,
+}
+
+pub fn format_json<T: Serialize>(value: &T) -> Result<String, Error> {
+    serde_json::to_string_pretty(value).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+pub fn format_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            if i < widths.len() {
+                widths[i] = widths[i].max(cell.len());
+            }
+        }
+    }
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<String>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+    let mut lines = vec![
+        format_row(headers.to_vec()),
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<String>>()
+            .join("-+-"),
+    ];
+    for row in rows {
+        lines.push(format_row(row.iter().map(String::as_str).collect()));
+    }
+    lines.join("\n")
+}
+
+pub fn format_key_values(values: &[(&str, String)]) -> String {
+    format_table(
+        &["Field", "Value"],
+        &values
+            .iter()
+            .map(|(k, v)| vec![k.to_string(), v.clone()])
+            .collect::<Vec<Vec<String>>>(),
+    )
+}
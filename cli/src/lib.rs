@@ -1,3 +1,4 @@
 pub mod commands;
+pub mod node_commands;
 pub mod wallet_commands;
 pub mod wallets;
@@ -2,23 +2,74 @@ pub mod cli;
 use blst::min_pk::SecretKey;
 use clap::Parser;
 use cli::*;
+use dg_xch_cli::node_commands::{
+    get_block, get_blockchain_state, get_coin_records, get_fee_estimate, get_mempool_items,
+    puzzle_hash_from_args,
+};
 use dg_xch_cli::wallet_commands::{
-    create_cold_wallet, get_plotnft_ready_state, migrate_plot_nft, migrate_plot_nft_with_owner_key,
+    create_cold_wallet, create_plotnft_wallet, get_plotnft_ready_state, get_transactions,
+    get_wallet_balance, get_wallet_sync_status, log_in, migrate_plot_nft,
+    migrate_plot_nft_with_owner_key, send_transaction, send_transaction_multi,
 };
 use dg_xch_clients::protocols::pool::create_pool_login_url;
 use dg_xch_clients::rpc::full_node::FullnodeClient;
+use dg_xch_clients::rpc::wallet::WalletClient;
 use dg_xch_core::blockchain::sized_bytes::Bytes32;
 use simple_logger::SimpleLogger;
-use std::io::Error;
+use std::io::{Error, ErrorKind};
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     let cli = Cli::parse();
     SimpleLogger::new().env().init().unwrap_or_default();
+    let fullnode_client = FullnodeClient::new(
+        &cli.fullnode_host.unwrap_or("localhost".to_string()),
+        cli.fullnode_port.unwrap_or(8444),
+        cli.ssl_path.clone(),
+        &None,
+    );
 
     match cli.action {
-        RootCommands::GetCoinRecord { .. } => {
-            //Do Stuff Here
+        RootCommands::GetCoinRecord {
+            puzzlehash,
+            address,
+            include_spent_coins,
+        } => {
+            let puzzle_hash = puzzle_hash_from_args(puzzlehash, address)?;
+            println!(
+                "{}",
+                get_coin_records(
+                    &fullnode_client,
+                    &puzzle_hash,
+                    include_spent_coins,
+                    cli.output
+                )
+                .await?
+            );
+        }
+        RootCommands::GetBlockchainState => {
+            println!(
+                "{}",
+                get_blockchain_state(&fullnode_client, cli.output).await?
+            );
+        }
+        RootCommands::GetBlock {
+            height,
+            header_hash,
+        } => {
+            println!(
+                "{}",
+                get_block(&fullnode_client, height, header_hash, cli.output).await?
+            );
+        }
+        RootCommands::GetMempoolItems => {
+            println!("{}", get_mempool_items(&fullnode_client, cli.output).await?);
+        }
+        RootCommands::GetFeeEstimate { target_times, cost } => {
+            println!(
+                "{}",
+                get_fee_estimate(&fullnode_client, cost, &target_times, cli.output).await?
+            );
         }
         RootCommands::MovePlotNFT {
             target_pool,
@@ -26,15 +77,8 @@ async fn main() -> Result<(), Error> {
             mnemonic,
             fee,
         } => {
-            let host = cli.fullnode_host.unwrap_or("localhost".to_string());
-            let client = FullnodeClient::new(
-                &host,
-                cli.fullnode_port.unwrap_or(8444),
-                cli.ssl_path,
-                &None,
-            );
             migrate_plot_nft(
-                &client,
+                &fullnode_client,
                 &target_pool,
                 &Bytes32::from(launcher_id),
                 &mnemonic,
@@ -47,17 +91,10 @@ async fn main() -> Result<(), Error> {
             launcher_id,
             owner_key,
         } => {
-            let host = cli.fullnode_host.unwrap_or("localhost".to_string());
-            let client = FullnodeClient::new(
-                &host,
-                cli.fullnode_port.unwrap_or(8444),
-                cli.ssl_path,
-                &None,
-            );
             let owner_key = SecretKey::from_bytes(Bytes32::from(&owner_key).as_ref())
                 .expect("Failed to Parse Owner Secret Key");
             migrate_plot_nft_with_owner_key(
-                &client,
+                &fullnode_client,
                 &target_pool,
                 &Bytes32::from(launcher_id),
                 &owner_key,
@@ -65,14 +102,7 @@ async fn main() -> Result<(), Error> {
             .await?
         }
         RootCommands::GetPlotnftState { launcher_id } => {
-            let host = cli.fullnode_host.unwrap_or("localhost".to_string());
-            let client = FullnodeClient::new(
-                &host,
-                cli.fullnode_port.unwrap_or(8444),
-                cli.ssl_path,
-                &None,
-            );
-            get_plotnft_ready_state(&client, &Bytes32::from(launcher_id))
+            get_plotnft_ready_state(&fullnode_client, &Bytes32::from(launcher_id))
                 .await
                 .map(|_| ())?
         }
@@ -97,9 +127,63 @@ async fn main() -> Result<(), Error> {
             println!("{}", url);
         }
         RootCommands::CreateWallet { action } => match action {
-            WalletAction::WithNFT { .. } => {}
+            WalletAction::WithNFT {
+                pool_url,
+                faucet_request_url,
+                faucet_request_payload,
+            } => {
+                create_plotnft_wallet(
+                    &fullnode_client,
+                    pool_url,
+                    faucet_request_url,
+                    faucet_request_payload,
+                )
+                .await?
+            }
             WalletAction::Cold => create_cold_wallet()?,
         },
+        RootCommands::Wallet { action } => {
+            let host = cli.wallet_host.unwrap_or("localhost".to_string());
+            let client =
+                WalletClient::new(&host, cli.wallet_port.unwrap_or(9256), cli.ssl_path, None);
+            let output = match action {
+                WalletCommands::Balance { wallet_id } => {
+                    get_wallet_balance(&client, wallet_id, cli.output).await?
+                }
+                WalletCommands::Send {
+                    wallet_id,
+                    address,
+                    amount,
+                    csv,
+                    fee,
+                } => match (address, amount, csv) {
+                    (Some(address), Some(amount), None) => {
+                        send_transaction(&client, wallet_id, &address, amount, fee, cli.output)
+                            .await?
+                    }
+                    (None, None, Some(csv)) => {
+                        send_transaction_multi(&client, wallet_id, &csv, fee, cli.output).await?
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "Either address and amount or csv is required",
+                        ))
+                    }
+                },
+                WalletCommands::ListTransactions {
+                    wallet_id,
+                    start,
+                    end,
+                } => get_transactions(&client, wallet_id, start, end, cli.output).await?,
+                WalletCommands::SyncStatus => get_wallet_sync_status(&client, cli.output).await?,
+                WalletCommands::Login {
+                    fingerprint,
+                    skip_import,
+                } => log_in(&client, fingerprint, skip_import).await?,
+            };
+            println!("{output}");
+        }
     }
     Ok(())
 }
@@ -0,0 +1,239 @@
+use crate::commands::{format_json, format_key_values, format_table, OutputFormat};
+use dg_xch_clients::api::full_node::FullnodeAPI;
+use dg_xch_clients::rpc::full_node::FullnodeClient;
+use dg_xch_core::blockchain::block_record::BlockRecord;
+use dg_xch_core::blockchain::sized_bytes::{hex_to_bytes, Bytes32, SizedBytes};
+use dg_xch_keys::decode_puzzle_hash;
+use std::io::{Error, ErrorKind};
+
+pub fn parse_bytes32(hex: &str) -> Result<Bytes32, Error> {
+    let bytes = hex_to_bytes(hex).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("Invalid hex string ({hex}): {:?}", e),
+        )
+    })?;
+    if bytes.len() != 32 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Expected 32 bytes, got {} from ({hex})", bytes.len()),
+        ));
+    }
+    Ok(Bytes32::new(&bytes))
+}
+
+pub fn puzzle_hash_from_args(
+    puzzlehash: Option<String>,
+    address: Option<String>,
+) -> Result<Bytes32, Error> {
+    match (puzzlehash, address) {
+        (Some(puzzlehash), None) => parse_bytes32(&puzzlehash),
+        (None, Some(address)) => decode_puzzle_hash(&address),
+        _ => Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Exactly one of puzzlehash or address is required",
+        )),
+    }
+}
+
+pub async fn get_coin_records(
+    client: &FullnodeClient,
+    puzzle_hash: &Bytes32,
+    include_spent_coins: bool,
+    format: OutputFormat,
+) -> Result<String, Error> {
+    let records = client
+        .get_coin_records_by_puzzle_hash(puzzle_hash, Some(include_spent_coins), None, None)
+        .await?;
+    match format {
+        OutputFormat::Json => format_json(&records),
+        OutputFormat::Table => Ok(format_table(
+            &[
+                "Coin ID",
+                "Amount",
+                "Confirmed",
+                "Spent",
+                "Coinbase",
+                "Timestamp",
+            ],
+            &records
+                .iter()
+                .map(|r| {
+                    vec![
+                        r.coin.name().to_string(),
+                        r.coin.amount.to_string(),
+                        r.confirmed_block_index.to_string(),
+                        if r.spent {
+                            r.spent_block_index.to_string()
+                        } else {
+                            "-".to_string()
+                        },
+                        r.coinbase.to_string(),
+                        r.timestamp.to_string(),
+                    ]
+                })
+                .collect::<Vec<Vec<String>>>(),
+        )),
+    }
+}
+
+pub async fn get_blockchain_state(
+    client: &FullnodeClient,
+    format: OutputFormat,
+) -> Result<String, Error> {
+    let state = client.get_blockchain_state().await?;
+    match format {
+        OutputFormat::Json => format_json(&state),
+        OutputFormat::Table => Ok(format_key_values(&[
+            (
+                "Peak Height",
+                state
+                    .peak
+                    .as_ref()
+                    .map(|p| p.height.to_string())
+                    .unwrap_or("-".to_string()),
+            ),
+            (
+                "Peak Hash",
+                state
+                    .peak
+                    .as_ref()
+                    .map(|p| p.header_hash.to_string())
+                    .unwrap_or("-".to_string()),
+            ),
+            ("Synced", state.sync.synced.to_string()),
+            ("Sync Mode", state.sync.sync_mode.to_string()),
+            (
+                "Sync Progress",
+                format!(
+                    "{}/{}",
+                    state.sync.sync_progress_height, state.sync.sync_tip_height
+                ),
+            ),
+            ("Difficulty", state.difficulty.to_string()),
+            ("Sub Slot Iters", state.sub_slot_iters.to_string()),
+            ("Space", state.space.to_string()),
+            ("Mempool Size", state.mempool_size.to_string()),
+            ("Mempool Cost", state.mempool_cost.to_string()),
+            ("Node ID", state.node_id.to_string()),
+        ])),
+    }
+}
+
+pub async fn get_block(
+    client: &FullnodeClient,
+    height: Option<u32>,
+    header_hash: Option<String>,
+    format: OutputFormat,
+) -> Result<String, Error> {
+    let record = match (height, header_hash) {
+        (Some(height), None) => client.get_block_record_by_height(height).await?,
+        (None, Some(header_hash)) => {
+            client
+                .get_block_record(&parse_bytes32(&header_hash)?)
+                .await?
+        }
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Exactly one of height or header_hash is required",
+            ))
+        }
+    };
+    match format {
+        OutputFormat::Json => format_json(&record),
+        OutputFormat::Table => Ok(block_record_table(&record)),
+    }
+}
+
+fn block_record_table(record: &BlockRecord) -> String {
+    format_key_values(&[
+        ("Header Hash", record.header_hash.to_string()),
+        ("Previous Hash", record.prev_hash.to_string()),
+        ("Height", record.height.to_string()),
+        ("Weight", record.weight.to_string()),
+        ("Total Iters", record.total_iters.to_string()),
+        (
+            "Signage Point Index",
+            record.signage_point_index.to_string(),
+        ),
+        ("Farmer Puzzle Hash", record.farmer_puzzle_hash.to_string()),
+        ("Pool Puzzle Hash", record.pool_puzzle_hash.to_string()),
+        (
+            "Timestamp",
+            record
+                .timestamp
+                .map(|t| t.to_string())
+                .unwrap_or("-".to_string()),
+        ),
+        (
+            "Fees",
+            record
+                .fees
+                .map(|f| f.to_string())
+                .unwrap_or("-".to_string()),
+        ),
+    ])
+}
+
+pub async fn get_mempool_items(
+    client: &FullnodeClient,
+    format: OutputFormat,
+) -> Result<String, Error> {
+    let items = client.get_all_mempool_items().await?;
+    match format {
+        OutputFormat::Json => format_json(&items),
+        OutputFormat::Table => {
+            let mut items = items.into_values().collect::<Vec<_>>();
+            items.sort_by(|a, b| b.fee.cmp(&a.fee));
+            Ok(format_table(
+                &["Spend Bundle", "Fee", "Cost", "Spends", "Height Added"],
+                &items
+                    .iter()
+                    .map(|i| {
+                        vec![
+                            i.spend_bundle_name.to_string(),
+                            i.fee.to_string(),
+                            i.cost().to_string(),
+                            i.spend_bundle.coin_spends.len().to_string(),
+                            i.height_added_to_mempool.to_string(),
+                        ]
+                    })
+                    .collect::<Vec<Vec<String>>>(),
+            ))
+        }
+    }
+}
+
+pub async fn get_fee_estimate(
+    client: &FullnodeClient,
+    cost: Option<u64>,
+    target_times: &[u64],
+    format: OutputFormat,
+) -> Result<String, Error> {
+    let estimate = client.get_fee_estimate(cost, target_times).await?;
+    match format {
+        OutputFormat::Json => format_json(&estimate),
+        OutputFormat::Table => {
+            let mut table = format_table(
+                &["Target Time (s)", "Estimated Fee"],
+                &estimate
+                    .target_times
+                    .iter()
+                    .zip(&estimate.estimates)
+                    .map(|(t, e)| vec![t.to_string(), e.to_string()])
+                    .collect::<Vec<Vec<String>>>(),
+            );
+            table.push_str("\n\n");
+            table.push_str(&format_key_values(&[
+                ("Current Fee Rate", estimate.current_fee_rate.to_string()),
+                ("Mempool Size", estimate.mempool_size.to_string()),
+                ("Mempool Max Size", estimate.mempool_max_size.to_string()),
+                ("Mempool Fees", estimate.mempool_fees.to_string()),
+                ("Full Node Synced", estimate.full_node_synced.to_string()),
+                ("Peak Height", estimate.peak_height.to_string()),
+            ]));
+            Ok(table)
+        }
+    }
+}
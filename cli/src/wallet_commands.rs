@@ -1,3 +1,5 @@
+use crate::commands::{format_json, format_key_values, format_table, OutputFormat};
+use crate::node_commands::parse_bytes32;
 use crate::wallets::plotnft_utils::{
     get_plotnft_by_launcher_id, submit_next_state_spend_bundle,
     submit_next_state_spend_bundle_with_key, PlotNFTWallet,
@@ -7,24 +9,43 @@ use bip39::Mnemonic;
 use blst::min_pk::SecretKey;
 use dg_xch_clients::api::full_node::FullnodeAPI;
 use dg_xch_clients::api::pool::{DefaultPoolClient, PoolClient};
+use dg_xch_clients::api::wallet::WalletAPI;
 use dg_xch_clients::protocols::pool::{
-    GetPoolInfoResponse, FARMING_TO_POOL, POOL_PROTOCOL_VERSION,
+    GetPoolInfoResponse, FARMING_TO_POOL, POOL_PROTOCOL_VERSION, SELF_POOLING,
 };
 use dg_xch_clients::rpc::full_node::FullnodeClient;
+use dg_xch_clients::rpc::wallet::WalletClient;
+use dg_xch_core::blockchain::announcement::Announcement;
+use dg_xch_core::blockchain::coin::Coin;
 use dg_xch_core::blockchain::coin_spend::CoinSpend;
-use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48};
-use dg_xch_core::plots::PlotNft;
+use dg_xch_core::blockchain::pending_payment::PendingPayment;
+use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, SizedBytes};
+use dg_xch_core::blockchain::spend_bundle::SpendBundle;
+use dg_xch_core::blockchain::transaction_record::TransactionRecord;
+use dg_xch_core::blockchain::tx_status::TXStatus;
+use dg_xch_core::plots::{PlotNft, PlotNftExtraData};
 use dg_xch_core::pool::PoolState;
 use dg_xch_keys::*;
+use dg_xch_puzzles::clvm_puzzles::{
+    create_pooling_inner_puzzle, create_waiting_room_inner_puzzle, launcher_id_to_p2_puzzle_hash,
+    SINGLETON_LAUNCHER_HASH,
+};
 use dg_xch_puzzles::p2_delegated_puzzle_or_hidden_puzzle::{
     calculate_synthetic_secret_key, puzzle_hash_for_pk, DEFAULT_HIDDEN_PUZZLE_HASH,
 };
+use dg_xch_puzzles::singleton::{generate_launcher_coin, launch_conditions_and_coin_spend};
 use log::{debug, error, info};
 use std::collections::{HashMap, HashSet};
 use std::io::{Error, ErrorKind};
 use std::ops::Add;
 use std::time::{Duration, Instant};
 
+pub const PLOTNFT_DELAY_TIME: i32 = 604800;
+//Checked every 10 seconds, gives up after 30 minutes
+const PLOTNFT_CONFIRMATION_ATTEMPTS: u32 = 180;
+//Checked every 30 seconds, gives up after 1 hour
+const FUNDING_COIN_ATTEMPTS: u32 = 120;
+
 pub fn create_cold_wallet() -> Result<(), Error> {
     let mnemonic = Mnemonic::generate(24)
         .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("{:?}", e)))?;
@@ -66,6 +87,255 @@ pub fn create_cold_wallet() -> Result<(), Error> {
     Ok(())
 }
 
+pub async fn create_plotnft_wallet(
+    client: &FullnodeClient,
+    pool_url: Option<String>,
+    faucet_request_url: Option<String>,
+    faucet_request_payload: Option<String>,
+) -> Result<(), Error> {
+    let pool_url = pool_url.map(|target_pool| {
+        if target_pool.starts_with("https://") {
+            target_pool
+        } else {
+            format!("https://{}", target_pool)
+        }
+    });
+    let pool_info = match &pool_url {
+        Some(pool_url) => Some(get_pool_info(pool_url).await?),
+        None => None,
+    };
+    let mnemonic = Mnemonic::generate(24)
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("{:?}", e)))?;
+    let master_secret_key = key_from_mnemonic(&mnemonic.to_string())?;
+    let owner_sk = master_sk_to_singleton_owner_sk(&master_secret_key, 0)?;
+    let wallet_sk = master_sk_to_wallet_sk(&master_secret_key, 0)
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("MasterKey: {:?}", e)))?;
+    let wallet_puzzle_hash = puzzle_hash_for_pk(&Bytes48::from(wallet_sk.sk_to_pk().to_bytes()))?;
+    let address = encode_puzzle_hash(&wallet_puzzle_hash, "xch")?;
+    info!(
+        "Fingerprint: {}",
+        fingerprint(&master_secret_key.sk_to_pk())
+    );
+    info!("Mnemonic Phrase: {}", &mnemonic.to_string());
+    info!("Wallet Address: {address}");
+    if let Some(faucet_request_url) = faucet_request_url {
+        request_faucet_funds(&faucet_request_url, faucet_request_payload, &address).await?;
+    } else {
+        info!("Send at least 1 mojo to {address} to create the PlotNFT");
+    }
+    let pool_wallet = PlotNFTWallet::new(master_secret_key, client);
+    let funding_coin = wait_for_funding_coin(&pool_wallet).await?;
+    let pool_state = match (pool_url, pool_info) {
+        (Some(pool_url), Some(pool_info)) => PoolState {
+            owner_pubkey: Bytes48::from(owner_sk.sk_to_pk().to_bytes()),
+            pool_url: Some(pool_url),
+            relative_lock_height: pool_info.relative_lock_height,
+            state: FARMING_TO_POOL,
+            target_puzzle_hash: pool_info.target_puzzle_hash,
+            version: POOL_PROTOCOL_VERSION,
+        },
+        _ => PoolState {
+            owner_pubkey: Bytes48::from(owner_sk.sk_to_pk().to_bytes()),
+            pool_url: None,
+            relative_lock_height: 0,
+            state: SELF_POOLING,
+            target_puzzle_hash: wallet_puzzle_hash,
+            version: POOL_PROTOCOL_VERSION,
+        },
+    };
+    info!(
+        "Initial State: {}",
+        serde_json::to_string_pretty(&pool_state).unwrap_or_default()
+    );
+    let extra_data = PlotNftExtraData {
+        pool_state,
+        delay_time: PLOTNFT_DELAY_TIME,
+        delay_puzzle_hash: wallet_puzzle_hash,
+    };
+    let (launcher_id, launcher_announcement, launcher_spend) = create_plotnft_launcher_spend(
+        &funding_coin,
+        &extra_data,
+        &pool_wallet.wallet_info().constants.genesis_challenge,
+    )?;
+    let funding_record = pool_wallet
+        .generate_signed_transaction(
+            1,
+            &SINGLETON_LAUNCHER_HASH,
+            0,
+            Some(funding_coin.name()),
+            Some(vec![funding_coin]),
+            None,
+            false,
+            Some(&[launcher_announcement]),
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await?;
+    let spend_bundle = SpendBundle::aggregate(vec![
+        funding_record.spend_bundle.ok_or_else(|| {
+            Error::new(
+                ErrorKind::Other,
+                "Expected Transaction Record to have Spend bundle",
+            )
+        })?,
+        SpendBundle {
+            coin_spends: vec![launcher_spend],
+            aggregated_signature: Default::default(),
+        },
+    ])?;
+    info!("Submitting PlotNFT with LauncherID: {launcher_id}");
+    match client.push_tx(&spend_bundle).await? {
+        TXStatus::SUCCESS => {
+            info!("Transaction Submitted Successfully. Waiting for PlotNFT to be confirmed...");
+            let mut confirmed = false;
+            for _ in 0..PLOTNFT_CONFIRMATION_ATTEMPTS {
+                if let Ok(Some(plot_nft)) = get_plotnft_by_launcher_id(client, &launcher_id).await {
+                    info!(
+                        "PlotNFT confirmed at height {}",
+                        plot_nft.singleton_coin.confirmed_block_index
+                    );
+                    confirmed = true;
+                    break;
+                }
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                info!("Waiting for PlotNFT to appear...");
+            }
+            if !confirmed {
+                return Err(Error::new(
+                    ErrorKind::TimedOut,
+                    format!(
+                        "PlotNFT {launcher_id} was not confirmed, the spend may have been dropped"
+                    ),
+                ));
+            }
+        }
+        TXStatus::PENDING => {
+            return Err(Error::new(ErrorKind::Other, "Transaction is pending"));
+        }
+        TXStatus::FAILED => {
+            return Err(Error::new(ErrorKind::Other, "Failed to submit transaction"));
+        }
+    }
+    let p2_singleton_puzzle_hash = launcher_id_to_p2_puzzle_hash(
+        &launcher_id,
+        PLOTNFT_DELAY_TIME as u64,
+        &wallet_puzzle_hash,
+    )?;
+    info!("LauncherID: {launcher_id}");
+    info!(
+        "Pool Contract Address: {}",
+        encode_puzzle_hash(&p2_singleton_puzzle_hash, "xch")?
+    );
+    Ok(())
+}
+
+pub fn create_plotnft_launcher_spend(
+    funding_coin: &Coin,
+    extra_data: &PlotNftExtraData,
+    genesis_challenge: &Bytes32,
+) -> Result<(Bytes32, Announcement, CoinSpend), Error> {
+    let launcher_id = generate_launcher_coin(funding_coin, 1).name();
+    let pool_state = &extra_data.pool_state;
+    let delay_time = extra_data.delay_time as u64;
+    let waiting_room_inner_puzzle = create_waiting_room_inner_puzzle(
+        &pool_state.target_puzzle_hash,
+        pool_state.relative_lock_height,
+        &pool_state.owner_pubkey,
+        &launcher_id,
+        genesis_challenge,
+        delay_time,
+        &extra_data.delay_puzzle_hash,
+    )?;
+    let inner_puzzle = if pool_state.state == FARMING_TO_POOL {
+        create_pooling_inner_puzzle(
+            &pool_state.target_puzzle_hash,
+            &waiting_room_inner_puzzle.tree_hash(),
+            &pool_state.owner_pubkey,
+            &launcher_id,
+            genesis_challenge,
+            delay_time,
+            &extra_data.delay_puzzle_hash,
+        )?
+    } else {
+        waiting_room_inner_puzzle
+    };
+    let (_, launcher_spend) = launch_conditions_and_coin_spend(
+        funding_coin.clone(),
+        inner_puzzle,
+        extra_data.to_program(),
+        1,
+    )?;
+    let launcher_announcement = Announcement {
+        origin_info: launcher_id,
+        message: launcher_spend
+            .solution
+            .to_program()
+            .tree_hash()
+            .as_slice()
+            .to_vec(),
+    };
+    Ok((launcher_id, launcher_announcement, launcher_spend))
+}
+
+async fn request_faucet_funds(
+    faucet_request_url: &str,
+    faucet_request_payload: Option<String>,
+    address: &str,
+) -> Result<(), Error> {
+    let payload = faucet_request_payload
+        .map(|p| p.replace("{address}", address))
+        .unwrap_or_else(|| serde_json::json!({ "address": address }).to_string());
+    info!("Requesting funds from faucet: {faucet_request_url}");
+    let resp = reqwest::Client::new()
+        .post(faucet_request_url)
+        .header("Content-Type", "application/json")
+        .body(payload)
+        .send()
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, format!("Faucet request failed: {:?}", e)))?;
+    if resp.status().is_success() {
+        Ok(())
+    } else {
+        Err(Error::new(
+            ErrorKind::Other,
+            format!("Faucet request failed with status: {}", resp.status()),
+        ))
+    }
+}
+
+async fn wait_for_funding_coin(pool_wallet: &PlotNFTWallet) -> Result<Coin, Error> {
+    for _ in 0..FUNDING_COIN_ATTEMPTS {
+        if pool_wallet.sync().await? {
+            let store = pool_wallet.wallet_store();
+            let store = store.lock().await;
+            if let Some(record) = store
+                .unspent_coins
+                .values()
+                .max_by(|a, b| a.coin.amount.cmp(&b.coin.amount))
+            {
+                info!(
+                    "Found coin {} with {} mojos",
+                    record.coin.name(),
+                    record.coin.amount
+                );
+                return Ok(record.coin.clone());
+            }
+        }
+        info!("Waiting for wallet to be funded...");
+        tokio::time::sleep(Duration::from_secs(30)).await;
+    }
+    Err(Error::new(
+        ErrorKind::TimedOut,
+        "Wallet was not funded, check the faucet or send funds to the wallet and retry",
+    ))
+}
+
 pub fn keys_for_coinspends(
     coin_spends: &[CoinSpend],
     master_sk: &SecretKey,
@@ -400,3 +670,203 @@ pub async fn get_plotnft_ready_state(
         }
     }
 }
+
+pub async fn get_wallet_balance(
+    client: &WalletClient,
+    wallet_id: u32,
+    format: OutputFormat,
+) -> Result<String, Error> {
+    let balance = client.get_wallet_balance(wallet_id).await?;
+    match format {
+        OutputFormat::Json => format_json(&balance),
+        OutputFormat::Table => Ok(format_key_values(&[
+            ("Wallet ID", balance.wallet_id.to_string()),
+            (
+                "Confirmed Balance",
+                balance.confirmed_wallet_balance.to_string(),
+            ),
+            (
+                "Unconfirmed Balance",
+                balance.unconfirmed_wallet_balance.to_string(),
+            ),
+            ("Spendable Balance", balance.spendable_balance.to_string()),
+            ("Max Send Amount", balance.max_send_amount.to_string()),
+            ("Pending Change", balance.pending_change.to_string()),
+            ("Unspent Coins", balance.unspent_coin_count.to_string()),
+            (
+                "Pending Removals",
+                balance.pending_coin_removal_count.to_string(),
+            ),
+        ])),
+    }
+}
+
+pub async fn send_transaction(
+    client: &WalletClient,
+    wallet_id: u32,
+    address: &str,
+    amount: u64,
+    fee: u64,
+    format: OutputFormat,
+) -> Result<String, Error> {
+    decode_puzzle_hash(address)?;
+    let transaction = client
+        .send_transaction(wallet_id, amount, address.to_string(), fee)
+        .await?;
+    match format {
+        OutputFormat::Json => format_json(&transaction),
+        OutputFormat::Table => Ok(transaction_record_table(&transaction)),
+    }
+}
+
+pub async fn send_transaction_multi(
+    client: &WalletClient,
+    wallet_id: u32,
+    csv_path: &str,
+    fee: u64,
+    format: OutputFormat,
+) -> Result<String, Error> {
+    let payments = parse_payments_csv(&tokio::fs::read_to_string(csv_path).await?)?;
+    info!(
+        "Sending {} mojos to {} payees",
+        payments.iter().map(|p| p.amount as u128).sum::<u128>(),
+        payments.len()
+    );
+    let transaction = client
+        .send_transaction_multi(wallet_id, payments, fee)
+        .await?;
+    match format {
+        OutputFormat::Json => format_json(&transaction),
+        OutputFormat::Table => Ok(transaction_record_table(&transaction)),
+    }
+}
+
+pub fn parse_payments_csv(contents: &str) -> Result<Vec<PendingPayment>, Error> {
+    let mut payments = vec![];
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || (index == 0 && line.to_lowercase().starts_with("address"))
+        {
+            continue;
+        }
+        let (destination, amount) = line.split_once(',').ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Line {}: expected address,amount", index + 1),
+            )
+        })?;
+        let destination = destination.trim();
+        let puzzle_hash = if destination.starts_with("xch") || destination.starts_with("txch") {
+            decode_puzzle_hash(destination)
+        } else {
+            parse_bytes32(destination)
+        }
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("Line {}: {e}", index + 1)))?;
+        let amount = amount.trim().parse::<u64>().map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Line {}: invalid amount ({}): {e}",
+                    index + 1,
+                    amount.trim()
+                ),
+            )
+        })?;
+        payments.push(PendingPayment {
+            puzzle_hash,
+            amount,
+        });
+    }
+    if payments.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "No payments found in CSV",
+        ));
+    }
+    Ok(payments)
+}
+
+pub async fn get_transactions(
+    client: &WalletClient,
+    wallet_id: u32,
+    start: Option<u32>,
+    end: Option<u32>,
+    format: OutputFormat,
+) -> Result<String, Error> {
+    let transactions = client.get_transactions(wallet_id, start, end).await?;
+    match format {
+        OutputFormat::Json => format_json(&transactions),
+        OutputFormat::Table => Ok(format_table(
+            &[
+                "Transaction ID",
+                "Amount",
+                "Fee",
+                "To",
+                "Confirmed",
+                "Created",
+            ],
+            &transactions
+                .iter()
+                .map(|t| {
+                    vec![
+                        t.name.to_string(),
+                        t.amount.to_string(),
+                        t.fee_amount.to_string(),
+                        t.to_puzzle_hash.to_string(),
+                        if t.confirmed {
+                            t.confirmed_at_height.to_string()
+                        } else {
+                            "-".to_string()
+                        },
+                        t.created_at_time.to_string(),
+                    ]
+                })
+                .collect::<Vec<Vec<String>>>(),
+        )),
+    }
+}
+
+pub async fn get_wallet_sync_status(
+    client: &WalletClient,
+    format: OutputFormat,
+) -> Result<String, Error> {
+    let sync = client.get_sync_status().await?;
+    match format {
+        OutputFormat::Json => format_json(&sync),
+        OutputFormat::Table => Ok(format_key_values(&[
+            ("Synced", sync.synced.to_string()),
+            ("Syncing", sync.syncing.to_string()),
+            ("Genesis Initialized", sync.genesis_initialized.to_string()),
+        ])),
+    }
+}
+
+pub async fn log_in(
+    client: &WalletClient,
+    fingerprint: u32,
+    skip_import: bool,
+) -> Result<String, Error> {
+    let fingerprint = if skip_import {
+        client.log_in_and_skip(fingerprint).await?
+    } else {
+        client.log_in(fingerprint).await?
+    };
+    Ok(format!(
+        "Logged in to wallet with fingerprint: {fingerprint}"
+    ))
+}
+
+fn transaction_record_table(transaction: &TransactionRecord) -> String {
+    format_key_values(&[
+        ("Transaction ID", transaction.name.to_string()),
+        ("Wallet ID", transaction.wallet_id.to_string()),
+        ("Amount", transaction.amount.to_string()),
+        ("Fee", transaction.fee_amount.to_string()),
+        ("To Puzzle Hash", transaction.to_puzzle_hash.to_string()),
+        ("Additions", transaction.additions.len().to_string()),
+        ("Removals", transaction.removals.len().to_string()),
+        ("Confirmed", transaction.confirmed.to_string()),
+    ])
+}
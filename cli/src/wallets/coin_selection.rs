@@ -0,0 +1,443 @@
+use dg_xch_core::blockchain::coin::Coin;
+use dg_xch_core::blockchain::coin_record::CoinRecord;
+use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+use log::{debug, info};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+use std::io::{Error, ErrorKind};
+
+pub const DEFAULT_MAX_NUM_COINS: usize = 500;
+pub const DEFAULT_BNB_MAX_TRIES: usize = 100_000;
+
+//Picks which coins to spend for a target amount.
+//Implementations receive only coins that already passed the caller's filters
+//(excluded coins, min/max amounts, excluded amounts and pending spends)
+pub trait CoinSelector: Send + Sync {
+    fn select(
+        &self,
+        spendable: &[CoinRecord],
+        amount: u64,
+        max_coin_amount: u64,
+    ) -> Result<HashSet<Coin>, Error>;
+}
+
+//Exact match, then all smaller coins, then knapsack, then the smallest coin over the target
+#[derive(Debug, Clone, Copy)]
+pub struct DefaultCoinSelector {
+    pub max_num_coins: usize,
+}
+impl Default for DefaultCoinSelector {
+    fn default() -> Self {
+        DefaultCoinSelector {
+            max_num_coins: DEFAULT_MAX_NUM_COINS,
+        }
+    }
+}
+impl CoinSelector for DefaultCoinSelector {
+    fn select(
+        &self,
+        spendable: &[CoinRecord],
+        amount: u64,
+        max_coin_amount: u64,
+    ) -> Result<HashSet<Coin>, Error> {
+        let max_num_coins = self.max_num_coins;
+        let mut valid_spendable_coins: Vec<Coin> =
+            spendable.iter().map(|r| r.coin.clone()).collect();
+        valid_spendable_coins.sort_by(|f, s| f.amount.cmp(&s.amount));
+        match check_for_exact_match(&valid_spendable_coins, amount) {
+            Some(c) => {
+                info!("Selected coin with an exact match: {:?}", c);
+                Ok(HashSet::from([c]))
+            }
+            None => {
+                let mut smaller_coin_sum = 0; //coins smaller than target.
+                let mut smaller_coins = vec![];
+                for coin in &valid_spendable_coins {
+                    if coin.amount < amount {
+                        smaller_coin_sum += coin.amount;
+                    }
+                    smaller_coins.push(coin.clone());
+                }
+                if smaller_coin_sum == amount && smaller_coins.len() < max_num_coins && amount != 0
+                {
+                    debug!("Selected all smaller coins because they equate to an exact match of the target: {:?}", smaller_coins);
+                    Ok(HashSet::from_iter(smaller_coins.iter().cloned()))
+                } else if smaller_coin_sum < amount {
+                    let smallest_coin =
+                        select_smallest_coin_over_target(amount, &valid_spendable_coins);
+                    if let Some(smallest_coin) = smallest_coin {
+                        debug!("Selected closest greater coin: {}", smallest_coin.name());
+                        Ok(HashSet::from([smallest_coin]))
+                    } else {
+                        Err(too_many_coins(amount, max_num_coins))
+                    }
+                } else if smaller_coin_sum > amount {
+                    let mut coin_set = knapsack_coin_algorithm(
+                        &smaller_coins,
+                        amount,
+                        max_coin_amount,
+                        max_num_coins,
+                        None,
+                    );
+                    debug!("Selected coins from knapsack algorithm: {:?}", coin_set);
+                    if coin_set.is_none() {
+                        coin_set = sum_largest_coins(amount as u128, &smaller_coins);
+                        if coin_set.is_none()
+                            || coin_set.as_ref().map(|v| v.len()).unwrap_or_default()
+                                > max_num_coins
+                        {
+                            let greater_coin =
+                                select_smallest_coin_over_target(amount, &valid_spendable_coins);
+                            if let Some(greater_coin) = greater_coin {
+                                coin_set = Some(HashSet::from([greater_coin]));
+                            } else {
+                                return Err(too_many_coins(amount, max_num_coins));
+                            }
+                        }
+                    }
+                    coin_set.ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::InvalidInput,
+                            "Failed to select coins for transaction",
+                        )
+                    })
+                } else {
+                    match select_smallest_coin_over_target(amount, &valid_spendable_coins) {
+                        Some(coin) => {
+                            debug!(
+                                "Resorted to selecting smallest coin over target due to dust.: {:?}",
+                                coin
+                            );
+                            Ok(HashSet::from([coin]))
+                        }
+                        None => Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "Too many coins are required to make this transaction",
+                        )),
+                    }
+                }
+            }
+        }
+    }
+}
+
+//Searches for a set of coins summing to the amount plus at most max_waste mojos,
+//so the spend needs no change output. Fails with NotFound when no such set exists
+#[derive(Debug, Clone, Copy)]
+pub struct BranchAndBoundSelector {
+    pub max_waste: u64,
+    pub max_tries: usize,
+    pub max_num_coins: usize,
+}
+impl Default for BranchAndBoundSelector {
+    fn default() -> Self {
+        BranchAndBoundSelector {
+            max_waste: 0,
+            max_tries: DEFAULT_BNB_MAX_TRIES,
+            max_num_coins: DEFAULT_MAX_NUM_COINS,
+        }
+    }
+}
+impl CoinSelector for BranchAndBoundSelector {
+    fn select(
+        &self,
+        spendable: &[CoinRecord],
+        amount: u64,
+        _max_coin_amount: u64,
+    ) -> Result<HashSet<Coin>, Error> {
+        let mut coins: Vec<&Coin> = spendable.iter().map(|r| &r.coin).collect();
+        coins.sort_by(|f, s| s.amount.cmp(&f.amount));
+        let upper = amount as u128 + self.max_waste as u128;
+        //remaining[i] is the sum of coins[i..], used to prune branches that can never reach the amount
+        let mut remaining = vec![0u128; coins.len() + 1];
+        for i in (0..coins.len()).rev() {
+            remaining[i] = remaining[i + 1] + coins[i].amount as u128;
+        }
+        let mut selected: Vec<usize> = vec![];
+        let mut best: Option<(u128, Vec<usize>)> = None;
+        let mut tries = 0;
+        let mut index = 0;
+        let mut total = 0u128;
+        loop {
+            tries += 1;
+            let backtrack = if total > upper || total + remaining[index] < amount as u128 {
+                true
+            } else if total >= amount as u128 && !selected.is_empty() {
+                if best.as_ref().map(|(b, _)| total < *b).unwrap_or(true) {
+                    best = Some((total, selected.clone()));
+                }
+                //Adding more coins would only increase the waste
+                true
+            } else {
+                index >= coins.len() || selected.len() >= self.max_num_coins
+            };
+            if best
+                .as_ref()
+                .map(|(b, _)| *b == amount as u128)
+                .unwrap_or(false)
+                || tries >= self.max_tries
+            {
+                break;
+            }
+            if backtrack {
+                //Drop the last included coin and try the branch without it
+                match selected.pop() {
+                    Some(last) => {
+                        total -= coins[last].amount as u128;
+                        index = last + 1;
+                    }
+                    None => break,
+                }
+            } else {
+                selected.push(index);
+                total += coins[index].amount as u128;
+                index += 1;
+            }
+        }
+        match best {
+            Some((total, indexes)) => {
+                debug!(
+                    "Branch and bound selected {} coins for {amount} after {tries} tries, waste {}",
+                    indexes.len(),
+                    total - amount as u128
+                );
+                Ok(indexes.into_iter().map(|i| coins[i].clone()).collect())
+            }
+            None => Err(Error::new(
+                ErrorKind::NotFound,
+                format!("No changeless set of coins found for {amount} after {tries} tries"),
+            )),
+        }
+    }
+}
+
+//Spends the biggest coins first, minimising the number of inputs
+#[derive(Debug, Clone, Copy)]
+pub struct LargestFirstSelector {
+    pub max_num_coins: usize,
+}
+impl Default for LargestFirstSelector {
+    fn default() -> Self {
+        LargestFirstSelector {
+            max_num_coins: DEFAULT_MAX_NUM_COINS,
+        }
+    }
+}
+impl CoinSelector for LargestFirstSelector {
+    fn select(
+        &self,
+        spendable: &[CoinRecord],
+        amount: u64,
+        _max_coin_amount: u64,
+    ) -> Result<HashSet<Coin>, Error> {
+        let mut coins: Vec<&Coin> = spendable.iter().map(|r| &r.coin).collect();
+        coins.sort_by(|f, s| s.amount.cmp(&f.amount));
+        accumulate(coins, amount, self.max_num_coins)
+    }
+}
+
+//Spends the coins confirmed longest ago first, by confirmed_block_index
+#[derive(Debug, Clone, Copy)]
+pub struct OldestFirstSelector {
+    pub max_num_coins: usize,
+}
+impl Default for OldestFirstSelector {
+    fn default() -> Self {
+        OldestFirstSelector {
+            max_num_coins: DEFAULT_MAX_NUM_COINS,
+        }
+    }
+}
+impl CoinSelector for OldestFirstSelector {
+    fn select(
+        &self,
+        spendable: &[CoinRecord],
+        amount: u64,
+        _max_coin_amount: u64,
+    ) -> Result<HashSet<Coin>, Error> {
+        let mut records: Vec<&CoinRecord> = spendable.iter().collect();
+        records.sort_by(|f, s| {
+            f.confirmed_block_index
+                .cmp(&s.confirmed_block_index)
+                .then(s.coin.amount.cmp(&f.coin.amount))
+        });
+        accumulate(
+            records.into_iter().map(|r| &r.coin).collect(),
+            amount,
+            self.max_num_coins,
+        )
+    }
+}
+
+//Sweeps up every coin at or below dust_threshold (smallest first, up to max_num_coins),
+//then adds the largest remaining coins if the dust does not cover the amount.
+//An amount of 0 consolidates the dust without sending anything
+#[derive(Debug, Clone, Copy)]
+pub struct DustConsolidationSelector {
+    pub dust_threshold: u64,
+    pub max_num_coins: usize,
+}
+impl DustConsolidationSelector {
+    pub fn new(dust_threshold: u64) -> Self {
+        DustConsolidationSelector {
+            dust_threshold,
+            max_num_coins: DEFAULT_MAX_NUM_COINS,
+        }
+    }
+}
+impl CoinSelector for DustConsolidationSelector {
+    fn select(
+        &self,
+        spendable: &[CoinRecord],
+        amount: u64,
+        _max_coin_amount: u64,
+    ) -> Result<HashSet<Coin>, Error> {
+        let (mut dust, mut others): (Vec<&Coin>, Vec<&Coin>) = spendable
+            .iter()
+            .map(|r| &r.coin)
+            .partition(|c| c.amount <= self.dust_threshold);
+        dust.sort_by(|f, s| f.amount.cmp(&s.amount));
+        others.sort_by(|f, s| s.amount.cmp(&f.amount));
+        let mut selected = HashSet::new();
+        let mut total = 0u128;
+        for coin in dust.into_iter().take(self.max_num_coins) {
+            total += coin.amount as u128;
+            selected.insert(coin.clone());
+        }
+        for coin in others {
+            if total >= amount as u128 && !selected.is_empty() {
+                break;
+            }
+            if selected.len() >= self.max_num_coins {
+                return Err(too_many_coins(amount, self.max_num_coins));
+            }
+            total += coin.amount as u128;
+            selected.insert(coin.clone());
+        }
+        if total < amount as u128 || selected.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Not enough coins to consolidate for {amount}"),
+            ));
+        }
+        debug!(
+            "Consolidating {} coins at or below {} mojos",
+            selected.len(),
+            self.dust_threshold
+        );
+        Ok(selected)
+    }
+}
+
+fn accumulate(
+    ordered_coins: Vec<&Coin>,
+    amount: u64,
+    max_num_coins: usize,
+) -> Result<HashSet<Coin>, Error> {
+    let mut selected = HashSet::new();
+    let mut total = 0u128;
+    for coin in ordered_coins {
+        if selected.len() >= max_num_coins {
+            return Err(too_many_coins(amount, max_num_coins));
+        }
+        total += coin.amount as u128;
+        selected.insert(coin.clone());
+        if total >= amount as u128 {
+            return Ok(selected);
+        }
+    }
+    Err(Error::new(
+        ErrorKind::InvalidInput,
+        format!("Not enough coins to cover {amount}"),
+    ))
+}
+
+fn too_many_coins(amount: u64, max_num_coins: usize) -> Error {
+    Error::new(
+        ErrorKind::InvalidInput,
+        format!("Transaction of {amount} mojo would use more than {max_num_coins} coins. Try sending a smaller amount"),
+    )
+}
+
+fn check_for_exact_match(coin_list: &[Coin], target: u64) -> Option<Coin> {
+    for coin in coin_list {
+        if coin.amount == target {
+            return Some(coin.clone());
+        }
+    }
+    None
+}
+
+fn select_smallest_coin_over_target(target: u64, sorted_coin_list: &[Coin]) -> Option<Coin> {
+    if sorted_coin_list.first()?.amount < target {
+        None
+    } else {
+        for coin in sorted_coin_list.iter().rev() {
+            if coin.amount >= target {
+                return Some(coin.clone());
+            }
+        }
+        None
+    }
+}
+
+fn sum_largest_coins(target: u128, sorted_coins: &[Coin]) -> Option<HashSet<Coin>> {
+    let mut total_value = 0u128;
+    let mut selected_coins = HashSet::default();
+    for coin in sorted_coins {
+        total_value += coin.amount as u128;
+        selected_coins.insert(coin.clone());
+        if total_value >= target {
+            return Some(selected_coins);
+        }
+    }
+    None
+}
+
+fn knapsack_coin_algorithm(
+    smaller_coins: &[Coin],
+    target: u64,
+    max_coin_amount: u64,
+    max_num_coins: usize,
+    seed: Option<&[u8]>,
+) -> Option<HashSet<Coin>> {
+    let mut best_set_sum = max_coin_amount;
+    let mut best_set_of_coins: Option<HashSet<Coin>> = None;
+    let seed = Bytes32::new(seed.unwrap_or(b"knapsack seed"));
+    let mut rand = StdRng::from_seed(*seed.to_sized_bytes());
+    for _ in 0..1000 {
+        let mut selected_coins = HashSet::default();
+        let mut selected_coins_sum = 0;
+        let mut n_pass = 0;
+        let mut target_reached = false;
+        while n_pass < 2 && !target_reached {
+            for coin in smaller_coins {
+                if (n_pass == 0 && rand.gen::<bool>())
+                    || (n_pass == 1 && !selected_coins.contains(coin))
+                {
+                    if selected_coins.len() > max_num_coins {
+                        break;
+                    }
+                    selected_coins_sum += coin.amount;
+                    selected_coins.insert(coin.clone());
+                    match selected_coins_sum.cmp(&target) {
+                        std::cmp::Ordering::Greater => {
+                            target_reached = true;
+                            if selected_coins_sum < best_set_sum {
+                                best_set_of_coins = Some(selected_coins.clone());
+                                best_set_sum = selected_coins_sum;
+                                selected_coins_sum -= coin.amount;
+                                selected_coins.remove(coin);
+                            }
+                        }
+                        std::cmp::Ordering::Less => {}
+                        std::cmp::Ordering::Equal => return Some(selected_coins),
+                    }
+                }
+            }
+            n_pass += 1;
+        }
+    }
+    best_set_of_coins
+}
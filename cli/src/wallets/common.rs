@@ -9,9 +9,11 @@ use dg_xch_core::clvm::bls_bindings::{aggregate_verify_signature, verify_signatu
 use dg_xch_core::clvm::condition_utils::conditions_dict_for_solution;
 use dg_xch_core::consensus::constants::ConsensusConstants;
 use num_traits::cast::ToPrimitive;
+use serde::{Deserialize, Serialize};
 use std::future::Future;
 use std::io::{Error, ErrorKind};
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DerivationRecord {
     pub index: u32,
     pub puzzle_hash: Bytes32,
@@ -0,0 +1,604 @@
+use crate::wallets::coin_selection::CoinSelector;
+use crate::wallets::common::DerivationRecord;
+use crate::wallets::memory_wallet::{MemoryWalletConfig, MemoryWalletStore};
+use crate::wallets::{Wallet, WalletInfo, WalletStore};
+use async_trait::async_trait;
+use blst::min_pk::SecretKey;
+use chacha20poly1305::aead::Aead;
+use chacha20poly1305::{ChaCha20Poly1305, Key, KeyInit, Nonce};
+use dashmap::DashMap;
+use dg_xch_clients::api::full_node::FullnodeAPI;
+use dg_xch_clients::rpc::full_node::FullnodeClient;
+use dg_xch_core::blockchain::coin::Coin;
+use dg_xch_core::blockchain::coin_record::CoinRecord;
+use dg_xch_core::blockchain::coin_spend::CoinSpend;
+use dg_xch_core::blockchain::mempool_item::MempoolItem;
+use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48};
+use dg_xch_core::blockchain::transaction_record::TransactionRecord;
+use dg_xch_core::blockchain::tx_status::TXStatus;
+use log::{debug, warn};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use std::collections::{HashMap, HashSet};
+use std::fs::{read_to_string, rename, File, OpenOptions};
+use std::io::{Error, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+pub const FILE_WALLET_SCHEMA_VERSION: u32 = 1;
+const PBKDF2_ITERATIONS: u32 = 100_000;
+const COIN_RECORD_BATCH_SIZE: usize = 1000;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedSecret {
+    pub salt: String,
+    pub iterations: u32,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+impl EncryptedSecret {
+    pub fn encrypt(secret: &[u8], passphrase: &str) -> Result<Self, Error> {
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt, PBKDF2_ITERATIONS));
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), secret)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("Failed to encrypt: {:?}", e)))?;
+        Ok(EncryptedSecret {
+            salt: hex::encode(salt),
+            iterations: PBKDF2_ITERATIONS,
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+    pub fn decrypt(&self, passphrase: &str) -> Result<Vec<u8>, Error> {
+        let decode = |value: &str| {
+            hex::decode(value).map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid Encrypted Secret: {:?}", e),
+                )
+            })
+        };
+        let nonce = decode(&self.nonce)?;
+        if nonce.len() != 12 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid Nonce Length: {}", nonce.len()),
+            ));
+        }
+        let cipher = ChaCha20Poly1305::new(&derive_key(
+            passphrase,
+            &decode(&self.salt)?,
+            self.iterations,
+        ));
+        cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                decode(&self.ciphertext)?.as_slice(),
+            )
+            .map_err(|_| Error::new(ErrorKind::PermissionDenied, "Invalid Passphrase"))
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Key {
+    let mut key = Key::default();
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    key
+}
+
+#[derive(Serialize, Deserialize)]
+struct FileWalletData {
+    schema_version: u32,
+    master_sk: EncryptedSecret,
+    current_index: u32,
+    sync_height: u32,
+    derivation_records: Vec<DerivationRecord>,
+    coin_records: Vec<CoinRecord>,
+    transactions: Vec<TransactionRecord>,
+}
+
+fn migrate(mut value: Value) -> Result<FileWalletData, Error> {
+    let version = value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Missing Wallet Schema Version"))?
+        as u32;
+    if version > FILE_WALLET_SCHEMA_VERSION {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Wallet Schema Version {version} is newer than supported version {FILE_WALLET_SCHEMA_VERSION}"
+            ),
+        ));
+    }
+    for from_version in version..FILE_WALLET_SCHEMA_VERSION {
+        value = migrate_from(from_version, value)?;
+    }
+    serde_json::from_value(value).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("Failed to parse Wallet File: {:?}", e),
+        )
+    })
+}
+
+//Upgrades a wallet file from `version` to `version + 1`, add a step here when the schema changes
+fn migrate_from(version: u32, _value: Value) -> Result<Value, Error> {
+    Err(Error::new(
+        ErrorKind::InvalidData,
+        format!("No Migration from Wallet Schema Version {version}"),
+    ))
+}
+
+//Writes to a temp file, syncs it and renames it over the target,
+//so a crash leaves either the old or the new file but never a partial one
+fn write_file_atomic(path: &Path, data: &[u8]) -> Result<(), Error> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Wallet Path has no File Name"))?;
+    let tmp_path = path.with_file_name(format!("{}.tmp", file_name.to_string_lossy()));
+    {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&tmp_path)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+    }
+    rename(&tmp_path, path)?;
+    #[cfg(unix)]
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        File::open(parent)?.sync_all()?;
+    }
+    Ok(())
+}
+
+//Skips snapshots older than the last one written, so overlapping saves never roll the file back
+fn write_snapshot(
+    path: &Path,
+    last_saved: &std::sync::Mutex<u64>,
+    seq: u64,
+    data: &[u8],
+) -> Result<(), Error> {
+    let mut last_saved = last_saved
+        .lock()
+        .map_err(|e| Error::new(ErrorKind::Other, format!("Save Lock Poisoned: {:?}", e)))?;
+    if seq <= *last_saved {
+        return Ok(());
+    }
+    write_file_atomic(path, data)?;
+    *last_saved = seq;
+    Ok(())
+}
+
+pub struct FileWalletStore {
+    path: PathBuf,
+    master_sk: EncryptedSecret,
+    inner: MemoryWalletStore,
+    derivation_records: DashMap<Bytes32, DerivationRecord>,
+    sync_height: u32,
+    transactions: Vec<TransactionRecord>,
+    save_seq: AtomicU64,
+    last_saved: Arc<std::sync::Mutex<u64>>,
+}
+impl FileWalletStore {
+    pub fn create(path: &Path, master_sk: SecretKey, passphrase: &str) -> Result<Self, Error> {
+        if path.exists() {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("Wallet File already exists: {:?}", path),
+            ));
+        }
+        let store = Self {
+            path: path.to_path_buf(),
+            master_sk: EncryptedSecret::encrypt(&master_sk.to_bytes(), passphrase)?,
+            inner: MemoryWalletStore::new(master_sk, 0),
+            derivation_records: Default::default(),
+            sync_height: 0,
+            transactions: vec![],
+            save_seq: Default::default(),
+            last_saved: Default::default(),
+        };
+        let (seq, json) = store.snapshot()?;
+        write_snapshot(&store.path, &store.last_saved, seq, &json)?;
+        Ok(store)
+    }
+    pub fn open(path: &Path, passphrase: &str) -> Result<Self, Error> {
+        let value: Value = serde_json::from_str(&read_to_string(path)?).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Failed to parse Wallet File: {:?}", e),
+            )
+        })?;
+        let data = migrate(value)?;
+        let master_sk = SecretKey::from_bytes(&data.master_sk.decrypt(passphrase)?)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, format!("MasterKey: {:?}", e)))?;
+        let mut inner = MemoryWalletStore::new(master_sk, data.current_index);
+        inner.update_coin_records(data.coin_records);
+        Ok(Self {
+            path: path.to_path_buf(),
+            master_sk: data.master_sk,
+            inner,
+            derivation_records: data
+                .derivation_records
+                .into_iter()
+                .map(|r| (r.puzzle_hash, r))
+                .collect(),
+            sync_height: data.sync_height,
+            transactions: data.transactions,
+            save_seq: Default::default(),
+            last_saved: Default::default(),
+        })
+    }
+    //Serializes the wallet on the caller, the file write and fsync run on the blocking pool
+    pub async fn save(&self) -> Result<(), Error> {
+        let (seq, json) = self.snapshot()?;
+        let path = self.path.clone();
+        let last_saved = self.last_saved.clone();
+        tokio::task::spawn_blocking(move || write_snapshot(&path, &last_saved, seq, &json))
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("Wallet Save Failed: {:?}", e)))?
+    }
+    fn snapshot(&self) -> Result<(u64, Vec<u8>), Error> {
+        let seq = self.save_seq.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut derivation_records: Vec<DerivationRecord> = self
+            .derivation_records
+            .iter()
+            .map(|r| r.value().clone())
+            .collect();
+        derivation_records.sort_by_key(|r| (r.index, r.hardened));
+        let data = FileWalletData {
+            schema_version: FILE_WALLET_SCHEMA_VERSION,
+            master_sk: self.master_sk.clone(),
+            current_index: self.inner.current_index.load(Ordering::Relaxed),
+            sync_height: self.sync_height(),
+            derivation_records,
+            coin_records: self
+                .inner
+                .unspent_coins
+                .values()
+                .chain(self.inner.spent_coins.values())
+                .cloned()
+                .collect(),
+            transactions: self.transactions.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&data).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Failed to serialize Wallet File: {:?}", e),
+            )
+        })?;
+        Ok((seq, json))
+    }
+    pub async fn change_passphrase(&mut self, passphrase: &str) -> Result<(), Error> {
+        self.master_sk = EncryptedSecret::encrypt(&self.inner.master_sk.to_bytes(), passphrase)?;
+        self.save().await
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    pub fn memory_store(&self) -> &MemoryWalletStore {
+        &self.inner
+    }
+    pub fn sync_height(&self) -> u32 {
+        self.sync_height
+    }
+    pub fn is_our_puzzle_hash(&self, puzzle_hash: &Bytes32) -> bool {
+        self.derivation_records.contains_key(puzzle_hash)
+            || self.inner.is_our_puzzle_hash(puzzle_hash)
+    }
+    pub fn derivation_records(&self) -> Vec<DerivationRecord> {
+        self.derivation_records
+            .iter()
+            .map(|r| r.value().clone())
+            .collect()
+    }
+    pub async fn update_chain_state(
+        &mut self,
+        coin_records: Vec<CoinRecord>,
+        sync_height: u32,
+    ) -> Result<(), Error> {
+        self.inner.update_coin_records(coin_records);
+        self.sync_height = sync_height;
+        self.save().await
+    }
+    pub fn update_pending(&mut self, mempool_items: impl IntoIterator<Item = MempoolItem>) {
+        self.inner.update_pending(mempool_items);
+    }
+    pub fn transactions(&self) -> &[TransactionRecord] {
+        &self.transactions
+    }
+    pub async fn add_transaction(&mut self, transaction: TransactionRecord) -> Result<(), Error> {
+        match self
+            .transactions
+            .iter_mut()
+            .find(|t| t.name == transaction.name)
+        {
+            Some(existing) => *existing = transaction,
+            None => self.transactions.push(transaction),
+        }
+        self.save().await
+    }
+    //Returns true when the record was not already known
+    fn add_derivation_record(&self, record: &DerivationRecord) -> bool {
+        self.derivation_records
+            .insert(record.puzzle_hash, record.clone())
+            .is_none()
+    }
+}
+#[async_trait]
+impl WalletStore for FileWalletStore {
+    fn get_master_sk(&self) -> &SecretKey {
+        self.inner.get_master_sk()
+    }
+
+    async fn get_max_send_amount(&self) -> u128 {
+        self.inner.get_max_send_amount().await
+    }
+
+    async fn get_confirmed_balance(&self) -> u128 {
+        self.inner.get_confirmed_balance().await
+    }
+
+    async fn get_unconfirmed_balance(&self) -> u128 {
+        self.inner.get_unconfirmed_balance().await
+    }
+
+    async fn get_spendable_balance(&self) -> u128 {
+        self.inner.get_spendable_balance().await
+    }
+
+    async fn get_pending_change_balance(&self) -> u128 {
+        self.inner.get_pending_change_balance().await
+    }
+
+    async fn get_unused_derivation_record(
+        &self,
+        hardened: bool,
+    ) -> Result<DerivationRecord, Error> {
+        let record = self.inner.get_unused_derivation_record(hardened).await?;
+        if self.add_derivation_record(&record) {
+            self.save().await?;
+        }
+        Ok(record)
+    }
+
+    async fn get_derivation_record(&self, hardened: bool) -> Result<DerivationRecord, Error> {
+        let record = self.inner.get_derivation_record(hardened).await?;
+        self.add_derivation_record(&record);
+        Ok(record)
+    }
+
+    async fn get_derivation_record_at_index(
+        &self,
+        index: u32,
+        hardened: bool,
+    ) -> Result<DerivationRecord, Error> {
+        let record = self
+            .inner
+            .get_derivation_record_at_index(index, hardened)
+            .await?;
+        self.add_derivation_record(&record);
+        Ok(record)
+    }
+
+    async fn select_coins_with_strategy(
+        &self,
+        amount: u64,
+        exclude: Option<&[Coin]>,
+        min_coin_amount: Option<u64>,
+        max_coin_amount: u64,
+        exclude_coin_amounts: Option<&[u64]>,
+        strategy: &dyn CoinSelector,
+    ) -> Result<HashSet<Coin>, Error> {
+        self.inner
+            .select_coins_with_strategy(
+                amount,
+                exclude,
+                min_coin_amount,
+                max_coin_amount,
+                exclude_coin_amounts,
+                strategy,
+            )
+            .await
+    }
+
+    async fn populate_secret_key_for_puzzle_hash(
+        &self,
+        puz_hash: &Bytes32,
+    ) -> Result<Bytes48, Error> {
+        //Known records are re-derived directly instead of searching the first 100 indexes
+        let known = self
+            .derivation_records
+            .get(puz_hash)
+            .map(|r| (r.index, r.hardened));
+        if let Some((index, hardened)) = known {
+            if !self.inner.is_our_puzzle_hash(puz_hash) {
+                self.inner
+                    .get_derivation_record_at_index(index, hardened)
+                    .await?;
+            }
+        }
+        self.inner
+            .populate_secret_key_for_puzzle_hash(puz_hash)
+            .await
+    }
+
+    async fn populate_secret_keys_for_coin_spends(
+        &self,
+        coin_spends: &[CoinSpend],
+    ) -> Result<(), Error> {
+        for coin_spend in coin_spends {
+            self.populate_secret_key_for_puzzle_hash(&coin_spend.coin.puzzle_hash)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn secret_key_for_public_key(&self, public_key: &Bytes48) -> Result<SecretKey, Error> {
+        self.inner.secret_key_for_public_key(public_key).await
+    }
+}
+
+//A wallet that keeps its keys, coins and history on disk between restarts
+pub struct FileWallet {
+    info: WalletInfo<FileWalletStore>,
+    pub config: MemoryWalletConfig,
+    pub fullnode_client: FullnodeClient,
+    synced: AtomicBool,
+}
+impl FileWallet {
+    //Pushes a signed transaction and records it in the wallet history unless the node rejected it
+    pub async fn push_transaction(
+        &self,
+        transaction: TransactionRecord,
+    ) -> Result<TXStatus, Error> {
+        let spend_bundle = transaction.spend_bundle.as_ref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "Transaction has no spend bundle to push",
+            )
+        })?;
+        let status = self.fullnode_client.push_tx(spend_bundle).await?;
+        if status != TXStatus::FAILED {
+            self.info
+                .wallet_store
+                .lock()
+                .await
+                .add_transaction(transaction)
+                .await?;
+        }
+        Ok(status)
+    }
+    async fn fetch_coin_records(
+        &self,
+        puzzle_hashes: &[Bytes32],
+    ) -> Result<Vec<CoinRecord>, Error> {
+        let mut coin_records = vec![];
+        for chunk in puzzle_hashes.chunks(COIN_RECORD_BATCH_SIZE) {
+            coin_records.extend(
+                self.fullnode_client
+                    .get_coin_records_by_puzzle_hashes(chunk, Some(true), None, None)
+                    .await?,
+            );
+        }
+        Ok(coin_records)
+    }
+    //Checks every persisted puzzle hash, then keeps deriving new indexes
+    //until gap_limit consecutive indexes past the last used one have no coins
+    async fn sync_with_fullnode(&self) -> Result<bool, Error> {
+        let state = self.fullnode_client.get_blockchain_state().await?;
+        if !state.sync.synced {
+            warn!("Fullnode is not synced, skipping wallet sync");
+            return Ok(false);
+        }
+        let gap_limit = self.config.gap_limit.max(1);
+        let store = self.info.wallet_store.clone();
+        let known: HashMap<Bytes32, u32> = store
+            .lock()
+            .await
+            .derivation_records()
+            .into_iter()
+            .map(|r| (r.puzzle_hash, r.index))
+            .collect();
+        let mut scanned = known.values().map(|i| i + 1).max().unwrap_or_default();
+        let mut coin_records = self
+            .fetch_coin_records(&known.keys().copied().collect::<Vec<Bytes32>>())
+            .await?;
+        let mut next_unused = coin_records
+            .iter()
+            .filter_map(|r| known.get(&r.coin.puzzle_hash))
+            .map(|i| i + 1)
+            .max()
+            .unwrap_or_default();
+        while scanned - next_unused < gap_limit {
+            let mut puzzle_hashes = HashMap::new();
+            {
+                let store = store.lock().await;
+                for index in scanned..scanned + gap_limit {
+                    for hardened in [false, true] {
+                        let record = store
+                            .get_derivation_record_at_index(index, hardened)
+                            .await?;
+                        puzzle_hashes.insert(record.puzzle_hash, record.index);
+                    }
+                }
+            }
+            scanned += gap_limit;
+            let records = self
+                .fetch_coin_records(&puzzle_hashes.keys().copied().collect::<Vec<Bytes32>>())
+                .await?;
+            for record in records {
+                if let Some(index) = puzzle_hashes.get(&record.coin.puzzle_hash) {
+                    next_unused = next_unused.max(index + 1);
+                }
+                coin_records.push(record);
+            }
+        }
+        let mempool_items = self.fullnode_client.get_all_mempool_items().await?;
+        let mut store = store.lock().await;
+        store
+            .inner
+            .current_index
+            .store(next_unused, Ordering::Relaxed);
+        debug!(
+            "Scanned {scanned} derivation indexes, found {} coin records",
+            coin_records.len()
+        );
+        let sync_height = state.peak.map(|p| p.height).unwrap_or_default();
+        store.update_chain_state(coin_records, sync_height).await?;
+        store.update_pending(mempool_items.into_values());
+        Ok(true)
+    }
+}
+#[async_trait]
+impl Wallet<FileWalletStore, MemoryWalletConfig> for FileWallet {
+    fn create(info: WalletInfo<FileWalletStore>, config: MemoryWalletConfig) -> Self {
+        let fullnode_client = FullnodeClient::new(
+            &config.fullnode_host,
+            config.fullnode_port,
+            config.fullnode_ssl_path.clone(),
+            &config.additional_headers,
+        );
+        Self {
+            info,
+            config,
+            fullnode_client,
+            synced: AtomicBool::new(false),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.info.name
+    }
+
+    async fn sync(&self) -> Result<bool, Error> {
+        let synced = self.sync_with_fullnode().await;
+        self.synced
+            .store(*synced.as_ref().unwrap_or(&false), Ordering::Relaxed);
+        synced
+    }
+
+    fn is_synced(&self) -> bool {
+        self.synced.load(Ordering::Relaxed)
+    }
+
+    fn wallet_info(&self) -> &WalletInfo<FileWalletStore> {
+        &self.info
+    }
+
+    fn wallet_store(&self) -> Arc<Mutex<FileWalletStore>> {
+        self.info.wallet_store.clone()
+    }
+}
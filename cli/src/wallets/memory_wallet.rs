@@ -1,32 +1,37 @@
+use crate::wallets::coin_selection::CoinSelector;
 use crate::wallets::common::DerivationRecord;
 use crate::wallets::{SecretKeyStore, Wallet, WalletInfo, WalletStore};
 use async_trait::async_trait;
 use blst::min_pk::SecretKey;
 use dashmap::DashMap;
+use dg_xch_clients::api::full_node::FullnodeAPI;
 use dg_xch_clients::rpc::full_node::FullnodeClient;
 use dg_xch_core::blockchain::coin::Coin;
 use dg_xch_core::blockchain::coin_record::CoinRecord;
 use dg_xch_core::blockchain::coin_spend::CoinSpend;
-use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, SizedBytes};
+use dg_xch_core::blockchain::mempool_item::MempoolItem;
+use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48};
 use dg_xch_core::blockchain::wallet_type::WalletType;
 use dg_xch_keys::{master_sk_to_wallet_sk, master_sk_to_wallet_sk_unhardened};
 use dg_xch_puzzles::p2_delegated_puzzle_or_hidden_puzzle::{
     calculate_synthetic_secret_key, puzzle_hash_for_pk, DEFAULT_HIDDEN_PUZZLE_HASH,
 };
-use log::{debug, error, info};
-use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
+use log::{debug, error, info, warn};
 use std::collections::{HashMap, HashSet};
 use std::io::{Error, ErrorKind};
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+pub const DEFAULT_GAP_LIMIT: u32 = 100;
+
 pub struct MemoryWalletConfig {
     pub fullnode_host: String,
     pub fullnode_port: u16,
     pub fullnode_ssl_path: Option<String>,
     pub additional_headers: Option<HashMap<String, String>>,
+    //Number of consecutive unused derivation indexes scanned before sync stops
+    pub gap_limit: u32,
 }
 
 pub struct MemoryWalletStore {
@@ -34,6 +39,12 @@ pub struct MemoryWalletStore {
     pub current_index: AtomicU32,
     pub spent_coins: HashMap<Bytes32, CoinRecord>,
     pub unspent_coins: HashMap<Bytes32, CoinRecord>,
+    //Coins of ours being spent by mempool items
+    pub pending_removals: HashMap<Bytes32, Coin>,
+    //Coins being created for us by mempool items
+    pub pending_additions: HashMap<Bytes32, Coin>,
+    //The subset of pending_additions created by spends of our own coins
+    pub pending_change: HashMap<Bytes32, Coin>,
     derivation_records: DashMap<Bytes32, DerivationRecord>,
     keys_for_ph: DashMap<Bytes32, (Bytes32, Bytes48)>,
     secret_key_store: SecretKeyStore,
@@ -45,11 +56,52 @@ impl MemoryWalletStore {
             current_index: AtomicU32::new(starting_index),
             spent_coins: Default::default(),
             unspent_coins: Default::default(),
+            pending_removals: Default::default(),
+            pending_additions: Default::default(),
+            pending_change: Default::default(),
             keys_for_ph: Default::default(),
             derivation_records: Default::default(),
             secret_key_store: Default::default(),
         }
     }
+    pub fn is_our_puzzle_hash(&self, puzzle_hash: &Bytes32) -> bool {
+        self.keys_for_ph.contains_key(puzzle_hash)
+    }
+    pub fn update_coin_records(&mut self, coin_records: Vec<CoinRecord>) {
+        self.spent_coins.clear();
+        self.unspent_coins.clear();
+        for record in coin_records {
+            if record.spent {
+                self.spent_coins.insert(record.coin.name(), record);
+            } else {
+                self.unspent_coins.insert(record.coin.name(), record);
+            }
+        }
+    }
+    pub fn update_pending(&mut self, mempool_items: impl IntoIterator<Item = MempoolItem>) {
+        self.pending_removals.clear();
+        self.pending_additions.clear();
+        self.pending_change.clear();
+        for item in mempool_items {
+            let mut spends_ours = false;
+            for coin in item.spend_bundle.removals() {
+                if self.unspent_coins.contains_key(&coin.name())
+                    || self.is_our_puzzle_hash(&coin.puzzle_hash)
+                {
+                    spends_ours = true;
+                    self.pending_removals.insert(coin.name(), coin);
+                }
+            }
+            for coin in item.additions() {
+                if self.is_our_puzzle_hash(&coin.puzzle_hash) {
+                    if spends_ours {
+                        self.pending_change.insert(coin.name(), coin.clone());
+                    }
+                    self.pending_additions.insert(coin.name(), coin);
+                }
+            }
+        }
+    }
 }
 #[async_trait]
 impl WalletStore for MemoryWalletStore {
@@ -62,18 +114,32 @@ impl WalletStore for MemoryWalletStore {
             0
         } else {
             self.unspent_coins
-                .values()
-                .map(|v| v.coin.amount as u128)
+                .iter()
+                .filter(|(name, _)| !self.pending_removals.contains_key(name))
+                .map(|(_, v)| v.coin.amount as u128)
                 .sum()
         }
     }
 
     async fn get_confirmed_balance(&self) -> u128 {
-        todo!()
+        self.unspent_coins
+            .values()
+            .map(|v| v.coin.amount as u128)
+            .sum()
     }
 
     async fn get_unconfirmed_balance(&self) -> u128 {
-        todo!()
+        let removed: u128 = self
+            .pending_removals
+            .values()
+            .map(|c| c.amount as u128)
+            .sum();
+        let added: u128 = self
+            .pending_additions
+            .values()
+            .map(|c| c.amount as u128)
+            .sum();
+        (self.get_confirmed_balance().await + added).saturating_sub(removed)
     }
 
     async fn get_spendable_balance(&self) -> u128 {
@@ -81,7 +147,7 @@ impl WalletStore for MemoryWalletStore {
     }
 
     async fn get_pending_change_balance(&self) -> u128 {
-        todo!()
+        self.pending_change.values().map(|c| c.amount as u128).sum()
     }
 
     async fn get_unused_derivation_record(
@@ -106,7 +172,7 @@ impl WalletStore for MemoryWalletStore {
             pubkey,
             wallet_type: WalletType::PoolingWallet,
             wallet_id: 1,
-            hardened: false,
+            hardened,
         })
     }
 
@@ -129,7 +195,7 @@ impl WalletStore for MemoryWalletStore {
             pubkey,
             wallet_type: WalletType::PoolingWallet,
             wallet_id: 1,
-            hardened: false,
+            hardened,
         })
     }
 
@@ -155,17 +221,18 @@ impl WalletStore for MemoryWalletStore {
             pubkey,
             wallet_type: WalletType::PoolingWallet,
             wallet_id: 1,
-            hardened: false,
+            hardened,
         })
     }
 
-    async fn select_coins(
+    async fn select_coins_with_strategy(
         &self,
         amount: u64,
         exclude: Option<&[Coin]>,
         min_coin_amount: Option<u64>,
         max_coin_amount: u64,
         exclude_coin_amounts: Option<&[u64]>,
+        strategy: &dyn CoinSelector,
     ) -> Result<HashSet<Coin>, Error> {
         let spendable_amount = self.get_spendable_balance().await;
         let exclude = exclude.unwrap_or_default();
@@ -175,11 +242,12 @@ impl WalletStore for MemoryWalletStore {
             Err(Error::new(ErrorKind::InvalidInput, format!("Can't select amount higher than our spendable balance.  Amount: {amount}, spendable: {spendable_amount}")))
         } else {
             debug!("About to select coins for amount {amount}");
-            let max_num_coins = 500;
             let mut sum_spendable_coins = 0;
-            let mut valid_spendable_coins: Vec<Coin> = vec![];
+            let mut valid_spendable_coins: Vec<CoinRecord> = vec![];
             for coin_record in self.unspent_coins.values() {
-                if exclude.contains(&coin_record.coin) {
+                if exclude.contains(&coin_record.coin)
+                    || self.pending_removals.contains_key(&coin_record.coin.name())
+                {
                     continue;
                 }
                 if coin_record.coin.amount < min_coin_amount
@@ -191,7 +259,7 @@ impl WalletStore for MemoryWalletStore {
                     continue;
                 }
                 sum_spendable_coins += coin_record.coin.amount;
-                valid_spendable_coins.push(coin_record.coin.clone());
+                valid_spendable_coins.push(coin_record.clone());
             }
             if sum_spendable_coins < amount {
                 return Err(Error::new(ErrorKind::InvalidInput, format!("Transaction for {amount} is greater than spendable balance of {sum_spendable_coins}. There may be other transactions pending or our minimum coin amount is too high.")));
@@ -199,82 +267,7 @@ impl WalletStore for MemoryWalletStore {
             if amount == 0 && sum_spendable_coins == 0 {
                 return Err(Error::new(ErrorKind::InvalidInput, "No coins available to spend, you can not create a coin with an amount of 0, without already having coins."));
             }
-            valid_spendable_coins.sort_by(|f, s| f.amount.cmp(&s.amount));
-            match check_for_exact_match(&valid_spendable_coins, amount) {
-                Some(c) => {
-                    info!("Selected coin with an exact match: {:?}", c);
-                    Ok(HashSet::from([c]))
-                }
-                None => {
-                    let mut smaller_coin_sum = 0; //coins smaller than target.
-                    let mut smaller_coins = vec![];
-                    for coin in &valid_spendable_coins {
-                        if coin.amount < amount {
-                            smaller_coin_sum += coin.amount;
-                        }
-                        smaller_coins.push(coin.clone());
-                    }
-                    if smaller_coin_sum == amount
-                        && smaller_coins.len() < max_num_coins
-                        && amount != 0
-                    {
-                        debug!("Selected all smaller coins because they equate to an exact match of the target: {:?}", smaller_coins);
-                        Ok(HashSet::from_iter(smaller_coins.iter().cloned()))
-                    } else if smaller_coin_sum < amount {
-                        let smallest_coin =
-                            select_smallest_coin_over_target(amount, &valid_spendable_coins);
-                        if let Some(smallest_coin) = smallest_coin {
-                            debug!("Selected closest greater coin: {}", smallest_coin.name());
-                            Ok(HashSet::from([smallest_coin]))
-                        } else {
-                            return Err(Error::new(ErrorKind::InvalidInput, "Transaction of {amount} mojo would use more than {max_num_coins} coins. Try sending a smaller amount"));
-                        }
-                    } else if smaller_coin_sum > amount {
-                        let mut coin_set = knapsack_coin_algorithm(
-                            &smaller_coins,
-                            amount,
-                            max_coin_amount,
-                            max_num_coins,
-                            None,
-                        );
-                        debug!("Selected coins from knapsack algorithm: {:?}", coin_set);
-                        if coin_set.is_none() {
-                            coin_set = sum_largest_coins(amount as u128, &smaller_coins);
-                            if coin_set.is_none()
-                                || coin_set.as_ref().map(|v| v.len()).unwrap_or_default()
-                                    > max_num_coins
-                            {
-                                let greater_coin = select_smallest_coin_over_target(
-                                    amount,
-                                    &valid_spendable_coins,
-                                );
-                                if let Some(greater_coin) = greater_coin {
-                                    coin_set = Some(HashSet::from([greater_coin]));
-                                } else {
-                                    return Err(Error::new(ErrorKind::InvalidInput, "Transaction of {amount} mojo would use more than {max_num_coins} coins. Try sending a smaller amount"));
-                                }
-                            }
-                        }
-                        coin_set.ok_or_else(|| {
-                            Error::new(
-                                ErrorKind::InvalidInput,
-                                "Failed to select coins for transaction",
-                            )
-                        })
-                    } else {
-                        match select_smallest_coin_over_target(amount, &valid_spendable_coins) {
-                            Some(coin) => {
-                                debug!("Resorted to selecting smallest coin over target due to dust.: {:?}", coin);
-                                Ok(HashSet::from([coin]))
-                            }
-                            None => Err(Error::new(
-                                ErrorKind::InvalidInput,
-                                "Too many coins are required to make this transaction",
-                            )),
-                        }
-                    }
-                }
-            }
+            strategy.select(&valid_spendable_coins, amount, max_coin_amount)
         }
     }
 
@@ -339,95 +332,78 @@ impl WalletStore for MemoryWalletStore {
     }
 }
 
-fn check_for_exact_match(coin_list: &[Coin], target: u64) -> Option<Coin> {
-    for coin in coin_list {
-        if coin.amount == target {
-            return Some(coin.clone());
-        }
-    }
-    None
+pub struct MemoryWallet {
+    //A wallet that is lost on restarts
+    info: WalletInfo<MemoryWalletStore>,
+    pub config: MemoryWalletConfig,
+    pub fullnode_client: FullnodeClient,
+    synced: AtomicBool,
 }
-
-fn select_smallest_coin_over_target(target: u64, sorted_coin_list: &[Coin]) -> Option<Coin> {
-    if sorted_coin_list[0].amount < target {
-        None
-    } else {
-        for coin in sorted_coin_list.iter().rev() {
-            if coin.amount >= target {
-                return Some(coin.clone());
+impl MemoryWallet {
+    //Walks derivation indexes from 0 until gap_limit consecutive indexes have no coins,
+    //leaving current_index on the first unused index
+    async fn scan_coin_records(&self) -> Result<Vec<CoinRecord>, Error> {
+        let gap_limit = self.config.gap_limit.max(1);
+        let store = self.info.wallet_store.clone();
+        store.lock().await.current_index.store(0, Ordering::Relaxed);
+        let mut coin_records = vec![];
+        let mut scanned = 0u32;
+        let mut next_unused = 0u32;
+        while scanned - next_unused < gap_limit {
+            let mut puzzle_hashes = HashMap::new();
+            {
+                let store = store.lock().await;
+                for _ in 0..gap_limit {
+                    let record = store.get_unused_derivation_record(false).await?;
+                    let hardened = store
+                        .get_derivation_record_at_index(record.index, true)
+                        .await?;
+                    puzzle_hashes.insert(record.puzzle_hash, record.index);
+                    puzzle_hashes.insert(hardened.puzzle_hash, hardened.index);
+                }
             }
-        }
-        None
-    }
-}
-
-fn sum_largest_coins(target: u128, sorted_coins: &[Coin]) -> Option<HashSet<Coin>> {
-    let mut total_value = 0u128;
-    let mut selected_coins = HashSet::default();
-    for coin in sorted_coins {
-        total_value += coin.amount as u128;
-        selected_coins.insert(coin.clone());
-        if total_value >= target {
-            return Some(selected_coins);
-        }
-    }
-    None
-}
-
-fn knapsack_coin_algorithm(
-    smaller_coins: &[Coin],
-    target: u64,
-    max_coin_amount: u64,
-    max_num_coins: usize,
-    seed: Option<&[u8]>,
-) -> Option<HashSet<Coin>> {
-    let mut best_set_sum = max_coin_amount;
-    let mut best_set_of_coins: Option<HashSet<Coin>> = None;
-    let seed = Bytes32::new(seed.unwrap_or(b"knapsack seed"));
-    let mut rand = StdRng::from_seed(*seed.to_sized_bytes());
-    for _ in 0..1000 {
-        let mut selected_coins = HashSet::default();
-        let mut selected_coins_sum = 0;
-        let mut n_pass = 0;
-        let mut target_reached = false;
-        while n_pass < 2 && !target_reached {
-            for coin in smaller_coins {
-                if (n_pass == 0 && rand.gen::<bool>())
-                    || (n_pass == 1 && !selected_coins.contains(coin))
-                {
-                    if selected_coins.len() > max_num_coins {
-                        break;
-                    }
-                    selected_coins_sum += coin.amount;
-                    selected_coins.insert(coin.clone());
-                    match selected_coins_sum.cmp(&target) {
-                        std::cmp::Ordering::Greater => {
-                            target_reached = true;
-                            if selected_coins_sum < best_set_sum {
-                                best_set_of_coins = Some(selected_coins.clone());
-                                best_set_sum = selected_coins_sum;
-                                selected_coins_sum -= coin.amount;
-                                selected_coins.remove(coin);
-                            }
-                        }
-                        std::cmp::Ordering::Less => {}
-                        std::cmp::Ordering::Equal => return Some(selected_coins),
-                    }
+            scanned += gap_limit;
+            let records = self
+                .fullnode_client
+                .get_coin_records_by_puzzle_hashes(
+                    &puzzle_hashes.keys().copied().collect::<Vec<Bytes32>>(),
+                    Some(true),
+                    None,
+                    None,
+                )
+                .await?;
+            for record in records {
+                if let Some(index) = puzzle_hashes.get(&record.coin.puzzle_hash) {
+                    next_unused = next_unused.max(index + 1);
                 }
+                coin_records.push(record);
             }
-            n_pass += 1;
         }
+        store
+            .lock()
+            .await
+            .current_index
+            .store(next_unused, Ordering::Relaxed);
+        debug!(
+            "Scanned {scanned} derivation indexes, found {} coin records",
+            coin_records.len()
+        );
+        Ok(coin_records)
+    }
+    async fn sync_with_fullnode(&self) -> Result<bool, Error> {
+        let state = self.fullnode_client.get_blockchain_state().await?;
+        if !state.sync.synced {
+            warn!("Fullnode is not synced, skipping wallet sync");
+            return Ok(false);
+        }
+        let coin_records = self.scan_coin_records().await?;
+        let mempool_items = self.fullnode_client.get_all_mempool_items().await?;
+        let mut store = self.info.wallet_store.lock().await;
+        store.update_coin_records(coin_records);
+        store.update_pending(mempool_items.into_values());
+        Ok(true)
     }
-    best_set_of_coins
-}
-
-pub struct MemoryWallet {
-    //A wallet that is lost on restarts
-    info: WalletInfo<MemoryWalletStore>,
-    pub config: MemoryWalletConfig,
-    pub fullnode_client: FullnodeClient,
 }
-impl MemoryWallet {}
 #[async_trait]
 impl Wallet<MemoryWalletStore, MemoryWalletConfig> for MemoryWallet {
     fn create(info: WalletInfo<MemoryWalletStore>, config: MemoryWalletConfig) -> Self {
@@ -441,6 +417,7 @@ impl Wallet<MemoryWalletStore, MemoryWalletConfig> for MemoryWallet {
             info,
             config,
             fullnode_client,
+            synced: AtomicBool::new(false),
         }
     }
 
@@ -449,11 +426,14 @@ impl Wallet<MemoryWalletStore, MemoryWalletConfig> for MemoryWallet {
     }
 
     async fn sync(&self) -> Result<bool, Error> {
-        todo!()
+        let synced = self.sync_with_fullnode().await;
+        self.synced
+            .store(*synced.as_ref().unwrap_or(&false), Ordering::Relaxed);
+        synced
     }
 
     fn is_synced(&self) -> bool {
-        todo!()
+        self.synced.load(Ordering::Relaxed)
     }
 
     fn wallet_info(&self) -> &WalletInfo<MemoryWalletStore> {
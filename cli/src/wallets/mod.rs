@@ -1,3 +1,4 @@
+use crate::wallets::coin_selection::{CoinSelector, DefaultCoinSelector};
 use crate::wallets::common::{sign_coin_spends, DerivationRecord};
 use async_trait::async_trait;
 use blst::min_pk::SecretKey;
@@ -33,9 +34,12 @@ use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::Mutex;
 
+pub mod coin_selection;
 pub mod common;
+pub mod file_wallet;
 pub mod memory_wallet;
 pub mod plotnft_utils;
+pub mod transaction_builder;
 
 #[derive(Default)]
 pub struct SecretKeyStore {
@@ -92,6 +96,26 @@ pub trait WalletStore {
         min_coin_amount: Option<u64>,
         max_coin_amount: u64,
         exclude_coin_amounts: Option<&[u64]>,
+    ) -> Result<HashSet<Coin>, Error> {
+        self.select_coins_with_strategy(
+            amount,
+            exclude,
+            min_coin_amount,
+            max_coin_amount,
+            exclude_coin_amounts,
+            &DefaultCoinSelector::default(),
+        )
+        .await
+    }
+    //Coins picked here can be passed as `coins` when generating a transaction
+    async fn select_coins_with_strategy(
+        &self,
+        amount: u64,
+        exclude: Option<&[Coin]>,
+        min_coin_amount: Option<u64>,
+        max_coin_amount: u64,
+        exclude_coin_amounts: Option<&[u64]>,
+        strategy: &dyn CoinSelector,
     ) -> Result<HashSet<Coin>, Error>;
     async fn populate_secret_key_for_puzzle_hash(
         &self,
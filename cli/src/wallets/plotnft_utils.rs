@@ -1,28 +1,35 @@
 use crate::wallets::common::sign_coin_spend;
-use crate::wallets::memory_wallet::{MemoryWalletConfig, MemoryWalletStore};
+use crate::wallets::memory_wallet::{MemoryWalletConfig, MemoryWalletStore, DEFAULT_GAP_LIMIT};
 use crate::wallets::{Wallet, WalletInfo};
 use async_trait::async_trait;
 use blst::min_pk::SecretKey;
 use dg_xch_clients::api::full_node::FullnodeAPI;
-use dg_xch_clients::protocols::pool::{FARMING_TO_POOL, LEAVING_POOL, POOL_PROTOCOL_VERSION};
+use dg_xch_clients::protocols::pool::{
+    PoolError, PoolErrorCode, FARMING_TO_POOL, LEAVING_POOL, POOL_PROTOCOL_VERSION,
+};
+use dg_xch_clients::protocols::pool_server::is_pool_member;
 use dg_xch_clients::rpc::full_node::FullnodeClient;
 use dg_xch_core::blockchain::announcement::Announcement;
 use dg_xch_core::blockchain::coin_record::CoinRecord;
 use dg_xch_core::blockchain::coin_spend::{compute_additions_with_cost, CoinSpend};
-use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48};
+use dg_xch_core::blockchain::pending_payment::PendingPayment;
+use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, SizedBytes};
 use dg_xch_core::blockchain::spend_bundle::SpendBundle;
 use dg_xch_core::blockchain::transaction_record::{TransactionRecord, TransactionType};
 use dg_xch_core::blockchain::tx_status::TXStatus;
 use dg_xch_core::blockchain::wallet_type::WalletType;
+use dg_xch_core::consensus::coinbase::get_farmed_height;
 use dg_xch_core::consensus::constants::ConsensusConstants;
 use dg_xch_core::plots::PlotNft;
-use dg_xch_core::pool::PoolState;
+use dg_xch_core::pool::{Farmer, PoolState};
 use dg_xch_keys::{
-    master_sk_to_singleton_owner_sk, master_sk_to_wallet_sk, master_sk_to_wallet_sk_unhardened,
+    decode_puzzle_hash, master_sk_to_singleton_owner_sk, master_sk_to_wallet_sk,
+    master_sk_to_wallet_sk_unhardened,
 };
 use dg_xch_puzzles::clvm_puzzles::{
-    create_full_puzzle, create_travel_spend, get_most_recent_singleton_coin_from_coin_spend,
-    launcher_coin_spend_to_extra_data, pool_state_to_inner_puzzle, solution_to_pool_state,
+    create_absorb_spend, create_full_puzzle, create_travel_spend,
+    get_most_recent_singleton_coin_from_coin_spend, launcher_coin_spend_to_extra_data,
+    launcher_id_to_p2_puzzle_hash, pool_state_to_inner_puzzle, solution_to_pool_state,
     SINGLETON_LAUNCHER_HASH,
 };
 use dg_xch_puzzles::p2_delegated_puzzle_or_hidden_puzzle::puzzle_hash_for_pk;
@@ -124,6 +131,7 @@ impl PlotNFTWallet {
                 fullnode_port: client.port,
                 fullnode_ssl_path: client.ssl_path.clone(),
                 additional_headers: client.additional_headers.clone(),
+                gap_limit: DEFAULT_GAP_LIMIT,
             },
         )
     }
@@ -573,6 +581,209 @@ pub async fn get_plotnft_by_launcher_id(
     }
 }
 
+// Finds a farmer's PlotNFT for a pool server along with whether it is currently
+// farming to the pool at `target_puzzle_hash`
+pub async fn validate_farmer_singleton(
+    client: &FullnodeClient,
+    launcher_id: &Bytes32,
+    target_puzzle_hash: &Bytes32,
+    relative_lock_height: u32,
+) -> Result<(PlotNft, bool), PoolError> {
+    match get_plotnft_by_launcher_id(client, launcher_id).await {
+        Ok(Some(plot_nft)) => {
+            let is_member = is_pool_member(
+                &plot_nft.pool_state,
+                target_puzzle_hash,
+                relative_lock_height,
+            );
+            Ok((plot_nft, is_member))
+        }
+        Ok(None) => Err(PoolError::new(
+            PoolErrorCode::InvalidSingleton,
+            format!("Failed to find singleton for launcher_id {launcher_id}"),
+        )),
+        Err(e) => Err(PoolError::new(
+            PoolErrorCode::ServerException,
+            format!("Failed to load singleton for launcher_id {launcher_id}: {e:?}"),
+        )),
+    }
+}
+
+// The PlotNFT's delay time in seconds, a negative value is an error rather than a huge delay
+pub fn plotnft_delay_time(plot_nft: &PlotNft) -> Result<u64, Error> {
+    u64::try_from(plot_nft.delay_time).map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "PlotNFT {} has a negative delay time: {}",
+                plot_nft.launcher_id, plot_nft.delay_time
+            ),
+        )
+    })
+}
+
+// Unspent pool rewards paid to the PlotNFT's p2_singleton puzzle hash, along with
+// the height each reward was farmed at
+pub async fn get_unclaimed_pool_rewards(
+    client: &FullnodeClient,
+    plot_nft: &PlotNft,
+    genesis_challenge: &Bytes32,
+) -> Result<Vec<(CoinRecord, u32)>, Error> {
+    let p2_singleton_puzzle_hash = launcher_id_to_p2_puzzle_hash(
+        &plot_nft.launcher_id,
+        plotnft_delay_time(plot_nft)?,
+        &plot_nft.delay_puzzle_hash,
+    )?;
+    Ok(client
+        .get_coin_records_by_puzzle_hash(&p2_singleton_puzzle_hash, Some(false), None, None)
+        .await?
+        .into_iter()
+        .filter(|record| record.coinbase && !record.spent)
+        .filter_map(|record| {
+            get_farmed_height(&record, genesis_challenge).map(|height| (record, height))
+        })
+        .collect())
+}
+
+// Builds the absorb spends claiming every unclaimed reward of a PlotNFT, each claim
+// spends the singleton created by the one before it
+pub async fn create_absorb_spends(
+    client: &FullnodeClient,
+    launcher_id: &Bytes32,
+    genesis_challenge: &Bytes32,
+) -> Result<Vec<CoinSpend>, Error> {
+    let plot_nft = get_plotnft_by_launcher_id(client, launcher_id)
+        .await?
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("Failed to find PlotNFT for launcher_id {launcher_id}"),
+            )
+        })?;
+    let delay_time = plotnft_delay_time(&plot_nft)?;
+    let launcher_coin = client
+        .get_coin_record_by_name(launcher_id)
+        .await?
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("Failed to find launcher coin {launcher_id}"),
+            )
+        })?
+        .coin;
+    let parent = client
+        .get_coin_record_by_name(&plot_nft.singleton_coin.coin.parent_coin_info)
+        .await?
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("Failed to find last spend of singleton {launcher_id}"),
+            )
+        })?;
+    let mut last_spend = client.get_coin_spend(&parent).await?;
+    let mut spends = vec![];
+    for (reward, height) in get_unclaimed_pool_rewards(client, &plot_nft, genesis_challenge).await?
+    {
+        let absorb = create_absorb_spend(
+            &last_spend,
+            &plot_nft.pool_state,
+            &launcher_coin,
+            height,
+            reward.coin.amount,
+            genesis_challenge,
+            delay_time,
+            &plot_nft.delay_puzzle_hash,
+        )?;
+        last_spend = absorb[0].clone();
+        spends.extend(absorb);
+    }
+    Ok(spends)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayoutBatch {
+    pub additions: Vec<PendingPayment>,
+    pub fee: u64,
+}
+
+// Splits `amount` between farmers in proportion to their points, farmers sharing
+// payout instructions are paid in one addition. Every batch pays `fee_per_batch`
+// out of `amount` and holds at most `max_additions_per_batch` additions, amounts
+// are rounded down so the total paid never exceeds `amount`
+pub fn calculate_pool_payouts(
+    farmers: &[Farmer],
+    amount: u64,
+    fee_per_batch: u64,
+    max_additions_per_batch: usize,
+) -> Result<Vec<PayoutBatch>, Error> {
+    if max_additions_per_batch == 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "max_additions_per_batch must be greater than 0",
+        ));
+    }
+    let mut points: Vec<(Bytes32, u128)> = vec![];
+    for farmer in farmers.iter().filter(|f| f.points > 0) {
+        let instructions = farmer.payout_instructions.trim();
+        let puzzle_hash = if instructions.starts_with("xch") || instructions.starts_with("txch") {
+            decode_puzzle_hash(instructions)
+        } else {
+            hex::decode(instructions.trim_start_matches("0x"))
+                .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("{:?}", e)))
+                .and_then(|bytes| {
+                    if bytes.len() == 32 {
+                        Ok(Bytes32::new(&bytes))
+                    } else {
+                        Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Expected 32 bytes, got {}", bytes.len()),
+                        ))
+                    }
+                })
+        }
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Invalid payout instructions for launcher_id {}: {e}",
+                    farmer.launcher_id
+                ),
+            )
+        })?;
+        match points.iter_mut().find(|(ph, _)| *ph == puzzle_hash) {
+            Some((_, total)) => *total += farmer.points as u128,
+            None => points.push((puzzle_hash, farmer.points as u128)),
+        }
+    }
+    if points.is_empty() {
+        return Ok(vec![]);
+    }
+    let batches = points.len().div_ceil(max_additions_per_batch) as u64;
+    let total_fee = fee_per_batch.saturating_mul(batches);
+    let to_distribute = amount.checked_sub(total_fee).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("Amount {amount} does not cover {batches} batch fees of {fee_per_batch}"),
+        )
+    })? as u128;
+    let total_points: u128 = points.iter().map(|(_, p)| p).sum();
+    let additions: Vec<PendingPayment> = points
+        .into_iter()
+        .map(|(puzzle_hash, p)| PendingPayment {
+            puzzle_hash,
+            amount: (to_distribute * p / total_points) as u64,
+        })
+        .filter(|payment| payment.amount > 0)
+        .collect();
+    Ok(additions
+        .chunks(max_additions_per_batch)
+        .map(|chunk| PayoutBatch {
+            additions: chunk.to_vec(),
+            fee: fee_per_batch,
+        })
+        .collect())
+}
+
 pub async fn submit_next_state_spend_bundle(
     client: &FullnodeClient,
     pool_wallet: &PlotNFTWallet,
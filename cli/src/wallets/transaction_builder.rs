@@ -0,0 +1,368 @@
+use crate::wallets::coin_selection::{CoinSelector, DefaultCoinSelector};
+use crate::wallets::common::sign_coin_spends;
+use crate::wallets::{Wallet, WalletStore};
+use dg_xch_core::blockchain::announcement::Announcement;
+use dg_xch_core::blockchain::coin::Coin;
+use dg_xch_core::blockchain::coin_spend::CoinSpend;
+use dg_xch_core::blockchain::sized_bytes::Bytes32;
+use dg_xch_core::blockchain::spend_bundle::SpendBundle;
+use dg_xch_core::blockchain::transaction_record::{TransactionRecord, TransactionType};
+use dg_xch_core::blockchain::wallet_type::AmountWithPuzzlehash;
+use dg_xch_core::clvm::program::SerializedProgram;
+use dg_xch_serialize::{hash_256, ChiaSerialize};
+use log::debug;
+use num_traits::ToPrimitive;
+use std::collections::HashSet;
+use std::io::{Error, ErrorKind};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct SignedTransaction {
+    pub spend_bundle: SpendBundle,
+    pub additions: Vec<Coin>,
+    pub removals: Vec<Coin>,
+    pub outputs: Vec<AmountWithPuzzlehash>,
+    pub change: Option<Coin>,
+    pub fee: u64,
+}
+impl SignedTransaction {
+    pub fn name(&self) -> Bytes32 {
+        self.spend_bundle.name()
+    }
+    pub fn to_transaction_record(&self, wallet_id: u32) -> TransactionRecord {
+        TransactionRecord {
+            confirmed_at_height: 0,
+            created_at_time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            to_puzzle_hash: self
+                .outputs
+                .first()
+                .map(|o| o.puzzlehash)
+                .unwrap_or_default(),
+            amount: self.outputs.iter().map(|o| o.amount).sum(),
+            fee_amount: self.fee,
+            confirmed: false,
+            sent: 0,
+            spend_bundle: Some(self.spend_bundle.clone()),
+            additions: self.additions.clone(),
+            removals: self.removals.clone(),
+            wallet_id,
+            sent_to: vec![],
+            trade_id: None,
+            transaction_type: TransactionType::OutgoingTx as u32,
+            name: self.name(),
+            memos: vec![],
+        }
+    }
+}
+
+//Builds and signs a standard transaction spending coins owned by a wallet.
+//The first coin (by name) carries every output, the change and the fee, and
+//announces a hash of all inputs and outputs. Every other coin asserts that
+//announcement and announces its own, which the first coin asserts in turn,
+//so no spend in the bundle is valid without the rest.
+#[derive(Default)]
+pub struct TransactionBuilder {
+    outputs: Vec<AmountWithPuzzlehash>,
+    fee: u64,
+    coins: Option<Vec<Coin>>,
+    exclude_coins: Vec<Coin>,
+    min_coin_amount: Option<u64>,
+    max_coin_amount: Option<u64>,
+    exclude_coin_amounts: Vec<u64>,
+    coin_selector: Option<Box<dyn CoinSelector>>,
+    change_puzzle_hash: Option<Bytes32>,
+    coin_announcements: Vec<Vec<u8>>,
+    puzzle_announcements: Vec<Vec<u8>>,
+    coin_announcements_to_assert: Vec<Announcement>,
+    puzzle_announcements_to_assert: Vec<Announcement>,
+}
+impl TransactionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn add_output(mut self, puzzle_hash: Bytes32, amount: u64, memos: Vec<Vec<u8>>) -> Self {
+        self.outputs.push(AmountWithPuzzlehash {
+            amount,
+            puzzlehash: puzzle_hash,
+            memos,
+        });
+        self
+    }
+    pub fn add_outputs(mut self, outputs: impl IntoIterator<Item = AmountWithPuzzlehash>) -> Self {
+        self.outputs.extend(outputs);
+        self
+    }
+    pub fn fee(mut self, fee: u64) -> Self {
+        self.fee = fee;
+        self
+    }
+    //Spend exactly these coins instead of selecting from the wallet
+    pub fn coins(mut self, coins: Vec<Coin>) -> Self {
+        self.coins = Some(coins);
+        self
+    }
+    pub fn exclude_coins(mut self, coins: &[Coin]) -> Self {
+        self.exclude_coins.extend_from_slice(coins);
+        self
+    }
+    pub fn min_coin_amount(mut self, amount: u64) -> Self {
+        self.min_coin_amount = Some(amount);
+        self
+    }
+    pub fn max_coin_amount(mut self, amount: u64) -> Self {
+        self.max_coin_amount = Some(amount);
+        self
+    }
+    pub fn exclude_coin_amounts(mut self, amounts: &[u64]) -> Self {
+        self.exclude_coin_amounts.extend_from_slice(amounts);
+        self
+    }
+    pub fn coin_selector(mut self, selector: impl CoinSelector + 'static) -> Self {
+        self.coin_selector = Some(Box::new(selector));
+        self
+    }
+    //Defaults to a fresh puzzle hash from the wallet
+    pub fn change_puzzle_hash(mut self, puzzle_hash: Bytes32) -> Self {
+        self.change_puzzle_hash = Some(puzzle_hash);
+        self
+    }
+    pub fn create_coin_announcement(mut self, message: Vec<u8>) -> Self {
+        self.coin_announcements.push(message);
+        self
+    }
+    pub fn create_puzzle_announcement(mut self, message: Vec<u8>) -> Self {
+        self.puzzle_announcements.push(message);
+        self
+    }
+    pub fn assert_coin_announcement(mut self, announcement: Announcement) -> Self {
+        self.coin_announcements_to_assert.push(announcement);
+        self
+    }
+    pub fn assert_puzzle_announcement(mut self, announcement: Announcement) -> Self {
+        self.puzzle_announcements_to_assert.push(announcement);
+        self
+    }
+
+    pub async fn build<T, C, W>(self, wallet: &W) -> Result<SignedTransaction, Error>
+    where
+        T: WalletStore + Send + Sync,
+        W: Wallet<T, C> + Sync,
+    {
+        let output_amount = self
+            .outputs
+            .iter()
+            .try_fold(0u64, |total, o| total.checked_add(o.amount))
+            .and_then(|total| total.checked_add(self.fee))
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Transaction amount overflows"))?;
+        let mut unique_outputs = HashSet::new();
+        for output in &self.outputs {
+            if !unique_outputs.insert((output.puzzlehash, output.amount)) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Cannot create two identical coins",
+                ));
+            }
+        }
+        let mut coins: Vec<Coin> = match self.coins {
+            Some(coins) => {
+                if !self.exclude_coins.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Can't exclude coins when also specifically including coins",
+                    ));
+                }
+                coins
+            }
+            None => {
+                let max_coin_amount = self.max_coin_amount.unwrap_or(
+                    wallet
+                        .wallet_info()
+                        .constants
+                        .max_coin_amount
+                        .to_u64()
+                        .unwrap_or_default(),
+                );
+                let default_selector = DefaultCoinSelector::default();
+                let selector: &dyn CoinSelector = match &self.coin_selector {
+                    Some(selector) => selector.as_ref(),
+                    None => &default_selector,
+                };
+                wallet
+                    .wallet_store()
+                    .lock()
+                    .await
+                    .select_coins_with_strategy(
+                        output_amount,
+                        Some(&self.exclude_coins),
+                        self.min_coin_amount,
+                        max_coin_amount,
+                        Some(&self.exclude_coin_amounts),
+                        selector,
+                    )
+                    .await?
+                    .into_iter()
+                    .collect()
+            }
+        };
+        if coins.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "No coins to spend"));
+        }
+        coins.sort_by_key(|c| c.name().to_bytes());
+        coins.dedup();
+        let input_amount: u128 = coins.iter().map(|c| c.amount as u128).sum();
+        if input_amount < output_amount as u128 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Selected coins total {input_amount}, need {output_amount}"),
+            ));
+        }
+        let change_amount = (input_amount - output_amount as u128) as u64;
+        let origin = coins[0].clone();
+        let mut primaries = self.outputs.clone();
+        let mut change = None;
+        if change_amount > 0 {
+            let mut change_puzzle_hash = match self.change_puzzle_hash {
+                Some(puzzle_hash) => puzzle_hash,
+                None => wallet.get_new_puzzlehash().await?,
+            };
+            if unique_outputs.contains(&(change_puzzle_hash, change_amount)) {
+                //We cannot create two coins with the same id, use a new puzzle hash for the change
+                change_puzzle_hash = wallet.get_new_puzzlehash().await?;
+            }
+            primaries.push(AmountWithPuzzlehash {
+                amount: change_amount,
+                puzzlehash: change_puzzle_hash,
+                memos: vec![],
+            });
+            change = Some(Coin {
+                parent_coin_info: origin.name(),
+                puzzle_hash: change_puzzle_hash,
+                amount: change_amount,
+            });
+        }
+        let mut message_list: Vec<Bytes32> = coins.iter().map(|c| c.name()).collect();
+        for primary in &primaries {
+            message_list.push(
+                Coin {
+                    parent_coin_info: origin.name(),
+                    puzzle_hash: primary.puzzlehash,
+                    amount: primary.amount,
+                }
+                .name(),
+            );
+        }
+        let message = hash_256(message_list.iter().fold(vec![], |mut v, e| {
+            v.extend(e.to_bytes());
+            v
+        }));
+        let origin_announcement = Announcement {
+            origin_info: origin.name(),
+            message: message.clone(),
+        }
+        .name();
+        let mut origin_coin_announcements: HashSet<Vec<u8>> =
+            HashSet::from_iter(self.coin_announcements);
+        origin_coin_announcements.insert(message.clone());
+        let mut origin_coin_asserts: HashSet<Bytes32> = self
+            .coin_announcements_to_assert
+            .iter()
+            .map(|a| a.name())
+            .collect();
+        for coin in &coins[1..] {
+            origin_coin_asserts.insert(
+                Announcement {
+                    origin_info: coin.name(),
+                    message: message.clone(),
+                }
+                .name(),
+            );
+        }
+        let puzzle_announcements: HashSet<Vec<u8>> = HashSet::from_iter(self.puzzle_announcements);
+        let puzzle_asserts: HashSet<Bytes32> = self
+            .puzzle_announcements_to_assert
+            .iter()
+            .map(|a| a.name())
+            .collect();
+        let mut spends = vec![];
+        for coin in &coins {
+            let puzzle = wallet.puzzle_for_puzzle_hash(&coin.puzzle_hash).await?;
+            let solution = if *coin == origin {
+                wallet.make_solution(
+                    &primaries,
+                    0,
+                    Some(origin_coin_announcements.clone()),
+                    (!origin_coin_asserts.is_empty()).then(|| origin_coin_asserts.clone()),
+                    (!puzzle_announcements.is_empty()).then(|| puzzle_announcements.clone()),
+                    (!puzzle_asserts.is_empty()).then(|| puzzle_asserts.clone()),
+                    self.fee,
+                )?
+            } else {
+                wallet.make_solution(
+                    &[],
+                    0,
+                    Some(HashSet::from([message.clone()])),
+                    Some(HashSet::from([origin_announcement])),
+                    None,
+                    None,
+                    0,
+                )?
+            };
+            spends.push(CoinSpend {
+                coin: coin.clone(),
+                puzzle_reveal: SerializedProgram::from_bytes(&puzzle.serialized),
+                solution: SerializedProgram::from_bytes(&solution.serialized),
+            });
+        }
+        let wallet_store = wallet.wallet_store();
+        let spend_bundle = sign_coin_spends(
+            spends,
+            |pub_key| {
+                let pub_key = *pub_key;
+                let wallet_store = wallet_store.clone();
+                async move {
+                    wallet_store
+                        .lock()
+                        .await
+                        .secret_key_for_public_key(&pub_key)
+                        .await
+                }
+            },
+            &wallet.wallet_info().constants.agg_sig_me_additional_data,
+            wallet
+                .wallet_info()
+                .constants
+                .max_block_cost_clvm
+                .to_u64()
+                .unwrap_or(u64::MAX),
+        )
+        .await?;
+        let additions = spend_bundle.additions()?;
+        let removals = spend_bundle.removals();
+        let added: u128 = additions.iter().map(|c| c.amount as u128).sum();
+        if added + self.fee as u128 != input_amount {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Built transaction does not balance: in {input_amount}, out {added}, fee {}",
+                    self.fee
+                ),
+            ));
+        }
+        debug!(
+            "Built transaction {} spending {} coins into {} outputs",
+            spend_bundle.name(),
+            removals.len(),
+            additions.len()
+        );
+        Ok(SignedTransaction {
+            spend_bundle,
+            additions,
+            removals,
+            outputs: self.outputs,
+            change,
+            fee: self.fee,
+        })
+    }
+}
@@ -0,0 +1,14 @@
+use crate::protocols::crawler::{IpsAfterTimestamp, PeerCounts};
+use async_trait::async_trait;
+use std::io::Error;
+
+#[async_trait]
+pub trait CrawlerAPI {
+    async fn get_peer_counts(&self) -> Result<PeerCounts, Error>;
+    async fn get_ips_after_timestamp(
+        &self,
+        after: u64,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<IpsAfterTimestamp, Error>;
+}
@@ -0,0 +1,59 @@
+use crate::protocols::data_layer::{
+    DataStoreChange, DataStoreRoot, DataStoreSyncStatus, InternalNode, KeyValue, KeyValueDiff,
+};
+use async_trait::async_trait;
+use dg_xch_core::blockchain::sized_bytes::Bytes32;
+use dg_xch_core::blockchain::unsized_bytes::UnsizedBytes;
+use std::io::Error;
+
+#[async_trait]
+pub trait DataLayerAPI {
+    async fn create_data_store(&self, fee: Option<u64>) -> Result<Bytes32, Error>;
+    async fn get_value(
+        &self,
+        id: &Bytes32,
+        key: &UnsizedBytes,
+        root_hash: Option<&Bytes32>,
+    ) -> Result<Option<UnsizedBytes>, Error>;
+    async fn get_keys(
+        &self,
+        id: &Bytes32,
+        root_hash: Option<&Bytes32>,
+    ) -> Result<Vec<UnsizedBytes>, Error>;
+    async fn get_keys_values(
+        &self,
+        id: &Bytes32,
+        root_hash: Option<&Bytes32>,
+    ) -> Result<Vec<KeyValue>, Error>;
+    async fn get_root(&self, id: &Bytes32) -> Result<DataStoreRoot, Error>;
+    async fn get_ancestors(&self, id: &Bytes32, hash: &Bytes32)
+        -> Result<Vec<InternalNode>, Error>;
+    async fn batch_update(
+        &self,
+        id: &Bytes32,
+        changelist: &[DataStoreChange],
+        fee: Option<u64>,
+    ) -> Result<Bytes32, Error>;
+    async fn insert(
+        &self,
+        id: &Bytes32,
+        key: &UnsizedBytes,
+        value: &UnsizedBytes,
+        fee: Option<u64>,
+    ) -> Result<Bytes32, Error>;
+    async fn delete_key(
+        &self,
+        id: &Bytes32,
+        key: &UnsizedBytes,
+        fee: Option<u64>,
+    ) -> Result<Bytes32, Error>;
+    async fn subscribe(&self, id: &Bytes32, urls: &[String]) -> Result<(), Error>;
+    async fn unsubscribe(&self, id: &Bytes32, retain: bool) -> Result<(), Error>;
+    async fn get_kv_diff(
+        &self,
+        id: &Bytes32,
+        hash_1: &Bytes32,
+        hash_2: &Bytes32,
+    ) -> Result<Vec<KeyValueDiff>, Error>;
+    async fn get_sync_status(&self, id: &Bytes32) -> Result<DataStoreSyncStatus, Error>;
+}
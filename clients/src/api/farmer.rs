@@ -0,0 +1,31 @@
+use crate::protocols::farmer::{
+    FarmerPoolState, FarmerSignagePoint, HarvesterInfo, HarvesterSummary, RewardTargets,
+};
+use async_trait::async_trait;
+use dg_xch_core::blockchain::sized_bytes::Bytes32;
+use std::io::Error;
+
+#[async_trait]
+pub trait FarmerAPI {
+    async fn get_signage_point(&self, sp_hash: &Bytes32) -> Result<FarmerSignagePoint, Error>;
+    async fn get_signage_points(&self) -> Result<Vec<FarmerSignagePoint>, Error>;
+    async fn get_reward_targets(
+        &self,
+        search_for_private_key: bool,
+        max_ph_to_search: Option<u32>,
+    ) -> Result<RewardTargets, Error>;
+    async fn set_reward_targets(
+        &self,
+        farmer_target: Option<String>,
+        pool_target: Option<String>,
+    ) -> Result<(), Error>;
+    async fn get_pool_state(&self) -> Result<Vec<FarmerPoolState>, Error>;
+    async fn set_payout_instructions(
+        &self,
+        launcher_id: &Bytes32,
+        payout_instructions: &str,
+    ) -> Result<(), Error>;
+    async fn get_harvesters(&self) -> Result<Vec<HarvesterInfo>, Error>;
+    async fn get_harvesters_summary(&self) -> Result<Vec<HarvesterSummary>, Error>;
+    async fn get_pool_login_link(&self, launcher_id: &Bytes32) -> Result<String, Error>;
+}
@@ -0,0 +1,15 @@
+use crate::protocols::harvester::{HarvesterConfig, HarvesterPlots};
+use async_trait::async_trait;
+use std::io::Error;
+
+#[async_trait]
+pub trait HarvesterAPI {
+    async fn get_plots(&self) -> Result<HarvesterPlots, Error>;
+    async fn refresh_plots(&self) -> Result<(), Error>;
+    async fn delete_plot(&self, filename: &str) -> Result<(), Error>;
+    async fn add_plot_directory(&self, dirname: &str) -> Result<(), Error>;
+    async fn get_plot_directories(&self) -> Result<Vec<String>, Error>;
+    async fn remove_plot_directory(&self, dirname: &str) -> Result<(), Error>;
+    async fn get_harvester_config(&self) -> Result<HarvesterConfig, Error>;
+    async fn update_harvester_config(&self, config: &HarvesterConfig) -> Result<(), Error>;
+}
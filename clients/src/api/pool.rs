@@ -4,9 +4,19 @@ use crate::protocols::pool::{
     PutFarmerRequest, PutFarmerResponse,
 };
 use async_trait::async_trait;
-use log::warn;
-use reqwest::Client;
+use dashmap::DashMap;
+use log::{debug, warn};
+use reqwest::{Client, RequestBuilder, Response};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+pub const DEFAULT_POOL_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+pub const DEFAULT_POOL_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+pub const DEFAULT_POOL_MAX_RETRIES: u32 = 2;
+pub const DEFAULT_POOL_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+pub const DEFAULT_POOL_MAX_RETRY_BACKOFF: Duration = Duration::from_secs(5);
 
 #[async_trait]
 pub trait PoolClient {
@@ -37,17 +47,286 @@ pub trait PoolClient {
     async fn get_pool_info(&self, pool_url: &str) -> Result<GetPoolInfoResponse, PoolError>;
 }
 
-#[derive(Default, Debug)]
+#[derive(Debug, Clone)]
+pub struct PoolClientConfig {
+    pub accept_invalid_certs: bool,
+    pub connect_timeout: Duration,
+    pub request_timeout: Duration,
+    // retries after the first attempt, only for network errors and 5xx responses
+    pub max_retries: u32,
+    pub retry_backoff: Duration,
+    pub max_retry_backoff: Duration,
+    // minimum spacing between requests sent to the same pool
+    pub min_request_interval: Option<Duration>,
+}
+impl Default for PoolClientConfig {
+    fn default() -> Self {
+        Self {
+            accept_invalid_certs: false,
+            connect_timeout: DEFAULT_POOL_CONNECT_TIMEOUT,
+            request_timeout: DEFAULT_POOL_REQUEST_TIMEOUT,
+            max_retries: DEFAULT_POOL_MAX_RETRIES,
+            retry_backoff: DEFAULT_POOL_RETRY_BACKOFF,
+            max_retry_backoff: DEFAULT_POOL_MAX_RETRY_BACKOFF,
+            min_request_interval: None,
+        }
+    }
+}
+impl PoolClientConfig {
+    // Exponential backoff before the given retry, starting at 1
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.retry_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_retry_backoff)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct PoolClientBuilder {
+    config: PoolClientConfig,
+}
+impl PoolClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    // Only for pools serving self signed certificates
+    pub fn accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.config.accept_invalid_certs = accept;
+        self
+    }
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = timeout;
+        self
+    }
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.config.request_timeout = timeout;
+        self
+    }
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.config.max_retries = max_retries;
+        self
+    }
+    pub fn retry_backoff(mut self, backoff: Duration, max_backoff: Duration) -> Self {
+        self.config.retry_backoff = backoff;
+        self.config.max_retry_backoff = max_backoff;
+        self
+    }
+    pub fn rate_limit(mut self, requests_per_second: u32) -> Self {
+        self.config.min_request_interval = if requests_per_second == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(1) / requests_per_second)
+        };
+        self
+    }
+    pub fn min_request_interval(mut self, interval: Option<Duration>) -> Self {
+        self.config.min_request_interval = interval;
+        self
+    }
+    pub fn build(self) -> Result<DefaultPoolClient, PoolError> {
+        let client = Client::builder()
+            .danger_accept_invalid_certs(self.config.accept_invalid_certs)
+            .connect_timeout(self.config.connect_timeout)
+            .build()
+            .map_err(|e| PoolError::new(PoolErrorCode::RequestFailed, e.to_string()))?;
+        Ok(DefaultPoolClient {
+            client,
+            config: self.config,
+            rate_limits: Default::default(),
+            partial_metrics: Default::default(),
+        })
+    }
+}
+
+// Partial submission results and latency for a single pool
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PartialMetrics {
+    pub accepted: u64,
+    pub rejected: u64,
+    pub failed: u64,
+    // latency of accepted partials, including retries and rate limiting
+    pub total_latency: Duration,
+    pub min_latency: Option<Duration>,
+    pub max_latency: Duration,
+    pub last_latency: Duration,
+}
+impl PartialMetrics {
+    pub fn submitted(&self) -> u64 {
+        self.accepted + self.rejected + self.failed
+    }
+    pub fn average_latency(&self) -> Option<Duration> {
+        if self.accepted == 0 {
+            None
+        } else {
+            Some(Duration::from_nanos(
+                (self.total_latency.as_nanos() / self.accepted as u128) as u64,
+            ))
+        }
+    }
+    fn record(&mut self, result: &Result<PostPartialResponse, PoolError>, latency: Duration) {
+        match result {
+            Ok(_) => {
+                self.accepted += 1;
+                self.total_latency += latency;
+                self.min_latency = Some(self.min_latency.map_or(latency, |m| m.min(latency)));
+                self.max_latency = self.max_latency.max(latency);
+                self.last_latency = latency;
+            }
+            Err(e) if e.error_code == PoolErrorCode::RequestFailed as u8 => self.failed += 1,
+            Err(_) => self.rejected += 1,
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct DefaultPoolClient {
     pub client: Client,
+    pub config: PoolClientConfig,
+    rate_limits: DashMap<String, Arc<Mutex<Instant>>>,
+    partial_metrics: DashMap<String, PartialMetrics>,
+}
+impl Default for DefaultPoolClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 impl DefaultPoolClient {
     pub fn new() -> Self {
-        Self {
-            client: Client::builder()
-                .danger_accept_invalid_certs(true)
-                .build()
-                .unwrap_or_default(),
+        Self::builder().build().unwrap_or_else(|e| {
+            warn!("Failed to build pool client, using defaults: {:?}", e);
+            Self {
+                client: Client::default(),
+                config: PoolClientConfig::default(),
+                rate_limits: Default::default(),
+                partial_metrics: Default::default(),
+            }
+        })
+    }
+    pub fn builder() -> PoolClientBuilder {
+        PoolClientBuilder::new()
+    }
+    pub fn partial_metrics(&self, pool_url: &str) -> Option<PartialMetrics> {
+        self.partial_metrics.get(pool_url).map(|m| m.clone())
+    }
+    pub fn all_partial_metrics(&self) -> HashMap<String, PartialMetrics> {
+        self.partial_metrics
+            .iter()
+            .map(|e| (e.key().clone(), e.value().clone()))
+            .collect()
+    }
+    // Waits for the next free slot for this pool when rate limiting is enabled
+    async fn wait_for_slot(&self, pool_url: &str) {
+        if let Some(interval) = self.config.min_request_interval {
+            let next = self
+                .rate_limits
+                .entry(pool_url.to_string())
+                .or_insert_with(|| Arc::new(Mutex::new(Instant::now())))
+                .clone();
+            let slot = {
+                let mut next = next.lock().await;
+                let slot = (*next).max(Instant::now());
+                *next = slot + interval;
+                slot
+            };
+            tokio::time::sleep_until(slot.into()).await;
+        }
+    }
+    // Sends the request with the configured timeout, retrying network errors
+    // and 5xx responses. The last response is returned once retries run out
+    async fn send(&self, pool_url: &str, request: RequestBuilder) -> Result<Response, PoolError> {
+        let mut retry = 0;
+        loop {
+            let attempt = request.try_clone().ok_or_else(|| {
+                PoolError::new(
+                    PoolErrorCode::RequestFailed,
+                    "Pool request body can not be retried",
+                )
+            })?;
+            self.wait_for_slot(pool_url).await;
+            let result = attempt.timeout(self.config.request_timeout).send().await;
+            let retryable = match &result {
+                Ok(resp) => resp.status().is_server_error(),
+                Err(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+            };
+            if !retryable || retry >= self.config.max_retries {
+                return result
+                    .map_err(|e| PoolError::new(PoolErrorCode::RequestFailed, e.to_string()));
+            }
+            retry += 1;
+            let backoff = self.config.backoff(retry);
+            let reason = match &result {
+                Ok(resp) => resp.status().to_string(),
+                Err(e) => e.to_string(),
+            };
+            debug!("Pool request to {pool_url} failed with {reason}, retry {retry} in {backoff:?}");
+            tokio::time::sleep(backoff).await;
+        }
+    }
+    async fn send_partial(
+        &self,
+        url: &str,
+        request: PostPartialRequest,
+        headers: &Option<HashMap<String, String>>,
+    ) -> Result<PostPartialResponse, PoolError> {
+        let mut request_builder = self.client.post(format!("{}/partial", url));
+        if let Some(headers) = headers {
+            for (k, v) in headers {
+                request_builder = request_builder.header(k, v);
+            }
+        }
+        match self.send(url, request_builder.json(&request)).await {
+            Ok(resp) => match resp.status() {
+                reqwest::StatusCode::OK => match resp.text().await {
+                    Ok(body) => match serde_json::from_str(body.as_str()) {
+                        Ok(c) => Ok(c),
+                        Err(_) => match serde_json::from_str(body.as_str()) {
+                            Ok(e) => Err(e),
+                            Err(_) => match serde_json::from_str(&body) {
+                                Ok(e) => {
+                                    warn!("Failed to Post Partial: {:?}", e);
+                                    Err(e)
+                                }
+                                Err(e) => {
+                                    warn!(
+                                        "Failed to parse partial Error, Invalid Json: {:?}, {}",
+                                        e, body
+                                    );
+                                    Err(PoolError {
+                                        error_code: PoolErrorCode::RequestFailed as u8,
+                                        error_message: e.to_string(),
+                                    })
+                                }
+                            },
+                        },
+                    },
+                    Err(e) => {
+                        warn!("Failed to Post Partial, Invalid Body: {:?}", e);
+                        Err(PoolError {
+                            error_code: PoolErrorCode::RequestFailed as u8,
+                            error_message: e.to_string(),
+                        })
+                    }
+                },
+                _ => {
+                    let status = resp.status();
+                    let text = resp.text().await.unwrap_or_default();
+                    warn!(
+                        "Failed to Post Partial, Bad Status Code: {:?}, {}",
+                        &status, &text
+                    );
+                    Err(PoolError {
+                        error_code: PoolErrorCode::RequestFailed as u8,
+                        error_message: format!(
+                            "Failed to Post Partial, Bad Status Code: {:?}, {}",
+                            status, text
+                        ),
+                    })
+                }
+            },
+            Err(e) => {
+                warn!("Failed to send Post Partial: {:?}", e);
+                Err(e)
+            }
         }
     }
 }
@@ -65,7 +344,7 @@ impl PoolClient for DefaultPoolClient {
                 request_builder = request_builder.header(k, v);
             }
         }
-        match request_builder.query(&request).send().await {
+        match self.send(url, request_builder.query(&request)).await {
             Ok(resp) => match resp.status() {
                 reqwest::StatusCode::OK => match resp.text().await {
                     Ok(body) => {
@@ -116,10 +395,7 @@ impl PoolClient for DefaultPoolClient {
             },
             Err(e) => {
                 warn!("Failed to send Get Farmer: {:?}", e);
-                Err(PoolError {
-                    error_code: PoolErrorCode::RequestFailed as u8,
-                    error_message: e.to_string(),
-                })
+                Err(e)
             }
         }
     }
@@ -136,7 +412,7 @@ impl PoolClient for DefaultPoolClient {
                 request_builder = request_builder.header(k, v);
             }
         }
-        match request_builder.json(&request).send().await {
+        match self.send(url, request_builder.json(&request)).await {
             Ok(resp) => match resp.status() {
                 reqwest::StatusCode::OK => match resp.text().await {
                     Ok(body) => match serde_json::from_str(body.as_str()) {
@@ -187,10 +463,7 @@ impl PoolClient for DefaultPoolClient {
             },
             Err(e) => {
                 warn!("Failed to send Post Farmer: {:?}", e);
-                Err(PoolError {
-                    error_code: PoolErrorCode::RequestFailed as u8,
-                    error_message: e.to_string(),
-                })
+                Err(e)
             }
         }
     }
@@ -207,7 +480,7 @@ impl PoolClient for DefaultPoolClient {
                 request_builder = request_builder.header(k, v);
             }
         }
-        match request_builder.json(&request).send().await {
+        match self.send(url, request_builder.json(&request)).await {
             Ok(resp) => match resp.status() {
                 reqwest::StatusCode::OK => match resp.text().await {
                     Ok(body) => match serde_json::from_str::<PoolError>(body.as_str()) {
@@ -252,10 +525,7 @@ impl PoolClient for DefaultPoolClient {
             },
             Err(e) => {
                 warn!("Failed to send Put Farmer: {:?}", e);
-                Err(PoolError {
-                    error_code: PoolErrorCode::RequestFailed as u8,
-                    error_message: e.to_string(),
-                })
+                Err(e)
             }
         }
     }
@@ -265,82 +535,21 @@ impl PoolClient for DefaultPoolClient {
         request: PostPartialRequest,
         headers: &Option<HashMap<String, String>>,
     ) -> Result<PostPartialResponse, PoolError> {
-        let mut request_builder = self.client.post(format!("{}/partial", url));
-        if let Some(headers) = headers {
-            for (k, v) in headers {
-                request_builder = request_builder.header(k, v);
-            }
-        }
-        match request_builder.json(&request).send().await {
-            Ok(resp) => match resp.status() {
-                reqwest::StatusCode::OK => match resp.text().await {
-                    Ok(body) => match serde_json::from_str(body.as_str()) {
-                        Ok(c) => Ok(c),
-                        Err(_) => match serde_json::from_str(body.as_str()) {
-                            Ok(e) => Err(e),
-                            Err(_) => match serde_json::from_str(&body) {
-                                Ok(e) => {
-                                    warn!("Failed to Post Partial: {:?}", e);
-                                    Err(e)
-                                }
-                                Err(e) => {
-                                    warn!(
-                                        "Failed to parse partial Error, Invalid Json: {:?}, {}",
-                                        e, body
-                                    );
-                                    Err(PoolError {
-                                        error_code: PoolErrorCode::RequestFailed as u8,
-                                        error_message: e.to_string(),
-                                    })
-                                }
-                            },
-                        },
-                    },
-                    Err(e) => {
-                        warn!("Failed to Post Partial, Invalid Body: {:?}", e);
-                        Err(PoolError {
-                            error_code: PoolErrorCode::RequestFailed as u8,
-                            error_message: e.to_string(),
-                        })
-                    }
-                },
-                _ => {
-                    let status = resp.status();
-                    let text = resp.text().await.unwrap_or_default();
-                    warn!(
-                        "Failed to Post Partial, Bad Status Code: {:?}, {}",
-                        &status, &text
-                    );
-                    Err(PoolError {
-                        error_code: PoolErrorCode::RequestFailed as u8,
-                        error_message: format!(
-                            "Failed to Post Partial, Bad Status Code: {:?}, {}",
-                            status, text
-                        ),
-                    })
-                }
-            },
-            Err(e) => {
-                warn!("Failed to send Post Partial: {:?}", e);
-                Err(PoolError {
-                    error_code: PoolErrorCode::RequestFailed as u8,
-                    error_message: e.to_string(),
-                })
-            }
-        }
+        let start = Instant::now();
+        let result = self.send_partial(url, request, headers).await;
+        self.partial_metrics
+            .entry(url.to_string())
+            .or_default()
+            .record(&result, start.elapsed());
+        result
     }
     async fn get_pool_info(&self, pool_url: &str) -> Result<GetPoolInfoResponse, PoolError> {
         let resp = self
-            .client
-            .get(format!("{}/pool_info", pool_url))
-            .send()
+            .send(pool_url, self.client.get(format!("{}/pool_info", pool_url)))
             .await
             .map_err(|e| {
                 warn!("Failed to load Pool Info: {:?}", e);
-                PoolError {
-                    error_code: PoolErrorCode::RequestFailed as u8,
-                    error_message: e.to_string(),
-                }
+                e
             })?;
         match resp.status() {
             reqwest::StatusCode::OK => {
@@ -1,16 +1,28 @@
+use crate::protocols::crawler::PeerCounts;
+use crate::protocols::data_layer::{DataStoreSyncStatus, InternalNode, KeyValue, KeyValueDiff};
+use crate::protocols::farmer::{
+    FarmerPoolState, FarmerSignagePoint, HarvesterInfo, HarvesterSummary, NewSignagePoint,
+};
 use crate::protocols::full_node::BlockCountMetrics;
 use crate::protocols::full_node::FeeEstimate;
+use crate::protocols::harvester::Plot;
+use crate::protocols::shared::PeerConnection;
+use crate::protocols::wallet::{NftInfo, TradeRecord};
 use dg_xch_core::blockchain::block_record::BlockRecord;
 use dg_xch_core::blockchain::blockchain_state::BlockchainState;
 use dg_xch_core::blockchain::coin_record::CoinRecord;
 use dg_xch_core::blockchain::coin_spend::CoinSpend;
 use dg_xch_core::blockchain::full_block::FullBlock;
 use dg_xch_core::blockchain::mempool_item::MempoolItem;
+use dg_xch_core::blockchain::proof_of_space::ProofOfSpace;
 use dg_xch_core::blockchain::signage_point::SignagePoint;
+use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48};
+use dg_xch_core::blockchain::spend_bundle::SpendBundle;
 use dg_xch_core::blockchain::subslot_bundle::SubSlotBundle;
 use dg_xch_core::blockchain::transaction_record::TransactionRecord;
 use dg_xch_core::blockchain::tx_status::TXStatus;
 use dg_xch_core::blockchain::unfinished_block::UnfinishedBlock;
+use dg_xch_core::blockchain::unsized_bytes::UnsizedBytes;
 use dg_xch_core::blockchain::wallet_balance::WalletBalance;
 use dg_xch_core::blockchain::wallet_info::WalletInfo;
 
@@ -24,6 +36,19 @@ pub struct AdditionsAndRemovalsResp {
     pub success: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AllOffersResp {
+    pub trade_records: Vec<TradeRecord>,
+    pub offers: Option<Vec<String>>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AncestorsResp {
+    pub ancestors: Vec<InternalNode>,
+    pub success: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct BlockchainStateResp {
     pub blockchain_state: BlockchainState,
@@ -42,6 +67,13 @@ pub struct BlockRecordAryResp {
     pub success: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CatAssetIdResp {
+    pub asset_id: Bytes32,
+    pub wallet_id: u32,
+    pub success: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CoinRecordResp {
     pub coin_record: Option<CoinRecord>,
@@ -60,6 +92,122 @@ pub struct CoinSpendResp {
     pub success: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConnectionsResp {
+    pub connections: Vec<PeerConnection>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreateCatWalletResp {
+    #[serde(alias = "type")]
+    pub wallet_type: u8,
+    pub asset_id: Bytes32,
+    pub wallet_id: u32,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreateDidWalletResp {
+    #[serde(alias = "type")]
+    pub wallet_type: u8,
+    pub my_did: String,
+    pub wallet_id: u32,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreateOfferResp {
+    pub offer: String,
+    pub trade_record: TradeRecord,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DataStoreIdResp {
+    pub id: Bytes32,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DataStoreRootResp {
+    pub hash: Bytes32,
+    pub confirmed: bool,
+    pub timestamp: u64,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DataStoreSyncStatusResp {
+    pub sync_status: DataStoreSyncStatus,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DataStoreTXResp {
+    pub tx_id: Bytes32,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DataStoreValueResp {
+    pub value: Option<UnsizedBytes>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DidGetDidResp {
+    pub wallet_id: u32,
+    pub my_did: String,
+    pub coin_id: Option<Bytes32>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DidMetadataResp {
+    pub wallet_id: u32,
+    pub metadata: HashMap<String, String>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DidPubkeyResp {
+    pub pubkey: Bytes48,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DidWalletNameResp {
+    pub wallet_id: u32,
+    pub name: String,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EmptyResp {
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FarmerPoolStateResp {
+    pub pool_state: Vec<FarmerPoolState>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FarmerSignagePointResp {
+    pub signage_point: NewSignagePoint,
+    pub proofs: Vec<(String, ProofOfSpace)>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FarmerSignagePointAryResp {
+    pub signage_points: Vec<FarmerSignagePoint>,
+    pub success: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FeeEstimateResp {
     pub fee_estimate: FeeEstimate,
@@ -83,12 +231,62 @@ pub struct FullBlockAryResp {
     pub success: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HarvesterConfigResp {
+    pub use_gpu_harvesting: bool,
+    pub gpu_index: u8,
+    pub enforce_gpu_index: bool,
+    pub disable_cpu_affinity: bool,
+    pub parallel_decompressor_count: u32,
+    pub decompressor_thread_count: u32,
+    pub recursive_plot_scan: bool,
+    pub refresh_parameter_interval_seconds: u32,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HarvesterInfoAryResp {
+    pub harvesters: Vec<HarvesterInfo>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HarvesterSummaryAryResp {
+    pub harvesters: Vec<HarvesterSummary>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IpsAfterTimestampResp {
+    pub ips: Vec<String>,
+    pub total: u64,
+    pub success: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct InitialFreezePeriodResp {
     pub initial_freeze_end_timestamp: u64,
     pub success: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KeysResp {
+    pub keys: Vec<UnsizedBytes>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KeysValuesResp {
+    pub keys_values: Vec<KeyValue>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KeyValueDiffResp {
+    pub diff: Vec<KeyValueDiff>,
+    pub success: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LoginResp {
     pub fingerprint: u32,
@@ -132,6 +330,62 @@ pub struct NetSpaceResp {
     pub success: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NftListResp {
+    pub wallet_id: u32,
+    pub nft_list: Vec<NftInfo>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NftMintResp {
+    pub wallet_id: u32,
+    pub nft_id: String,
+    pub spend_bundle: SpendBundle,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PeerCountsResp {
+    pub peer_counts: PeerCounts,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlotDirectoriesResp {
+    pub directories: Vec<String>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlotsResp {
+    pub plots: Vec<Plot>,
+    pub failed_to_open_filenames: Vec<String>,
+    pub not_found_filenames: Vec<String>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PoolLoginLinkResp {
+    pub login_link: String,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RewardTargetsResp {
+    pub farmer_target: String,
+    pub pool_target: String,
+    pub have_farmer_sk: Option<bool>,
+    pub have_pool_sk: Option<bool>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RoutesResp {
+    pub routes: Vec<String>,
+    pub success: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SignagePointOrEOSResp {
     pub signage_point: Option<SignagePoint>,
@@ -147,18 +401,45 @@ pub struct SignedTransactionRecordResp {
     pub success: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SpendBundleResp {
+    pub wallet_id: u32,
+    pub spend_bundle: SpendBundle,
+    pub success: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TXResp {
     pub status: TXStatus,
     pub success: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TradeRecordResp {
+    pub trade_record: TradeRecord,
+    pub success: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TransactionRecordResp {
     pub transaction: TransactionRecord,
     pub success: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TransactionRecordsResp {
+    pub transactions: Vec<TransactionRecord>,
+    pub wallet_id: u32,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TransactionResultResp {
+    pub transaction: TransactionRecord,
+    pub transaction_id: Bytes32,
+    pub success: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UnfinishedBlockAryResp {
     pub headers: Vec<UnfinishedBlock>,
@@ -167,7 +448,7 @@ pub struct UnfinishedBlockAryResp {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct WalletBalanceResp {
-    pub wallets: Vec<WalletBalance>,
+    pub wallet_balance: WalletBalance,
     pub success: bool,
 }
 
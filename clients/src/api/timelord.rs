@@ -0,0 +1,12 @@
+use crate::protocols::shared::PeerConnection;
+use async_trait::async_trait;
+use dg_xch_core::blockchain::network_info::NetworkInfo;
+use std::io::Error;
+
+#[async_trait]
+pub trait TimelordAPI {
+    async fn healthz(&self) -> Result<bool, Error>;
+    async fn get_network_info(&self) -> Result<NetworkInfo, Error>;
+    async fn get_connections(&self) -> Result<Vec<PeerConnection>, Error>;
+    async fn get_routes(&self) -> Result<Vec<String>, Error>;
+}
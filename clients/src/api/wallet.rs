@@ -1,11 +1,20 @@
+use crate::protocols::wallet::{
+    AllOffers, CatAssetId, CatSpendRequest, CreateCatWalletRequest, CreateDidWalletRequest,
+    CreateOfferForIdsRequest, CreatedCatWallet, CreatedDidWallet, CreatedOffer, DidInfo,
+    DidTransferRequest, GetAllOffersRequest, MintedNft, NftInfo, NftMintRequest,
+    NftTransferRequest, SentTransaction, TradeRecord,
+};
 use async_trait::async_trait;
 use dg_xch_core::blockchain::announcement::Announcement;
 use dg_xch_core::blockchain::coin::Coin;
 use dg_xch_core::blockchain::pending_payment::PendingPayment;
+use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48};
+use dg_xch_core::blockchain::spend_bundle::SpendBundle;
 use dg_xch_core::blockchain::transaction_record::TransactionRecord;
 use dg_xch_core::blockchain::wallet_balance::WalletBalance;
 use dg_xch_core::blockchain::wallet_info::WalletInfo;
 use dg_xch_core::blockchain::wallet_sync::WalletSync;
+use std::collections::HashMap;
 use std::io::Error;
 
 #[async_trait]
@@ -13,7 +22,7 @@ pub trait WalletAPI {
     async fn log_in(&self, wallet_fingerprint: u32) -> Result<u32, Error>;
     async fn log_in_and_skip(&self, wallet_fingerprint: u32) -> Result<u32, Error>;
     async fn get_wallets(&self) -> Result<Vec<WalletInfo>, Error>;
-    async fn get_wallet_balance(&self, wallet_id: u32) -> Result<Vec<WalletBalance>, Error>;
+    async fn get_wallet_balance(&self, wallet_id: u32) -> Result<WalletBalance, Error>;
     async fn get_sync_status(&self) -> Result<WalletSync, Error>;
     async fn send_transaction(
         &self,
@@ -33,6 +42,12 @@ pub trait WalletAPI {
         wallet_id: u32,
         transaction_id: String,
     ) -> Result<TransactionRecord, Error>;
+    async fn get_transactions(
+        &self,
+        wallet_id: u32,
+        start: Option<u32>,
+        end: Option<u32>,
+    ) -> Result<Vec<TransactionRecord>, Error>;
     async fn create_signed_transaction(
         &self,
         wallet_id: u32,
@@ -42,4 +57,44 @@ pub trait WalletAPI {
         puzzle_announcements: Vec<Announcement>,
         fee: u64,
     ) -> Result<TransactionRecord, Error>;
+    async fn create_cat_wallet(
+        &self,
+        request: &CreateCatWalletRequest,
+    ) -> Result<CreatedCatWallet, Error>;
+    async fn cat_spend(&self, request: &CatSpendRequest) -> Result<SentTransaction, Error>;
+    async fn cat_get_asset_id(&self, wallet_id: u32) -> Result<CatAssetId, Error>;
+    async fn create_offer_for_ids(
+        &self,
+        request: &CreateOfferForIdsRequest,
+    ) -> Result<CreatedOffer, Error>;
+    async fn take_offer(&self, offer: &str, fee: u64) -> Result<TradeRecord, Error>;
+    async fn get_all_offers(&self, request: &GetAllOffersRequest) -> Result<AllOffers, Error>;
+    async fn cancel_offer(&self, trade_id: &Bytes32, secure: bool, fee: u64) -> Result<(), Error>;
+    async fn nft_get_nfts(
+        &self,
+        wallet_id: u32,
+        start_index: Option<u32>,
+        num: Option<u32>,
+    ) -> Result<Vec<NftInfo>, Error>;
+    async fn nft_mint_nft(&self, request: &NftMintRequest) -> Result<MintedNft, Error>;
+    async fn nft_transfer_nft(&self, request: &NftTransferRequest) -> Result<SpendBundle, Error>;
+    async fn create_did_wallet(
+        &self,
+        request: &CreateDidWalletRequest,
+    ) -> Result<CreatedDidWallet, Error>;
+    async fn did_get_did(&self, wallet_id: u32) -> Result<DidInfo, Error>;
+    async fn did_get_pubkey(&self, wallet_id: u32) -> Result<Bytes48, Error>;
+    async fn did_get_wallet_name(&self, wallet_id: u32) -> Result<String, Error>;
+    async fn did_set_wallet_name(&self, wallet_id: u32, name: &str) -> Result<(), Error>;
+    async fn did_get_metadata(&self, wallet_id: u32) -> Result<HashMap<String, String>, Error>;
+    async fn did_update_metadata(
+        &self,
+        wallet_id: u32,
+        metadata: &HashMap<String, String>,
+        fee: u64,
+    ) -> Result<SpendBundle, Error>;
+    async fn did_transfer_did(
+        &self,
+        request: &DidTransferRequest,
+    ) -> Result<SentTransaction, Error>;
 }
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct PeerCounts {
+    pub total_last_5_days: u64,
+    pub reliable_nodes: u64,
+    pub ipv4_last_5_days: u64,
+    pub ipv6_last_5_days: u64,
+    pub versions: HashMap<String, u64>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct IpsAfterTimestamp {
+    pub ips: Vec<String>,
+    pub total: u64,
+}
@@ -0,0 +1,79 @@
+use dg_xch_core::blockchain::sized_bytes::Bytes32;
+use dg_xch_core::blockchain::unsized_bytes::UnsizedBytes;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct KeyValue {
+    pub hash: Bytes32,
+    pub key: UnsizedBytes,
+    pub value: UnsizedBytes,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct DataStoreRoot {
+    pub hash: Bytes32,
+    pub confirmed: bool,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct InternalNode {
+    pub hash: Bytes32,
+    pub left_hash: Bytes32,
+    pub right_hash: Bytes32,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    Insert,
+    Delete,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct DataStoreChange {
+    pub action: ChangeAction,
+    pub key: UnsizedBytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<UnsizedBytes>,
+}
+
+impl DataStoreChange {
+    pub fn insert(key: UnsizedBytes, value: UnsizedBytes) -> Self {
+        DataStoreChange {
+            action: ChangeAction::Insert,
+            key,
+            value: Some(value),
+        }
+    }
+    pub fn delete(key: UnsizedBytes) -> Self {
+        DataStoreChange {
+            action: ChangeAction::Delete,
+            key,
+            value: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DiffType {
+    Insert,
+    Delete,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct KeyValueDiff {
+    #[serde(alias = "type")]
+    pub diff_type: DiffType,
+    pub key: UnsizedBytes,
+    pub value: UnsizedBytes,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct DataStoreSyncStatus {
+    pub root_hash: Bytes32,
+    pub generation: u32,
+    pub target_root_hash: Bytes32,
+    pub target_generation: u32,
+}
@@ -1,10 +1,11 @@
+use crate::protocols::harvester::Plot;
 // use dg_xch_core::blockchain::challenge_chain_subslot::ChallengeChainSubSlot;
 // use dg_xch_core::blockchain::foliage_block_data::FoliageBlockData;
 // use dg_xch_core::blockchain::foliage_transaction_block::FoliageTransactionBlock;
 use dg_xch_core::blockchain::pool_target::PoolTarget;
 use dg_xch_core::blockchain::proof_of_space::ProofOfSpace;
 // use dg_xch_core::blockchain::reward_chain_subslot::RewardChainSubSlot;
-use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes96};
+use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, Bytes96};
 use dg_xch_macros::ChiaSerial;
 use serde::{Deserialize, Serialize};
 
@@ -79,3 +80,95 @@ pub struct SignedValues {
     pub foliage_block_data_signature: Bytes96,
     pub foliage_transaction_block_signature: Bytes96,
 }
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct FarmerSignagePoint {
+    pub signage_point: NewSignagePoint,
+    pub proofs: Vec<(String, ProofOfSpace)>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct RewardTargets {
+    pub farmer_target: String,
+    pub pool_target: String,
+    pub have_farmer_sk: Option<bool>,
+    pub have_pool_sk: Option<bool>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct FarmerPoolConfig {
+    pub launcher_id: Bytes32,
+    pub pool_url: String,
+    pub payout_instructions: String,
+    pub target_puzzle_hash: Bytes32,
+    pub p2_singleton_puzzle_hash: Bytes32,
+    pub owner_public_key: Bytes48,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct FarmerPoolError {
+    pub error_code: u8,
+    pub error_message: String,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct FarmerPoolState {
+    pub p2_singleton_puzzle_hash: Bytes32,
+    pub points_found_since_start: u64,
+    pub points_found_24h: Vec<(f64, u64)>,
+    pub points_acknowledged_since_start: u64,
+    pub points_acknowledged_24h: Vec<(f64, u64)>,
+    pub next_farmer_update: f64,
+    pub next_pool_info_update: f64,
+    pub current_points: u64,
+    pub current_difficulty: Option<u64>,
+    pub pool_errors_24h: Vec<(f64, FarmerPoolError)>,
+    pub authentication_token_timeout: Option<u8>,
+    #[serde(default)]
+    pub plot_count: u32,
+    pub pool_config: FarmerPoolConfig,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct HarvesterConnection {
+    pub node_id: Bytes32,
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct HarvesterSyncStatus {
+    pub initial: bool,
+    pub plot_files_processed: u32,
+    pub plot_files_total: u32,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct HarvesterInfo {
+    pub connection: HarvesterConnection,
+    pub plots: Vec<Plot>,
+    pub failed_to_open_filenames: Vec<String>,
+    pub no_key_filenames: Vec<String>,
+    pub duplicates: Vec<String>,
+    pub total_plot_size: u64,
+    #[serde(default)]
+    pub total_effective_plot_size: u64,
+    pub syncing: Option<HarvesterSyncStatus>,
+    pub last_sync_time: Option<f64>,
+    pub harvesting_mode: Option<u8>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct HarvesterSummary {
+    pub connection: HarvesterConnection,
+    pub plots: u64,
+    pub failed_to_open_filenames: u64,
+    pub no_key_filenames: u64,
+    pub duplicates: u64,
+    pub total_plot_size: u64,
+    #[serde(default)]
+    pub total_effective_plot_size: u64,
+    pub syncing: Option<HarvesterSyncStatus>,
+    pub last_sync_time: Option<f64>,
+    pub harvesting_mode: Option<u8>,
+}
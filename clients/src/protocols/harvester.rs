@@ -159,3 +159,22 @@ pub struct PlotSyncResponse {
     pub message_type: i16,
     pub error: Option<PlotSyncError>,
 }
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct HarvesterPlots {
+    pub plots: Vec<Plot>,
+    pub failed_to_open_filenames: Vec<String>,
+    pub not_found_filenames: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct HarvesterConfig {
+    pub use_gpu_harvesting: bool,
+    pub gpu_index: u8,
+    pub enforce_gpu_index: bool,
+    pub disable_cpu_affinity: bool,
+    pub parallel_decompressor_count: u32,
+    pub decompressor_thread_count: u32,
+    pub recursive_plot_scan: bool,
+    pub refresh_parameter_interval_seconds: u32,
+}
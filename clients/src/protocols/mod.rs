@@ -1,15 +1,18 @@
+pub mod crawler;
+pub mod data_layer;
 pub mod farmer;
 pub mod full_node;
 pub mod harvester;
 pub mod introducer;
 pub mod pool;
+pub mod pool_server;
 pub mod shared;
 pub mod timelord;
 pub mod wallet;
 use dg_xch_macros::ChiaSerial;
 
 #[repr(u8)]
-#[derive(ChiaSerial, Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(ChiaSerial, Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ProtocolMessageTypes {
     Unknown = 0,
     //Shared protocol (all services)
@@ -79,6 +79,14 @@ pub struct PoolError {
     pub error_code: u8,
     pub error_message: String,
 }
+impl PoolError {
+    pub fn new(error_code: PoolErrorCode, error_message: impl Into<String>) -> Self {
+        Self {
+            error_code: error_code as u8,
+            error_message: error_message.into(),
+        }
+    }
+}
 
 #[derive(ChiaSerial, PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
 pub struct AuthenticationPayload {
@@ -218,7 +226,7 @@ pub async fn create_pool_login_url(
         ids.push_str(&hex::encode(launcher_id.as_slice()));
     }
     Ok(format!(
-        "{target_pool}/login?launcher_id={ids}&authentication_token={}&signature={})",
+        "{target_pool}/login?launcher_id={ids}&authentication_token={}&signature={}",
         parts.auth_token, parts.aggregate_signature
     ))
 }
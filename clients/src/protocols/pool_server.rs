@@ -0,0 +1,463 @@
+use crate::api::full_node::FullnodeAPI;
+use crate::protocols::pool::{
+    validate_authentication_token, AuthenticationPayload, PoolError, PoolErrorCode,
+    PostFarmerRequest, PostPartialPayload, PostPartialRequest, PutFarmerRequest, FARMING_TO_POOL,
+    POOL_PROTOCOL_VERSION,
+};
+use blst::min_pk::{PublicKey, Signature};
+use dg_xch_core::blockchain::signage_point_or_eos::SignagePointOrEOS;
+use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, Bytes96, SizedBytes};
+use dg_xch_core::clvm::bls_bindings::{aggregate_verify_signature, verify_signature};
+use dg_xch_core::consensus::constants::ConsensusConstants;
+use dg_xch_core::consensus::pot_iterations::{calculate_iterations_quality, ITERS_LIMIT};
+use dg_xch_core::plots::PlotNft;
+use dg_xch_core::pool::{Farmer, PoolState};
+use dg_xch_pos::verify_and_get_quality_string;
+use dg_xch_serialize::{hash_256, ChiaSerialize};
+use log::debug;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+// Seconds a partial may arrive after the node first saw its signage point
+pub const PARTIAL_TIME_LIMIT: u64 = 25;
+// Shortest delay a farmer may set for claiming rewards back from the pool
+pub const MIN_DELAY_TIME: u64 = 3600;
+// The node may not have seen the signage point yet, it is looked up again after this delay
+pub const SIGNAGE_POINT_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidatedPartial {
+    pub launcher_id: Bytes32,
+    pub harvester_id: Bytes32,
+    pub quality_string: Bytes32,
+    pub required_iters: u64,
+    pub difficulty: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct PartialValidator {
+    pub constants: ConsensusConstants,
+    pub authentication_token_timeout: u8,
+    pub partial_time_limit: u64,
+    pub iters_limit: u64,
+}
+impl PartialValidator {
+    pub fn new(constants: ConsensusConstants, authentication_token_timeout: u8) -> Self {
+        Self {
+            constants,
+            authentication_token_timeout,
+            partial_time_limit: PARTIAL_TIME_LIMIT,
+            iters_limit: ITERS_LIMIT,
+        }
+    }
+
+    // Runs the same checks as the reference pool in the same order, `signage_point`
+    // is the node's answer to get_recent_signage_point_or_eos for the partial
+    pub fn validate(
+        &self,
+        partial: &PostPartialRequest,
+        farmer: &Farmer,
+        signage_point: Option<&SignagePointOrEOS>,
+        time_received: u64,
+    ) -> Result<ValidatedPartial, PoolError> {
+        let payload = &partial.payload;
+        if payload.launcher_id != farmer.launcher_id {
+            return Err(PoolError::new(
+                PoolErrorCode::FarmerNotKnown,
+                format!("Partial is for launcher {}", payload.launcher_id),
+            ));
+        }
+        if !validate_authentication_token(
+            payload.authentication_token,
+            self.authentication_token_timeout,
+        ) {
+            return Err(PoolError::new(
+                PoolErrorCode::InvalidAuthenticationToken,
+                format!(
+                    "Invalid authentication token {}",
+                    payload.authentication_token
+                ),
+            ));
+        }
+        self.validate_signature(partial, farmer)?;
+        if payload.proof_of_space.pool_contract_puzzle_hash != Some(farmer.p2_singleton_puzzle_hash)
+        {
+            return Err(PoolError::new(
+                PoolErrorCode::InvalidP2SingletonPuzzleHash,
+                format!(
+                    "Invalid pool contract puzzle hash {:?}",
+                    payload.proof_of_space.pool_contract_puzzle_hash
+                ),
+            ));
+        }
+        let signage_point = match signage_point {
+            Some(sp) if !sp.reverted => sp,
+            _ => {
+                return Err(PoolError::new(
+                    PoolErrorCode::NotFound,
+                    format!("Did not find signage point or EOS {}", payload.sp_hash),
+                ));
+            }
+        };
+        let delay = time_received.saturating_sub(signage_point.time_received as u64);
+        if delay > self.partial_time_limit {
+            return Err(PoolError::new(
+                PoolErrorCode::TooLate,
+                format!(
+                    "Received partial {delay}s after the signage point, the limit is {}s",
+                    self.partial_time_limit
+                ),
+            ));
+        }
+        let challenge_hash = challenge_hash(signage_point).ok_or_else(|| {
+            PoolError::new(
+                PoolErrorCode::NotFound,
+                format!("Signage point {} has no challenge", payload.sp_hash),
+            )
+        })?;
+        // validate_proof returns an empty quality string when the proof does not match the challenge
+        let quality_string = verify_and_get_quality_string(
+            &payload.proof_of_space,
+            &self.constants,
+            &challenge_hash,
+            &payload.sp_hash,
+        )
+        .filter(|q| *q != Bytes32::default())
+        .ok_or_else(|| {
+            PoolError::new(
+                PoolErrorCode::InvalidProof,
+                format!("Invalid proof of space {}", payload.sp_hash),
+            )
+        })?;
+        let required_iters = calculate_iterations_quality(
+            self.constants.difficulty_constant_factor,
+            &quality_string,
+            payload.proof_of_space.size,
+            farmer.difficulty,
+            &payload.sp_hash,
+        );
+        if required_iters >= self.iters_limit {
+            return Err(PoolError::new(
+                PoolErrorCode::ProofNotGoodEnough,
+                format!(
+                    "Proof of space has required iters {required_iters}, too high for difficulty {}",
+                    farmer.difficulty
+                ),
+            ));
+        }
+        Ok(ValidatedPartial {
+            launcher_id: payload.launcher_id,
+            harvester_id: payload.harvester_id,
+            quality_string,
+            required_iters,
+            difficulty: farmer.difficulty,
+        })
+    }
+
+    // The partial is signed by both the plot key and the farmer's authentication key
+    pub fn validate_signature(
+        &self,
+        partial: &PostPartialRequest,
+        farmer: &Farmer,
+    ) -> Result<(), PoolError> {
+        let invalid = || {
+            PoolError::new(
+                PoolErrorCode::InvalidSignature,
+                format!(
+                    "The aggregate signature is invalid {}",
+                    partial.aggregate_signature
+                ),
+            )
+        };
+        let signature: Signature = (&partial.aggregate_signature)
+            .try_into()
+            .map_err(|_| invalid())?;
+        let message = hash_256(partial.payload.to_bytes());
+        if aggregate_verify_signature(
+            &[
+                &partial.payload.proof_of_space.plot_public_key,
+                &farmer.authentication_public_key,
+            ],
+            &vec![message.as_slice(), message.as_slice()],
+            &signature,
+        ) {
+            Ok(())
+        } else {
+            Err(invalid())
+        }
+    }
+
+    // Looks up the partial's signage point on the node before validating, retrying
+    // once in case the partial arrived before the node saw the signage point
+    pub async fn validate_with_node<T: FullnodeAPI + Sync>(
+        &self,
+        client: &T,
+        partial: &PostPartialRequest,
+        farmer: &Farmer,
+    ) -> Result<ValidatedPartial, PoolError> {
+        let time_received = OffsetDateTime::now_utc().unix_timestamp() as u64;
+        let mut signage_point = fetch_signage_point(client, &partial.payload).await;
+        if signage_point.is_none() {
+            tokio::time::sleep(SIGNAGE_POINT_RETRY_DELAY).await;
+            signage_point = fetch_signage_point(client, &partial.payload).await;
+        }
+        self.validate(partial, farmer, signage_point.as_ref(), time_received)
+    }
+}
+
+async fn fetch_signage_point<T: FullnodeAPI + Sync>(
+    client: &T,
+    payload: &PostPartialPayload,
+) -> Option<SignagePointOrEOS> {
+    let result = if payload.end_of_sub_slot {
+        client
+            .get_recent_signage_point_or_eos(None, Some(&payload.sp_hash))
+            .await
+    } else {
+        client
+            .get_recent_signage_point_or_eos(Some(&payload.sp_hash), None)
+            .await
+    };
+    result
+        .map_err(|e| debug!("Failed to find signage point {}: {:?}", payload.sp_hash, e))
+        .ok()
+}
+
+fn challenge_hash(signage_point: &SignagePointOrEOS) -> Option<Bytes32> {
+    if let Some(sp) = &signage_point.signage_point {
+        Some(sp.cc_vdf.challenge)
+    } else {
+        signage_point
+            .eos
+            .as_ref()
+            .map(|eos| Bytes32::new(&hash_256(eos.challenge_chain.to_bytes())))
+    }
+}
+
+// A singleton is a member when it is farming to this pool with the pool's lock height
+pub fn is_pool_member(
+    pool_state: &PoolState,
+    target_puzzle_hash: &Bytes32,
+    relative_lock_height: u32,
+) -> bool {
+    pool_state.target_puzzle_hash == *target_puzzle_hash
+        && pool_state.relative_lock_height == relative_lock_height
+        && pool_state.version == POOL_PROTOCOL_VERSION
+        && pool_state.state == FARMING_TO_POOL
+}
+
+// Checks a POST /farmer request against the farmer's PlotNFT, which is signed
+// by the singleton owner key
+pub fn verify_post_farmer(
+    request: &PostFarmerRequest,
+    plot_nft: &PlotNft,
+    authentication_token_timeout: u8,
+) -> Result<(), PoolError> {
+    let payload = &request.payload;
+    verify_farmer_request(
+        &payload.launcher_id,
+        payload.authentication_token,
+        &hash_256(payload.to_bytes()),
+        &request.signature,
+        plot_nft,
+        authentication_token_timeout,
+    )?;
+    let delay_time = u64::try_from(plot_nft.delay_time).map_err(|_| {
+        PoolError::new(
+            PoolErrorCode::DelayTimeTooShort,
+            format!(
+                "Delay time can not be negative, got {}",
+                plot_nft.delay_time
+            ),
+        )
+    })?;
+    if delay_time < MIN_DELAY_TIME {
+        return Err(PoolError::new(
+            PoolErrorCode::DelayTimeTooShort,
+            format!(
+                "Delay time too short, must be at least {MIN_DELAY_TIME} seconds, got {delay_time}"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+// Checks a PUT /farmer request against the farmer's PlotNFT
+pub fn verify_put_farmer(
+    request: &PutFarmerRequest,
+    plot_nft: &PlotNft,
+    authentication_token_timeout: u8,
+) -> Result<(), PoolError> {
+    let payload = &request.payload;
+    verify_farmer_request(
+        &payload.launcher_id,
+        payload.authentication_token,
+        &hash_256(payload.to_bytes()),
+        &request.signature,
+        plot_nft,
+        authentication_token_timeout,
+    )
+}
+
+fn verify_farmer_request(
+    launcher_id: &Bytes32,
+    authentication_token: u64,
+    message: &[u8],
+    signature: &Bytes96,
+    plot_nft: &PlotNft,
+    authentication_token_timeout: u8,
+) -> Result<(), PoolError> {
+    if *launcher_id != plot_nft.launcher_id {
+        return Err(PoolError::new(
+            PoolErrorCode::InvalidSingleton,
+            format!(
+                "Request is for launcher {launcher_id}, singleton is {}",
+                plot_nft.launcher_id
+            ),
+        ));
+    }
+    if !validate_authentication_token(authentication_token, authentication_token_timeout) {
+        return Err(PoolError::new(
+            PoolErrorCode::InvalidAuthenticationToken,
+            format!("Invalid authentication token {authentication_token}"),
+        ));
+    }
+    if !verify(&plot_nft.pool_state.owner_pubkey, message, signature) {
+        return Err(PoolError::new(
+            PoolErrorCode::InvalidSignature,
+            format!("Failed to verify signature {signature} for launcher {launcher_id}"),
+        ));
+    }
+    Ok(())
+}
+
+fn verify(public_key: &Bytes48, message: &[u8], signature: &Bytes96) -> bool {
+    let public_key = match PublicKey::from_bytes(public_key.as_slice()) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    match Signature::try_from(signature) {
+        Ok(signature) => verify_signature(&public_key, message, &signature),
+        Err(_) => false,
+    }
+}
+
+// The query of a GET /login link built from `create_pool_login_parts`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolLogin {
+    pub launcher_ids: Vec<Bytes32>,
+    pub authentication_token: u64,
+    pub signature: Bytes96,
+}
+impl PoolLogin {
+    // Accepts either the full login url or only its query string
+    pub fn from_url(url: &str) -> Result<Self, PoolError> {
+        let query = url.split_once('?').map(|(_, q)| q).unwrap_or(url);
+        let mut launcher_ids = None;
+        let mut authentication_token = None;
+        let mut signature = None;
+        for pair in query.split('&') {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match key {
+                "launcher_id" => {
+                    launcher_ids = Some(
+                        value
+                            .split(',')
+                            .map(|id| Ok(Bytes32::new(&decode_hex(id, 32)?)))
+                            .collect::<Result<Vec<_>, PoolError>>()?,
+                    )
+                }
+                "authentication_token" => {
+                    authentication_token = Some(value.parse().map_err(|_| {
+                        PoolError::new(
+                            PoolErrorCode::InvalidAuthenticationToken,
+                            format!("Invalid authentication token {value}"),
+                        )
+                    })?)
+                }
+                "signature" => signature = Some(Bytes96::new(&decode_hex(value, 96)?)),
+                _ => {}
+            }
+        }
+        match (launcher_ids, authentication_token, signature) {
+            (Some(launcher_ids), Some(authentication_token), Some(signature)) => Ok(Self {
+                launcher_ids,
+                authentication_token,
+                signature,
+            }),
+            _ => Err(PoolError::new(
+                PoolErrorCode::RequestFailed,
+                "Login requires launcher_id, authentication_token and signature",
+            )),
+        }
+    }
+
+    // `authentication_public_keys` are the farmers' keys in the same order as
+    // `launcher_ids`, each signed a get_login AuthenticationPayload
+    pub fn verify(
+        &self,
+        authentication_public_keys: &[Bytes48],
+        target_puzzle_hash: &Bytes32,
+        authentication_token_timeout: u8,
+    ) -> Result<(), PoolError> {
+        if authentication_public_keys.len() != self.launcher_ids.len() {
+            return Err(PoolError::new(
+                PoolErrorCode::FarmerNotKnown,
+                format!(
+                    "Expected {} authentication keys, got {}",
+                    self.launcher_ids.len(),
+                    authentication_public_keys.len()
+                ),
+            ));
+        }
+        if !validate_authentication_token(self.authentication_token, authentication_token_timeout) {
+            return Err(PoolError::new(
+                PoolErrorCode::InvalidAuthenticationToken,
+                format!("Invalid authentication token {}", self.authentication_token),
+            ));
+        }
+        let messages: Vec<Vec<u8>> = self
+            .launcher_ids
+            .iter()
+            .map(|launcher_id| {
+                hash_256(
+                    AuthenticationPayload {
+                        method_name: String::from("get_login"),
+                        launcher_id: *launcher_id,
+                        target_puzzle_hash: *target_puzzle_hash,
+                        authentication_token: self.authentication_token,
+                    }
+                    .to_bytes(),
+                )
+            })
+            .collect();
+        let verified = match Signature::try_from(&self.signature) {
+            Ok(signature) => aggregate_verify_signature(
+                &authentication_public_keys.iter().collect::<Vec<_>>(),
+                &messages.iter().map(|m| m.as_slice()).collect(),
+                &signature,
+            ),
+            Err(_) => false,
+        };
+        if verified {
+            Ok(())
+        } else {
+            Err(PoolError::new(
+                PoolErrorCode::InvalidSignature,
+                format!("Failed to verify login signature {}", self.signature),
+            ))
+        }
+    }
+}
+
+fn decode_hex(value: &str, size: usize) -> Result<Vec<u8>, PoolError> {
+    hex::decode(value.trim_start_matches("0x"))
+        .ok()
+        .filter(|bytes| bytes.len() == size)
+        .ok_or_else(|| {
+            PoolError::new(
+                PoolErrorCode::RequestFailed,
+                format!("Expected {size} hex encoded bytes, got {value}"),
+            )
+        })
+}
@@ -1,3 +1,4 @@
+use dg_xch_core::blockchain::sized_bytes::Bytes32;
 use dg_xch_macros::ChiaSerial;
 use log::error;
 use rustls::client::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
@@ -29,6 +30,21 @@ pub struct Handshake {
     pub capabilities: Vec<(u16, String)>,
 }
 
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct PeerConnection {
+    #[serde(alias = "type")]
+    pub node_type: u8,
+    pub local_port: u16,
+    pub peer_host: String,
+    pub peer_port: u16,
+    pub peer_server_port: u16,
+    pub node_id: Bytes32,
+    pub creation_time: f64,
+    pub bytes_read: Option<u64>,
+    pub bytes_written: Option<u64>,
+    pub last_message_time: f64,
+}
+
 pub const CAPABILITIES: [(u16, &str); 3] = [
     (Capability::Base as u16, "1"),
     (Capability::BlockHeaders as u16, "1"),
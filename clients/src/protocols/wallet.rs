@@ -2,9 +2,13 @@ use dg_xch_core::blockchain::coin::Coin;
 use dg_xch_core::blockchain::header_block::HeaderBlock;
 use dg_xch_core::blockchain::sized_bytes::Bytes32;
 use dg_xch_core::blockchain::spend_bundle::SpendBundle;
+use dg_xch_core::blockchain::transaction_record::TransactionRecord;
+use dg_xch_core::blockchain::unsized_bytes::UnsizedBytes;
 use dg_xch_core::clvm::program::SerializedProgram;
 use dg_xch_macros::ChiaSerial;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
 #[derive(ChiaSerial, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub struct RequestPuzzleSolution {
@@ -155,6 +159,13 @@ pub struct RegisterForPhUpdates {
     pub min_height: u32,
 }
 
+#[derive(ChiaSerial, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct RespondToPhUpdates {
+    pub puzzle_hashes: Vec<Bytes32>,
+    pub min_height: u32,
+    pub coin_states: Vec<CoinState>,
+}
+
 #[derive(ChiaSerial, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub struct CoinState {
     pub coin: Coin,
@@ -232,3 +243,304 @@ pub struct FeeEstimateGroup {
 pub struct FeeRate {
     pub mojos_per_clvm_cost: u64,
 }
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct CatSpendAddition {
+    pub puzzle_hash: Bytes32,
+    pub amount: u64,
+    #[serde(default)]
+    pub memos: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+pub struct CatSpendRequest {
+    pub wallet_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inner_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additions: Option<Vec<CatSpendAddition>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memos: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coins: Option<Vec<Coin>>,
+    pub fee: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_coin_amount: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_coin_amount: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_coin_amounts: Option<Vec<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reuse_puzhash: Option<bool>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum NewWalletMode {
+    New,
+    Existing,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct CreateCatWalletRequest {
+    pub wallet_type: String,
+    pub mode: NewWalletMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_id: Option<Bytes32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub fee: u64,
+}
+impl CreateCatWalletRequest {
+    pub fn new(amount: u64, name: Option<String>, fee: u64) -> Self {
+        CreateCatWalletRequest {
+            wallet_type: "cat_wallet".to_string(),
+            mode: NewWalletMode::New,
+            amount: Some(amount),
+            asset_id: None,
+            name,
+            fee,
+        }
+    }
+    pub fn existing(asset_id: Bytes32, name: Option<String>) -> Self {
+        CreateCatWalletRequest {
+            wallet_type: "cat_wallet".to_string(),
+            mode: NewWalletMode::Existing,
+            amount: None,
+            asset_id: Some(asset_id),
+            name,
+            fee: 0,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct CreatedCatWallet {
+    pub wallet_id: u32,
+    pub wallet_type: u8,
+    pub asset_id: Bytes32,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct SentTransaction {
+    pub transaction: TransactionRecord,
+    pub transaction_id: Bytes32,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct CatAssetId {
+    pub wallet_id: u32,
+    pub asset_id: Bytes32,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+pub struct CreateOfferForIdsRequest {
+    pub offer: HashMap<String, i64>,
+    pub fee: u64,
+    pub validate_only: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver_dict: Option<HashMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_coin_amount: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_coin_amount: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reuse_puzhash: Option<bool>,
+}
+impl CreateOfferForIdsRequest {
+    pub fn add(mut self, wallet_id: u32, amount: i64) -> Self {
+        self.offer.insert(wallet_id.to_string(), amount);
+        self
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug, Default)]
+pub struct OfferSummary {
+    #[serde(default)]
+    pub offered: HashMap<String, i64>,
+    #[serde(default)]
+    pub requested: HashMap<String, i64>,
+    #[serde(default)]
+    pub fees: u64,
+    #[serde(default)]
+    pub infos: HashMap<String, Value>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct TradeRecord {
+    pub trade_id: Bytes32,
+    pub status: String,
+    pub confirmed_at_index: u32,
+    pub accepted_at_time: Option<u64>,
+    pub created_at_time: u64,
+    pub is_my_offer: bool,
+    pub sent: u32,
+    #[serde(default)]
+    pub sent_to: Vec<(String, u8, Option<String>)>,
+    pub taken_offer: Option<UnsizedBytes>,
+    pub coins_of_interest: Vec<Coin>,
+    #[serde(default)]
+    pub summary: OfferSummary,
+    #[serde(default)]
+    pub pending: HashMap<String, u64>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct CreatedOffer {
+    pub offer: String,
+    pub trade_record: TradeRecord,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct GetAllOffersRequest {
+    pub start: u32,
+    pub end: u32,
+    pub exclude_my_offers: bool,
+    pub exclude_taken_offers: bool,
+    pub include_completed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_key: Option<String>,
+    pub reverse: bool,
+    pub file_contents: bool,
+}
+impl Default for GetAllOffersRequest {
+    fn default() -> Self {
+        GetAllOffersRequest {
+            start: 0,
+            end: 10,
+            exclude_my_offers: false,
+            exclude_taken_offers: false,
+            include_completed: false,
+            sort_key: None,
+            reverse: false,
+            file_contents: false,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct AllOffers {
+    pub trade_records: Vec<TradeRecord>,
+    pub offers: Option<Vec<String>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct NftInfo {
+    pub nft_id: String,
+    pub launcher_id: Bytes32,
+    pub nft_coin_id: Bytes32,
+    pub nft_coin_confirmation_height: u32,
+    pub owner_did: Option<Bytes32>,
+    pub royalty_percentage: Option<u16>,
+    pub royalty_puzzle_hash: Option<Bytes32>,
+    pub data_uris: Vec<String>,
+    pub data_hash: UnsizedBytes,
+    pub metadata_uris: Vec<String>,
+    pub metadata_hash: UnsizedBytes,
+    pub license_uris: Vec<String>,
+    pub license_hash: UnsizedBytes,
+    pub edition_total: u64,
+    pub edition_number: u64,
+    pub updater_puzhash: Bytes32,
+    pub chain_info: String,
+    pub mint_height: u32,
+    pub supports_did: bool,
+    pub p2_address: Bytes32,
+    pub pending_transaction: bool,
+    pub minter_did: Option<Bytes32>,
+    pub launcher_puzhash: Bytes32,
+    pub off_chain_metadata: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct NftMintRequest {
+    pub wallet_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_address: Option<String>,
+    pub uris: Vec<String>,
+    pub hash: Bytes32,
+    pub meta_uris: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta_hash: Option<Bytes32>,
+    pub license_uris: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_hash: Option<Bytes32>,
+    pub edition_number: u64,
+    pub edition_total: u64,
+    pub royalty_percentage: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub did_id: Option<String>,
+    pub fee: u64,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct MintedNft {
+    pub wallet_id: u32,
+    pub nft_id: String,
+    pub spend_bundle: SpendBundle,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct NftTransferRequest {
+    pub wallet_id: u32,
+    pub target_address: String,
+    pub nft_coin_id: Bytes32,
+    pub fee: u64,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct CreateDidWalletRequest {
+    pub wallet_type: String,
+    pub did_type: NewWalletMode,
+    pub amount: u64,
+    pub backup_dids: Vec<String>,
+    pub num_of_backup_ids_needed: u64,
+    pub metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_name: Option<String>,
+    pub fee: u64,
+}
+impl CreateDidWalletRequest {
+    pub fn new(amount: u64, fee: u64) -> Self {
+        CreateDidWalletRequest {
+            wallet_type: "did_wallet".to_string(),
+            did_type: NewWalletMode::New,
+            amount,
+            backup_dids: vec![],
+            num_of_backup_ids_needed: 0,
+            metadata: HashMap::new(),
+            wallet_name: None,
+            fee,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct CreatedDidWallet {
+    pub wallet_id: u32,
+    pub wallet_type: u8,
+    pub my_did: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct DidInfo {
+    pub wallet_id: u32,
+    pub my_did: String,
+    pub coin_id: Option<Bytes32>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct DidTransferRequest {
+    pub wallet_id: u32,
+    pub inner_address: String,
+    pub fee: u64,
+    pub with_recovery_info: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reuse_puzhash: Option<bool>,
+}
@@ -0,0 +1,74 @@
+use crate::api::crawler::CrawlerAPI;
+use crate::api::responses::{IpsAfterTimestampResp, PeerCountsResp};
+use crate::protocols::crawler::{IpsAfterTimestamp, PeerCounts};
+use crate::rpc::{get_client, get_url, post};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Map};
+use std::collections::HashMap;
+use std::io::Error;
+
+pub struct CrawlerClient {
+    client: Client,
+    pub host: String,
+    pub port: u16,
+    pub ssl_path: Option<String>,
+    pub additional_headers: Option<HashMap<String, String>>,
+}
+
+impl CrawlerClient {
+    pub fn new(
+        host: &str,
+        port: u16,
+        ssl_path: Option<String>,
+        additional_headers: &Option<HashMap<String, String>>,
+    ) -> Self {
+        CrawlerClient {
+            client: get_client(ssl_path.clone()).unwrap_or_default(),
+            host: host.to_string(),
+            port,
+            ssl_path,
+            additional_headers: additional_headers.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl CrawlerAPI for CrawlerClient {
+    async fn get_peer_counts(&self) -> Result<PeerCounts, Error> {
+        Ok(post::<PeerCountsResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_peer_counts"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?
+        .peer_counts)
+    }
+    async fn get_ips_after_timestamp(
+        &self,
+        after: u64,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<IpsAfterTimestamp, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("after".to_string(), json!(after));
+        if let Some(offset) = offset {
+            request_body.insert("offset".to_string(), json!(offset));
+        }
+        if let Some(limit) = limit {
+            request_body.insert("limit".to_string(), json!(limit));
+        }
+        let resp = post::<IpsAfterTimestampResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_ips_after_timestamp"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(IpsAfterTimestamp {
+            ips: resp.ips,
+            total: resp.total,
+        })
+    }
+}
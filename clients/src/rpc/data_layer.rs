@@ -0,0 +1,275 @@
+use crate::api::data_layer::DataLayerAPI;
+use crate::api::responses::{
+    AncestorsResp, DataStoreIdResp, DataStoreRootResp, DataStoreSyncStatusResp, DataStoreTXResp,
+    DataStoreValueResp, KeyValueDiffResp, KeysResp, KeysValuesResp,
+};
+use crate::protocols::data_layer::{
+    DataStoreChange, DataStoreRoot, DataStoreSyncStatus, InternalNode, KeyValue, KeyValueDiff,
+};
+use crate::rpc::{get_client, get_url, post, post_empty};
+use async_trait::async_trait;
+use dg_xch_core::blockchain::sized_bytes::Bytes32;
+use dg_xch_core::blockchain::unsized_bytes::UnsizedBytes;
+use reqwest::Client;
+use serde_json::{json, Map};
+use std::collections::HashMap;
+use std::io::Error;
+
+pub struct DataLayerClient {
+    client: Client,
+    pub host: String,
+    pub port: u16,
+    pub ssl_path: Option<String>,
+    pub additional_headers: Option<HashMap<String, String>>,
+}
+
+impl DataLayerClient {
+    pub fn new(
+        host: &str,
+        port: u16,
+        ssl_path: Option<String>,
+        additional_headers: &Option<HashMap<String, String>>,
+    ) -> Self {
+        DataLayerClient {
+            client: get_client(ssl_path.clone()).unwrap_or_default(),
+            host: host.to_string(),
+            port,
+            ssl_path,
+            additional_headers: additional_headers.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl DataLayerAPI for DataLayerClient {
+    async fn create_data_store(&self, fee: Option<u64>) -> Result<Bytes32, Error> {
+        let mut request_body = Map::new();
+        if let Some(fee) = fee {
+            request_body.insert("fee".to_string(), json!(fee));
+        }
+        Ok(post::<DataStoreIdResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "create_data_store"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .id)
+    }
+    async fn get_value(
+        &self,
+        id: &Bytes32,
+        key: &UnsizedBytes,
+        root_hash: Option<&Bytes32>,
+    ) -> Result<Option<UnsizedBytes>, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        request_body.insert("key".to_string(), json!(key));
+        if let Some(root_hash) = root_hash {
+            request_body.insert("root_hash".to_string(), json!(root_hash));
+        }
+        Ok(post::<DataStoreValueResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_value"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .value)
+    }
+    async fn get_keys(
+        &self,
+        id: &Bytes32,
+        root_hash: Option<&Bytes32>,
+    ) -> Result<Vec<UnsizedBytes>, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        if let Some(root_hash) = root_hash {
+            request_body.insert("root_hash".to_string(), json!(root_hash));
+        }
+        Ok(post::<KeysResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_keys"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .keys)
+    }
+    async fn get_keys_values(
+        &self,
+        id: &Bytes32,
+        root_hash: Option<&Bytes32>,
+    ) -> Result<Vec<KeyValue>, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        if let Some(root_hash) = root_hash {
+            request_body.insert("root_hash".to_string(), json!(root_hash));
+        }
+        Ok(post::<KeysValuesResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_keys_values"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .keys_values)
+    }
+    async fn get_root(&self, id: &Bytes32) -> Result<DataStoreRoot, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        let resp = post::<DataStoreRootResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_root"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(DataStoreRoot {
+            hash: resp.hash,
+            confirmed: resp.confirmed,
+            timestamp: resp.timestamp,
+        })
+    }
+    async fn get_ancestors(
+        &self,
+        id: &Bytes32,
+        hash: &Bytes32,
+    ) -> Result<Vec<InternalNode>, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        request_body.insert("hash".to_string(), json!(hash));
+        Ok(post::<AncestorsResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_ancestors"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .ancestors)
+    }
+    async fn batch_update(
+        &self,
+        id: &Bytes32,
+        changelist: &[DataStoreChange],
+        fee: Option<u64>,
+    ) -> Result<Bytes32, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        request_body.insert("changelist".to_string(), json!(changelist));
+        if let Some(fee) = fee {
+            request_body.insert("fee".to_string(), json!(fee));
+        }
+        Ok(post::<DataStoreTXResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "batch_update"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .tx_id)
+    }
+    async fn insert(
+        &self,
+        id: &Bytes32,
+        key: &UnsizedBytes,
+        value: &UnsizedBytes,
+        fee: Option<u64>,
+    ) -> Result<Bytes32, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        request_body.insert("key".to_string(), json!(key));
+        request_body.insert("value".to_string(), json!(value));
+        if let Some(fee) = fee {
+            request_body.insert("fee".to_string(), json!(fee));
+        }
+        Ok(post::<DataStoreTXResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "insert"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .tx_id)
+    }
+    async fn delete_key(
+        &self,
+        id: &Bytes32,
+        key: &UnsizedBytes,
+        fee: Option<u64>,
+    ) -> Result<Bytes32, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        request_body.insert("key".to_string(), json!(key));
+        if let Some(fee) = fee {
+            request_body.insert("fee".to_string(), json!(fee));
+        }
+        Ok(post::<DataStoreTXResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "delete_key"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .tx_id)
+    }
+    async fn subscribe(&self, id: &Bytes32, urls: &[String]) -> Result<(), Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        request_body.insert("urls".to_string(), json!(urls));
+        post_empty(
+            &self.client,
+            &self.host,
+            self.port,
+            "subscribe",
+            &request_body,
+            &self.additional_headers,
+        )
+        .await
+    }
+    async fn unsubscribe(&self, id: &Bytes32, retain: bool) -> Result<(), Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        request_body.insert("retain".to_string(), json!(retain));
+        post_empty(
+            &self.client,
+            &self.host,
+            self.port,
+            "unsubscribe",
+            &request_body,
+            &self.additional_headers,
+        )
+        .await
+    }
+    async fn get_kv_diff(
+        &self,
+        id: &Bytes32,
+        hash_1: &Bytes32,
+        hash_2: &Bytes32,
+    ) -> Result<Vec<KeyValueDiff>, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        request_body.insert("hash_1".to_string(), json!(hash_1));
+        request_body.insert("hash_2".to_string(), json!(hash_2));
+        Ok(post::<KeyValueDiffResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_kv_diff"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .diff)
+    }
+    async fn get_sync_status(&self, id: &Bytes32) -> Result<DataStoreSyncStatus, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("id".to_string(), json!(id));
+        Ok(post::<DataStoreSyncStatusResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_sync_status"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .sync_status)
+    }
+}
@@ -0,0 +1,193 @@
+use crate::api::farmer::FarmerAPI;
+use crate::api::responses::{
+    EmptyResp, FarmerPoolStateResp, FarmerSignagePointAryResp, FarmerSignagePointResp,
+    HarvesterInfoAryResp, HarvesterSummaryAryResp, PoolLoginLinkResp, RewardTargetsResp,
+};
+use crate::protocols::farmer::{
+    FarmerPoolState, FarmerSignagePoint, HarvesterInfo, HarvesterSummary, RewardTargets,
+};
+use crate::rpc::{get_client, get_url, post};
+use async_trait::async_trait;
+use dg_xch_core::blockchain::sized_bytes::Bytes32;
+use reqwest::Client;
+use serde_json::{json, Map};
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+
+pub struct FarmerClient {
+    client: Client,
+    pub host: String,
+    pub port: u16,
+    pub ssl_path: Option<String>,
+    pub additional_headers: Option<HashMap<String, String>>,
+}
+
+impl FarmerClient {
+    pub fn new(
+        host: &str,
+        port: u16,
+        ssl_path: Option<String>,
+        additional_headers: &Option<HashMap<String, String>>,
+    ) -> Self {
+        FarmerClient {
+            client: get_client(ssl_path.clone()).unwrap_or_default(),
+            host: host.to_string(),
+            port,
+            ssl_path,
+            additional_headers: additional_headers.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl FarmerAPI for FarmerClient {
+    async fn get_signage_point(&self, sp_hash: &Bytes32) -> Result<FarmerSignagePoint, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("sp_hash".to_string(), json!(sp_hash));
+        let resp = post::<FarmerSignagePointResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_signage_point"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(FarmerSignagePoint {
+            signage_point: resp.signage_point,
+            proofs: resp.proofs,
+        })
+    }
+    async fn get_signage_points(&self) -> Result<Vec<FarmerSignagePoint>, Error> {
+        Ok(post::<FarmerSignagePointAryResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_signage_points"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?
+        .signage_points)
+    }
+    async fn get_reward_targets(
+        &self,
+        search_for_private_key: bool,
+        max_ph_to_search: Option<u32>,
+    ) -> Result<RewardTargets, Error> {
+        let mut request_body = Map::new();
+        request_body.insert(
+            "search_for_private_key".to_string(),
+            json!(search_for_private_key),
+        );
+        if let Some(max_ph_to_search) = max_ph_to_search {
+            request_body.insert("max_ph_to_search".to_string(), json!(max_ph_to_search));
+        }
+        let resp = post::<RewardTargetsResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_reward_targets"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(RewardTargets {
+            farmer_target: resp.farmer_target,
+            pool_target: resp.pool_target,
+            have_farmer_sk: resp.have_farmer_sk,
+            have_pool_sk: resp.have_pool_sk,
+        })
+    }
+    async fn set_reward_targets(
+        &self,
+        farmer_target: Option<String>,
+        pool_target: Option<String>,
+    ) -> Result<(), Error> {
+        let mut request_body = Map::new();
+        if let Some(farmer_target) = farmer_target {
+            request_body.insert("farmer_target".to_string(), json!(farmer_target));
+        }
+        if let Some(pool_target) = pool_target {
+            request_body.insert("pool_target".to_string(), json!(pool_target));
+        }
+        let resp = post::<EmptyResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "set_reward_targets"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?;
+        if resp.success {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorKind::Other,
+                format!("Failed to set reward targets: {:?}", resp.error),
+            ))
+        }
+    }
+    async fn get_pool_state(&self) -> Result<Vec<FarmerPoolState>, Error> {
+        Ok(post::<FarmerPoolStateResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_pool_state"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?
+        .pool_state)
+    }
+    async fn set_payout_instructions(
+        &self,
+        launcher_id: &Bytes32,
+        payout_instructions: &str,
+    ) -> Result<(), Error> {
+        let mut request_body = Map::new();
+        request_body.insert("launcher_id".to_string(), json!(launcher_id));
+        request_body.insert(
+            "payout_instructions".to_string(),
+            json!(payout_instructions),
+        );
+        let resp = post::<EmptyResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "set_payout_instructions"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?;
+        if resp.success {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorKind::Other,
+                format!("Failed to set payout instructions: {:?}", resp.error),
+            ))
+        }
+    }
+    async fn get_harvesters(&self) -> Result<Vec<HarvesterInfo>, Error> {
+        Ok(post::<HarvesterInfoAryResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_harvesters"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?
+        .harvesters)
+    }
+    async fn get_harvesters_summary(&self) -> Result<Vec<HarvesterSummary>, Error> {
+        Ok(post::<HarvesterSummaryAryResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_harvesters_summary"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?
+        .harvesters)
+    }
+    async fn get_pool_login_link(&self, launcher_id: &Bytes32) -> Result<String, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("launcher_id".to_string(), json!(launcher_id));
+        Ok(post::<PoolLoginLinkResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_pool_login_link"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .login_link)
+    }
+}
@@ -0,0 +1,146 @@
+use crate::api::harvester::HarvesterAPI;
+use crate::api::responses::{HarvesterConfigResp, PlotDirectoriesResp, PlotsResp};
+use crate::protocols::harvester::{HarvesterConfig, HarvesterPlots};
+use crate::rpc::{get_client, get_url, post, post_empty};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::io::Error;
+
+pub struct HarvesterClient {
+    client: Client,
+    pub host: String,
+    pub port: u16,
+    pub ssl_path: Option<String>,
+    pub additional_headers: Option<HashMap<String, String>>,
+}
+
+impl HarvesterClient {
+    pub fn new(
+        host: &str,
+        port: u16,
+        ssl_path: Option<String>,
+        additional_headers: &Option<HashMap<String, String>>,
+    ) -> Self {
+        HarvesterClient {
+            client: get_client(ssl_path.clone()).unwrap_or_default(),
+            host: host.to_string(),
+            port,
+            ssl_path,
+            additional_headers: additional_headers.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl HarvesterAPI for HarvesterClient {
+    async fn get_plots(&self) -> Result<HarvesterPlots, Error> {
+        let resp = post::<PlotsResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_plots"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(HarvesterPlots {
+            plots: resp.plots,
+            failed_to_open_filenames: resp.failed_to_open_filenames,
+            not_found_filenames: resp.not_found_filenames,
+        })
+    }
+    async fn refresh_plots(&self) -> Result<(), Error> {
+        post_empty(
+            &self.client,
+            &self.host,
+            self.port,
+            "refresh_plots",
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await
+    }
+    async fn delete_plot(&self, filename: &str) -> Result<(), Error> {
+        let mut request_body = Map::new();
+        request_body.insert("filename".to_string(), json!(filename));
+        post_empty(
+            &self.client,
+            &self.host,
+            self.port,
+            "delete_plot",
+            &request_body,
+            &self.additional_headers,
+        )
+        .await
+    }
+    async fn add_plot_directory(&self, dirname: &str) -> Result<(), Error> {
+        let mut request_body = Map::new();
+        request_body.insert("dirname".to_string(), json!(dirname));
+        post_empty(
+            &self.client,
+            &self.host,
+            self.port,
+            "add_plot_directory",
+            &request_body,
+            &self.additional_headers,
+        )
+        .await
+    }
+    async fn get_plot_directories(&self) -> Result<Vec<String>, Error> {
+        Ok(post::<PlotDirectoriesResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_plot_directories"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?
+        .directories)
+    }
+    async fn remove_plot_directory(&self, dirname: &str) -> Result<(), Error> {
+        let mut request_body = Map::new();
+        request_body.insert("dirname".to_string(), json!(dirname));
+        post_empty(
+            &self.client,
+            &self.host,
+            self.port,
+            "remove_plot_directory",
+            &request_body,
+            &self.additional_headers,
+        )
+        .await
+    }
+    async fn get_harvester_config(&self) -> Result<HarvesterConfig, Error> {
+        let resp = post::<HarvesterConfigResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_harvester_config"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(HarvesterConfig {
+            use_gpu_harvesting: resp.use_gpu_harvesting,
+            gpu_index: resp.gpu_index,
+            enforce_gpu_index: resp.enforce_gpu_index,
+            disable_cpu_affinity: resp.disable_cpu_affinity,
+            parallel_decompressor_count: resp.parallel_decompressor_count,
+            decompressor_thread_count: resp.decompressor_thread_count,
+            recursive_plot_scan: resp.recursive_plot_scan,
+            refresh_parameter_interval_seconds: resp.refresh_parameter_interval_seconds,
+        })
+    }
+    async fn update_harvester_config(&self, config: &HarvesterConfig) -> Result<(), Error> {
+        let request_body = match json!(config) {
+            Value::Object(map) => map,
+            _ => Map::new(),
+        };
+        post_empty(
+            &self.client,
+            &self.host,
+            self.port,
+            "update_harvester_config",
+            &request_body,
+            &self.additional_headers,
+        )
+        .await
+    }
+}
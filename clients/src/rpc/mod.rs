@@ -1,6 +1,12 @@
+pub mod crawler;
+pub mod data_layer;
+pub mod farmer;
 pub mod full_node;
+pub mod harvester;
+pub mod timelord;
 pub mod wallet;
 
+use crate::api::responses::EmptyResp;
 use crate::protocols::shared::NoCertificateVerification;
 use crate::protocols::shared::{load_certs, load_private_key};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
@@ -112,3 +118,29 @@ where
         Err(err) => Err(Error::new(ErrorKind::InvalidData, format!("{:?}", err))),
     }
 }
+
+//For endpoints that only answer with success, a false success becomes an error
+pub(crate) async fn post_empty(
+    client: &Client,
+    host: &str,
+    port: u16,
+    endpoint: &str,
+    data: &Map<String, Value>,
+    additional_headers: &Option<HashMap<String, String>>,
+) -> Result<(), Error> {
+    let resp = post::<EmptyResp>(
+        client,
+        &get_url(host, port, endpoint),
+        data,
+        additional_headers,
+    )
+    .await?;
+    if resp.success {
+        Ok(())
+    } else {
+        Err(Error::new(
+            ErrorKind::Other,
+            format!("Failed to {endpoint}: {:?}", resp.error),
+        ))
+    }
+}
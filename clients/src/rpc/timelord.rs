@@ -0,0 +1,82 @@
+use crate::api::responses::{ConnectionsResp, EmptyResp, NetworkInfoResp, RoutesResp};
+use crate::api::timelord::TimelordAPI;
+use crate::protocols::shared::PeerConnection;
+use crate::rpc::{get_client, get_url, post};
+use async_trait::async_trait;
+use dg_xch_core::blockchain::network_info::NetworkInfo;
+use reqwest::Client;
+use serde_json::Map;
+use std::collections::HashMap;
+use std::io::Error;
+
+pub struct TimelordClient {
+    client: Client,
+    pub host: String,
+    pub port: u16,
+    pub ssl_path: Option<String>,
+    pub additional_headers: Option<HashMap<String, String>>,
+}
+
+impl TimelordClient {
+    pub fn new(
+        host: &str,
+        port: u16,
+        ssl_path: Option<String>,
+        additional_headers: &Option<HashMap<String, String>>,
+    ) -> Self {
+        TimelordClient {
+            client: get_client(ssl_path.clone()).unwrap_or_default(),
+            host: host.to_string(),
+            port,
+            ssl_path,
+            additional_headers: additional_headers.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl TimelordAPI for TimelordClient {
+    async fn healthz(&self) -> Result<bool, Error> {
+        Ok(post::<EmptyResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "healthz"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?
+        .success)
+    }
+    async fn get_network_info(&self) -> Result<NetworkInfo, Error> {
+        let resp = post::<NetworkInfoResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_network_info"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(NetworkInfo {
+            network_name: resp.network_name,
+            network_prefix: resp.network_prefix,
+        })
+    }
+    async fn get_connections(&self) -> Result<Vec<PeerConnection>, Error> {
+        Ok(post::<ConnectionsResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_connections"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?
+        .connections)
+    }
+    async fn get_routes(&self) -> Result<Vec<String>, Error> {
+        Ok(post::<RoutesResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_routes"),
+            &Map::new(),
+            &self.additional_headers,
+        )
+        .await?
+        .routes)
+    }
+}
@@ -1,22 +1,34 @@
 use crate::api::wallet::WalletAPI;
+use crate::protocols::wallet::{
+    AllOffers, CatAssetId, CatSpendRequest, CreateCatWalletRequest, CreateDidWalletRequest,
+    CreateOfferForIdsRequest, CreatedCatWallet, CreatedDidWallet, CreatedOffer, DidInfo,
+    DidTransferRequest, GetAllOffersRequest, MintedNft, NftInfo, NftMintRequest,
+    NftTransferRequest, SentTransaction, TradeRecord,
+};
 use async_trait::async_trait;
 use dg_xch_core::blockchain::announcement::Announcement;
 use dg_xch_core::blockchain::coin::Coin;
 use dg_xch_core::blockchain::pending_payment::PendingPayment;
+use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48};
+use dg_xch_core::blockchain::spend_bundle::SpendBundle;
 use dg_xch_core::blockchain::transaction_record::TransactionRecord;
 use dg_xch_core::blockchain::wallet_balance::WalletBalance;
 use dg_xch_core::blockchain::wallet_info::WalletInfo;
 use dg_xch_core::blockchain::wallet_sync::WalletSync;
 use reqwest::Client;
-use serde_json::{json, Map};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
 use std::collections::HashMap;
-use std::io::Error;
+use std::io::{Error, ErrorKind};
 
 use crate::api::responses::{
-    LoginResp, SignedTransactionRecordResp, TransactionRecordResp, WalletBalanceResp,
+    AllOffersResp, CatAssetIdResp, CreateCatWalletResp, CreateDidWalletResp, CreateOfferResp,
+    DidGetDidResp, DidMetadataResp, DidPubkeyResp, DidWalletNameResp, LoginResp, NftListResp,
+    NftMintResp, SignedTransactionRecordResp, SpendBundleResp, TradeRecordResp,
+    TransactionRecordResp, TransactionRecordsResp, TransactionResultResp, WalletBalanceResp,
     WalletInfoResp, WalletSyncResp,
 };
-use crate::rpc::{get_client, get_url, post};
+use crate::rpc::{get_client, get_url, post, post_empty};
 
 pub struct WalletClient {
     client: Client,
@@ -39,6 +51,18 @@ impl WalletClient {
         }
     }
 }
+
+fn to_request_body<T: Serialize>(request: &T) -> Result<Map<String, Value>, Error> {
+    match serde_json::to_value(request)
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("{:?}", e)))?
+    {
+        Value::Object(map) => Ok(map),
+        _ => Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Request did not serialize to an object",
+        )),
+    }
+}
 #[async_trait]
 impl WalletAPI for WalletClient {
     async fn log_in(&self, wallet_fingerprint: u32) -> Result<u32, Error> {
@@ -75,7 +99,7 @@ impl WalletAPI for WalletClient {
         .await?
         .wallets)
     }
-    async fn get_wallet_balance(&self, wallet_id: u32) -> Result<Vec<WalletBalance>, Error> {
+    async fn get_wallet_balance(&self, wallet_id: u32) -> Result<WalletBalance, Error> {
         let mut request_body = Map::new();
         request_body.insert("wallet_id".to_string(), json!(wallet_id));
         Ok(post::<WalletBalanceResp>(
@@ -85,7 +109,7 @@ impl WalletAPI for WalletClient {
             &self.additional_headers,
         )
         .await?
-        .wallets)
+        .wallet_balance)
     }
     async fn get_sync_status(&self) -> Result<WalletSync, Error> {
         let resp = post::<WalletSyncResp>(
@@ -115,7 +139,7 @@ impl WalletAPI for WalletClient {
         request_body.insert("fee".to_string(), json!(fee));
         Ok(post::<TransactionRecordResp>(
             &self.client,
-            &get_url(self.host.as_str(), self.port, "SendTransaction"),
+            &get_url(self.host.as_str(), self.port, "send_transaction"),
             &request_body,
             &self.additional_headers,
         )
@@ -158,6 +182,29 @@ impl WalletAPI for WalletClient {
         .await?
         .transaction)
     }
+    async fn get_transactions(
+        &self,
+        wallet_id: u32,
+        start: Option<u32>,
+        end: Option<u32>,
+    ) -> Result<Vec<TransactionRecord>, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("wallet_id".to_string(), json!(wallet_id));
+        if let Some(start) = start {
+            request_body.insert("start".to_string(), json!(start));
+        }
+        if let Some(end) = end {
+            request_body.insert("end".to_string(), json!(end));
+        }
+        Ok(post::<TransactionRecordsResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_transactions"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .transactions)
+    }
     async fn create_signed_transaction(
         &self,
         wallet_id: u32,
@@ -186,4 +233,271 @@ impl WalletAPI for WalletClient {
         .await?
         .signed_tx)
     }
+    async fn create_cat_wallet(
+        &self,
+        request: &CreateCatWalletRequest,
+    ) -> Result<CreatedCatWallet, Error> {
+        let resp = post::<CreateCatWalletResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "create_new_wallet"),
+            &to_request_body(request)?,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(CreatedCatWallet {
+            wallet_id: resp.wallet_id,
+            wallet_type: resp.wallet_type,
+            asset_id: resp.asset_id,
+        })
+    }
+    async fn cat_spend(&self, request: &CatSpendRequest) -> Result<SentTransaction, Error> {
+        let resp = post::<TransactionResultResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "cat_spend"),
+            &to_request_body(request)?,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(SentTransaction {
+            transaction: resp.transaction,
+            transaction_id: resp.transaction_id,
+        })
+    }
+    async fn cat_get_asset_id(&self, wallet_id: u32) -> Result<CatAssetId, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("wallet_id".to_string(), json!(wallet_id));
+        let resp = post::<CatAssetIdResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "cat_get_asset_id"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(CatAssetId {
+            wallet_id: resp.wallet_id,
+            asset_id: resp.asset_id,
+        })
+    }
+    async fn create_offer_for_ids(
+        &self,
+        request: &CreateOfferForIdsRequest,
+    ) -> Result<CreatedOffer, Error> {
+        let resp = post::<CreateOfferResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "create_offer_for_ids"),
+            &to_request_body(request)?,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(CreatedOffer {
+            offer: resp.offer,
+            trade_record: resp.trade_record,
+        })
+    }
+    async fn take_offer(&self, offer: &str, fee: u64) -> Result<TradeRecord, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("offer".to_string(), json!(offer));
+        request_body.insert("fee".to_string(), json!(fee));
+        Ok(post::<TradeRecordResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "take_offer"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .trade_record)
+    }
+    async fn get_all_offers(&self, request: &GetAllOffersRequest) -> Result<AllOffers, Error> {
+        let resp = post::<AllOffersResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "get_all_offers"),
+            &to_request_body(request)?,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(AllOffers {
+            trade_records: resp.trade_records,
+            offers: resp.offers,
+        })
+    }
+    async fn cancel_offer(&self, trade_id: &Bytes32, secure: bool, fee: u64) -> Result<(), Error> {
+        let mut request_body = Map::new();
+        request_body.insert("trade_id".to_string(), json!(trade_id));
+        request_body.insert("secure".to_string(), json!(secure));
+        request_body.insert("fee".to_string(), json!(fee));
+        post_empty(
+            &self.client,
+            &self.host,
+            self.port,
+            "cancel_offer",
+            &request_body,
+            &self.additional_headers,
+        )
+        .await
+    }
+    async fn nft_get_nfts(
+        &self,
+        wallet_id: u32,
+        start_index: Option<u32>,
+        num: Option<u32>,
+    ) -> Result<Vec<NftInfo>, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("wallet_id".to_string(), json!(wallet_id));
+        if let Some(start_index) = start_index {
+            request_body.insert("start_index".to_string(), json!(start_index));
+        }
+        if let Some(num) = num {
+            request_body.insert("num".to_string(), json!(num));
+        }
+        Ok(post::<NftListResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "nft_get_nfts"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .nft_list)
+    }
+    async fn nft_mint_nft(&self, request: &NftMintRequest) -> Result<MintedNft, Error> {
+        let resp = post::<NftMintResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "nft_mint_nft"),
+            &to_request_body(request)?,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(MintedNft {
+            wallet_id: resp.wallet_id,
+            nft_id: resp.nft_id,
+            spend_bundle: resp.spend_bundle,
+        })
+    }
+    async fn nft_transfer_nft(&self, request: &NftTransferRequest) -> Result<SpendBundle, Error> {
+        Ok(post::<SpendBundleResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "nft_transfer_nft"),
+            &to_request_body(request)?,
+            &self.additional_headers,
+        )
+        .await?
+        .spend_bundle)
+    }
+    async fn create_did_wallet(
+        &self,
+        request: &CreateDidWalletRequest,
+    ) -> Result<CreatedDidWallet, Error> {
+        let resp = post::<CreateDidWalletResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "create_new_wallet"),
+            &to_request_body(request)?,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(CreatedDidWallet {
+            wallet_id: resp.wallet_id,
+            wallet_type: resp.wallet_type,
+            my_did: resp.my_did,
+        })
+    }
+    async fn did_get_did(&self, wallet_id: u32) -> Result<DidInfo, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("wallet_id".to_string(), json!(wallet_id));
+        let resp = post::<DidGetDidResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "did_get_did"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(DidInfo {
+            wallet_id: resp.wallet_id,
+            my_did: resp.my_did,
+            coin_id: resp.coin_id,
+        })
+    }
+    async fn did_get_pubkey(&self, wallet_id: u32) -> Result<Bytes48, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("wallet_id".to_string(), json!(wallet_id));
+        Ok(post::<DidPubkeyResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "did_get_pubkey"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .pubkey)
+    }
+    async fn did_get_wallet_name(&self, wallet_id: u32) -> Result<String, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("wallet_id".to_string(), json!(wallet_id));
+        Ok(post::<DidWalletNameResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "did_get_wallet_name"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .name)
+    }
+    async fn did_set_wallet_name(&self, wallet_id: u32, name: &str) -> Result<(), Error> {
+        let mut request_body = Map::new();
+        request_body.insert("wallet_id".to_string(), json!(wallet_id));
+        request_body.insert("name".to_string(), json!(name));
+        post_empty(
+            &self.client,
+            &self.host,
+            self.port,
+            "did_set_wallet_name",
+            &request_body,
+            &self.additional_headers,
+        )
+        .await
+    }
+    async fn did_get_metadata(&self, wallet_id: u32) -> Result<HashMap<String, String>, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("wallet_id".to_string(), json!(wallet_id));
+        Ok(post::<DidMetadataResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "did_get_metadata"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .metadata)
+    }
+    async fn did_update_metadata(
+        &self,
+        wallet_id: u32,
+        metadata: &HashMap<String, String>,
+        fee: u64,
+    ) -> Result<SpendBundle, Error> {
+        let mut request_body = Map::new();
+        request_body.insert("wallet_id".to_string(), json!(wallet_id));
+        request_body.insert("metadata".to_string(), json!(metadata));
+        request_body.insert("fee".to_string(), json!(fee));
+        Ok(post::<SpendBundleResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "did_update_metadata"),
+            &request_body,
+            &self.additional_headers,
+        )
+        .await?
+        .spend_bundle)
+    }
+    async fn did_transfer_did(
+        &self,
+        request: &DidTransferRequest,
+    ) -> Result<SentTransaction, Error> {
+        let resp = post::<TransactionResultResp>(
+            &self.client,
+            &get_url(self.host.as_str(), self.port, "did_transfer_did"),
+            &to_request_body(request)?,
+            &self.additional_headers,
+        )
+        .await?;
+        Ok(SentTransaction {
+            transaction: resp.transaction,
+            transaction_id: resp.transaction_id,
+        })
+    }
 }
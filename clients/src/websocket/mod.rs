@@ -1,7 +1,10 @@
 pub mod farmer;
 pub mod full_node;
 pub mod harvester;
+pub mod server;
+pub mod supervised;
 pub mod wallet;
+pub mod wallet_sync;
 
 use crate::protocols::shared::{
     load_certs, load_certs_from_bytes, load_private_key, load_private_key_from_bytes, Handshake,
@@ -374,7 +377,7 @@ async fn perform_handshake(
     )
     .await
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ChiaMessageFilter {
     pub msg_type: Option<ProtocolMessageTypes>,
     pub id: Option<u16>,
@@ -393,6 +396,7 @@ impl ChiaMessageFilter {
     }
 }
 
+#[derive(Clone)]
 pub struct ChiaMessageHandler {
     filter: ChiaMessageFilter,
     handle: Arc<dyn MessageHandler + Send + Sync>,
@@ -0,0 +1,463 @@
+use crate::protocols::shared::{
+    load_certs, load_certs_from_bytes, load_private_key, load_private_key_from_bytes, Handshake,
+    CAPABILITIES, PROTOCOL_VERSION, SOFTWARE_VERSION,
+};
+use crate::protocols::ProtocolMessageTypes;
+use crate::websocket::{
+    ChiaMessage, ChiaMessageFilter, ChiaMessageHandler, MessageHandler, NodeType, ServerConnection,
+    Websocket,
+};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+use dg_xch_core::ssl::{generate_ca_signed_cert_data, CHIA_CA_CRT, CHIA_CA_KEY};
+use dg_xch_serialize::{hash_256, ChiaSerialize};
+use hyper::header::{CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, UPGRADE};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response, StatusCode};
+use log::{debug, error, info, warn};
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{Certificate, PrivateKey, RootCertStore, ServerConfig};
+use std::convert::Infallible;
+use std::io::{Cursor, Error, ErrorKind};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::select;
+use tokio::sync::Mutex;
+use tokio_rustls::TlsAcceptor;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct ServerSSLConfig {
+    pub ssl_crt_path: String,
+    pub ssl_key_path: String,
+    pub ssl_ca_crt_path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebsocketServerConfig {
+    pub host: String,
+    pub port: u16,
+    pub network_id: String,
+    pub node_type: NodeType,
+    pub capabilities: Vec<(u16, String)>,
+    //When None a certificate signed by the Chia CA is generated and peers are verified against the Chia CA
+    pub ssl_info: Option<ServerSSLConfig>,
+}
+impl WebsocketServerConfig {
+    pub fn new(host: &str, port: u16, network_id: &str, node_type: NodeType) -> Self {
+        WebsocketServerConfig {
+            host: host.to_string(),
+            port,
+            network_id: network_id.to_string(),
+            node_type,
+            capabilities: CAPABILITIES
+                .iter()
+                .map(|e| (e.0, e.1.to_string()))
+                .collect(),
+            ssl_info: None,
+        }
+    }
+}
+
+pub struct Peer {
+    pub node_id: Bytes32,
+    pub address: SocketAddr,
+    pub connection: Arc<Mutex<ServerConnection>>,
+    handshake: Mutex<Option<Handshake>>,
+}
+impl Peer {
+    pub async fn handshake(&self) -> Option<Handshake> {
+        self.handshake.lock().await.clone()
+    }
+    pub async fn node_type(&self) -> Option<NodeType> {
+        self.handshake
+            .lock()
+            .await
+            .as_ref()
+            .map(|h| NodeType::from(h.node_type))
+    }
+    pub async fn send(&self, msg: ChiaMessage) -> Result<(), Error> {
+        self.connection.lock().await.send(msg.into()).await
+    }
+}
+
+pub type PeerMap = Arc<DashMap<Bytes32, Arc<Peer>>>;
+
+#[async_trait]
+pub trait PeerMessageHandler {
+    async fn handle(&self, msg: Arc<ChiaMessage>, peer: Arc<Peer>) -> Result<(), Error>;
+}
+
+struct IgnorePeerHandler {
+    handle: Arc<dyn MessageHandler + Send + Sync>,
+}
+#[async_trait]
+impl PeerMessageHandler for IgnorePeerHandler {
+    async fn handle(&self, msg: Arc<ChiaMessage>, _peer: Arc<Peer>) -> Result<(), Error> {
+        self.handle.handle(msg).await
+    }
+}
+
+type HandlerMap = Arc<DashMap<ProtocolMessageTypes, Arc<dyn PeerMessageHandler + Send + Sync>>>;
+
+struct ServerContext {
+    network_id: String,
+    server_port: u16,
+    node_type: NodeType,
+    capabilities: Vec<(u16, String)>,
+    handlers: HandlerMap,
+    peers: PeerMap,
+}
+
+pub struct WebsocketServer {
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    context: Arc<ServerContext>,
+}
+impl WebsocketServer {
+    pub async fn bind(config: WebsocketServerConfig) -> Result<Self, Error> {
+        let acceptor = TlsAcceptor::from(Arc::new(load_server_config(&config)?));
+        let listener = TcpListener::bind(format!("{}:{}", config.host, config.port)).await?;
+        let server_port = listener.local_addr()?.port();
+        info!("Websocket server listening on {}", listener.local_addr()?);
+        Ok(WebsocketServer {
+            listener,
+            acceptor,
+            context: Arc::new(ServerContext {
+                network_id: config.network_id,
+                server_port,
+                node_type: config.node_type,
+                capabilities: config.capabilities,
+                handlers: Default::default(),
+                peers: Default::default(),
+            }),
+        })
+    }
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        self.listener.local_addr()
+    }
+    pub fn peers(&self) -> PeerMap {
+        self.context.peers.clone()
+    }
+    pub fn add_handler(
+        &self,
+        msg_type: ProtocolMessageTypes,
+        handle: Arc<dyn MessageHandler + Send + Sync>,
+    ) {
+        self.add_peer_handler(msg_type, Arc::new(IgnorePeerHandler { handle }));
+    }
+    pub fn add_peer_handler(
+        &self,
+        msg_type: ProtocolMessageTypes,
+        handle: Arc<dyn PeerMessageHandler + Send + Sync>,
+    ) {
+        self.context.handlers.insert(msg_type, handle);
+    }
+    pub fn remove_handler(&self, msg_type: ProtocolMessageTypes) {
+        self.context.handlers.remove(&msg_type);
+    }
+    pub async fn broadcast(&self, msg: ChiaMessage, node_type: Option<NodeType>) {
+        let peers: Vec<Arc<Peer>> = self
+            .context
+            .peers
+            .iter()
+            .map(|p| p.value().clone())
+            .collect();
+        for peer in peers {
+            if node_type.is_some() && peer.node_type().await != node_type {
+                continue;
+            }
+            if let Err(e) = peer.send(msg.clone()).await {
+                warn!(
+                    "Failed to send {:?} to {}: {:?}",
+                    msg.msg_type, peer.node_id, e
+                );
+            }
+        }
+    }
+    pub async fn run(&self, run: Arc<AtomicBool>) -> Result<(), Error> {
+        loop {
+            select! {
+                accepted = self.listener.accept() => {
+                    match accepted {
+                        Ok((stream, address)) => {
+                            let acceptor = self.acceptor.clone();
+                            let context = self.context.clone();
+                            let run = run.clone();
+                            tokio::spawn(async move {
+                                if let Err(e) = handle_connection(stream, address, acceptor, context, run).await {
+                                    debug!("Connection from {} closed: {:?}", address, e);
+                                }
+                            });
+                        }
+                        Err(e) => {
+                            error!("Failed to accept connection: {:?}", e);
+                        }
+                    }
+                }
+                _ = async {
+                    loop {
+                        if !run.load(Ordering::Relaxed) {
+                            debug!("Websocket server is exiting");
+                            return;
+                        } else {
+                            tokio::time::sleep(Duration::from_secs(1)).await
+                        }
+                    }
+                } => {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+fn load_server_config(config: &WebsocketServerConfig) -> Result<ServerConfig, Error> {
+    let (certs, key, ca_certs) = match &config.ssl_info {
+        Some(ssl_info) => (
+            load_certs(&ssl_info.ssl_crt_path)?,
+            load_private_key(&ssl_info.ssl_key_path)?,
+            load_certs(&ssl_info.ssl_ca_crt_path)?,
+        ),
+        None => {
+            let (cert_bytes, key_bytes) = generate_ca_signed_cert_data(CHIA_CA_CRT, CHIA_CA_KEY)
+                .map_err(|e| Error::new(ErrorKind::Other, format!("OpenSSL Errors: {:?}", e)))?;
+            (
+                load_certs_from_bytes(cert_bytes.as_bytes())?,
+                load_private_key_from_bytes(key_bytes.as_bytes())?,
+                load_certs_from_bytes(CHIA_CA_CRT.as_bytes())?,
+            )
+        }
+    };
+    build_server_config(certs, key, ca_certs)
+}
+
+fn build_server_config(
+    certs: Vec<Certificate>,
+    key: PrivateKey,
+    ca_certs: Vec<Certificate>,
+) -> Result<ServerConfig, Error> {
+    let mut roots = RootCertStore::empty();
+    for cert in ca_certs {
+        roots.add(&cert).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid CA Certificate: {:?}", e),
+            )
+        })?;
+    }
+    ServerConfig::builder()
+        .with_safe_defaults()
+        .with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+        .with_single_cert(certs, key)
+        .map_err(|e| Error::new(ErrorKind::Other, format!("Error Building Server: {:?}", e)))
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    address: SocketAddr,
+    acceptor: TlsAcceptor,
+    context: Arc<ServerContext>,
+    run: Arc<AtomicBool>,
+) -> Result<(), Error> {
+    let stream = acceptor.accept(stream).await?;
+    //Chia identifies peers by the hash of their certificate
+    let node_id = stream
+        .get_ref()
+        .1
+        .peer_certificates()
+        .and_then(|certs| certs.first())
+        .map(|cert| Bytes32::new(&hash_256(&cert.0)))
+        .ok_or_else(|| Error::new(ErrorKind::PermissionDenied, "No Peer Certificate"))?;
+    let service =
+        service_fn(move |req| upgrade_request(req, node_id, address, context.clone(), run.clone()));
+    Http::new()
+        .serve_connection(stream, service)
+        .with_upgrades()
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, e))
+}
+
+async fn upgrade_request(
+    mut req: Request<Body>,
+    node_id: Bytes32,
+    address: SocketAddr,
+    context: Arc<ServerContext>,
+    run: Arc<AtomicBool>,
+) -> Result<Response<Body>, Infallible> {
+    let accept_key = match req.headers().get(SEC_WEBSOCKET_KEY) {
+        Some(key) => derive_accept_key(key.as_bytes()),
+        None => {
+            let mut resp = Response::new(Body::from("Expected Websocket Upgrade"));
+            *resp.status_mut() = StatusCode::BAD_REQUEST;
+            return Ok(resp);
+        }
+    };
+    let on_upgrade = hyper::upgrade::on(&mut req);
+    tokio::spawn(async move {
+        match on_upgrade.await {
+            Ok(upgraded) => {
+                let stream = WebSocketStream::from_raw_socket(upgraded, Role::Server, None).await;
+                run_peer(stream, node_id, address, context, run).await;
+            }
+            Err(e) => {
+                error!("Failed to upgrade connection from {}: {:?}", address, e);
+            }
+        }
+    });
+    Ok(Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header(CONNECTION, "Upgrade")
+        .header(UPGRADE, "websocket")
+        .header(SEC_WEBSOCKET_ACCEPT, accept_key)
+        .body(Body::empty())
+        .unwrap_or_default())
+}
+
+async fn run_peer(
+    stream: WebSocketStream<hyper::upgrade::Upgraded>,
+    node_id: Bytes32,
+    address: SocketAddr,
+    context: Arc<ServerContext>,
+    run: Arc<AtomicBool>,
+) {
+    let (connection, mut stream) = ServerConnection::new(stream);
+    let peer = Arc::new(Peer {
+        node_id,
+        address,
+        connection: Arc::new(Mutex::new(connection)),
+        handshake: Mutex::new(None),
+    });
+    let router = Arc::new(PeerRouter {
+        peer: peer.clone(),
+        context: context.clone(),
+    });
+    peer.connection
+        .lock()
+        .await
+        .subscribe(
+            Uuid::new_v4(),
+            ChiaMessageHandler::new(
+                ChiaMessageFilter {
+                    msg_type: None,
+                    id: None,
+                },
+                router,
+            ),
+        )
+        .await;
+    debug!("Peer {} connected from {}", peer.node_id, address);
+    stream.run(run).await;
+    context
+        .peers
+        .remove_if(&peer.node_id, |_, p| Arc::ptr_eq(p, &peer));
+    //Drops the router which holds a reference back to the peer
+    peer.connection.lock().await.clear().await;
+    info!("Peer {} disconnected", peer.node_id);
+}
+
+struct PeerRouter {
+    peer: Arc<Peer>,
+    context: Arc<ServerContext>,
+}
+impl PeerRouter {
+    async fn handle_handshake(&self, msg: Arc<ChiaMessage>) -> Result<(), Error> {
+        let mut cursor = Cursor::new(&msg.data);
+        let handshake = Handshake::from_bytes(&mut cursor).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Failed to parse Handshake: {:?}", e),
+            )
+        })?;
+        if handshake.network_id != self.context.network_id {
+            let _ = self.peer.connection.lock().await.close(None).await;
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Peer {} is on network {}, expected {}",
+                    self.peer.node_id, handshake.network_id, self.context.network_id
+                ),
+            ));
+        }
+        self.peer
+            .send(ChiaMessage::new(
+                ProtocolMessageTypes::Handshake,
+                &Handshake {
+                    network_id: self.context.network_id.clone(),
+                    protocol_version: PROTOCOL_VERSION.to_string(),
+                    software_version: SOFTWARE_VERSION.to_string(),
+                    server_port: self.context.server_port,
+                    node_type: self.context.node_type.clone() as u8,
+                    capabilities: self.context.capabilities.clone(),
+                },
+                msg.id,
+            ))
+            .await?;
+        info!(
+            "Peer {} completed handshake as {:?}",
+            self.peer.node_id,
+            NodeType::from(handshake.node_type)
+        );
+        *self.peer.handshake.lock().await = Some(handshake);
+        //Like Chia, a reconnecting node replaces its old connection, which is closed
+        if let Some(old) = self
+            .context
+            .peers
+            .insert(self.peer.node_id, self.peer.clone())
+        {
+            if !Arc::ptr_eq(&old, &self.peer) {
+                info!(
+                    "Peer {} reconnected from {}, closing connection from {}",
+                    self.peer.node_id, self.peer.address, old.address
+                );
+                let _ = old.connection.lock().await.close(None).await;
+            }
+        }
+        Ok(())
+    }
+}
+#[async_trait]
+impl MessageHandler for PeerRouter {
+    async fn handle(&self, msg: Arc<ChiaMessage>) -> Result<(), Error> {
+        if msg.msg_type == ProtocolMessageTypes::Handshake {
+            return self.handle_handshake(msg).await;
+        }
+        if self.peer.handshake.lock().await.is_none() {
+            let _ = self
+                .peer
+                .connection
+                .lock()
+                .await
+                .close(Some(Message::Close(None)))
+                .await;
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                format!(
+                    "Peer {} sent {:?} before Handshake",
+                    self.peer.node_id, msg.msg_type
+                ),
+            ));
+        }
+        let handler = self
+            .context
+            .handlers
+            .get(&msg.msg_type)
+            .map(|h| h.value().clone());
+        match handler {
+            Some(handler) => handler.handle(msg, self.peer.clone()).await,
+            None => {
+                debug!("No Handler for Message: {:?}", msg.msg_type);
+                Ok(())
+            }
+        }
+    }
+}
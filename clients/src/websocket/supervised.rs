@@ -0,0 +1,368 @@
+use crate::websocket::{
+    get_client, get_client_generated_tls, get_client_tls, perform_handshake, ChiaMessageHandler,
+    Client, ClientSSLConfig, NodeType, Websocket,
+};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use log::{error, info, warn};
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::select;
+use tokio::sync::broadcast::{channel, Receiver, Sender};
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub enum ClientConnectionMode {
+    Default,
+    GeneratedTls,
+    Tls {
+        ssl_crt_path: String,
+        ssl_key_path: String,
+        ssl_ca_crt_path: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ReconnectBackoff {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: u32,
+    pub max_attempts: Option<u32>,
+}
+impl Default for ReconnectBackoff {
+    fn default() -> Self {
+        ReconnectBackoff {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            multiplier: 2,
+            max_attempts: None,
+        }
+    }
+}
+impl ReconnectBackoff {
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1)
+            .saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SupervisedClientConfig {
+    pub host: String,
+    pub port: u16,
+    pub network_id: String,
+    pub node_type: NodeType,
+    pub mode: ClientConnectionMode,
+    pub additional_headers: Option<HashMap<String, String>>,
+    pub backoff: ReconnectBackoff,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connecting { attempt: u32 },
+    Connected,
+    Disconnected,
+    Reconnecting { attempt: u32, delay: Duration },
+    Stopped,
+}
+
+//Keeps a websocket connection alive, reconnecting and re-handshaking whenever the read stream ends.
+//Subscriptions made through this client are restored on every new connection.
+pub struct SupervisedClient {
+    client: Arc<Mutex<Option<Arc<Mutex<Client>>>>>,
+    subscribers: Arc<DashMap<Uuid, ChiaMessageHandler>>,
+    state: Arc<Mutex<ConnectionState>>,
+    events: Sender<ConnectionState>,
+    shutdown: Arc<AtomicBool>,
+    stop: Arc<Notify>,
+    handle: JoinHandle<()>,
+}
+impl SupervisedClient {
+    pub fn start(config: SupervisedClientConfig, run: Arc<AtomicBool>) -> Self {
+        let client = Arc::new(Mutex::new(None));
+        let subscribers = Arc::new(DashMap::new());
+        let state = Arc::new(Mutex::new(ConnectionState::Connecting { attempt: 0 }));
+        let (events, _) = channel(64);
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = Arc::new(Notify::new());
+        let supervisor = Supervisor {
+            config,
+            client: client.clone(),
+            subscribers: subscribers.clone(),
+            state: state.clone(),
+            events: events.clone(),
+            run,
+            shutdown: shutdown.clone(),
+            stop: stop.clone(),
+        };
+        let handle = tokio::spawn(async move { supervisor.run().await });
+        SupervisedClient {
+            client,
+            subscribers,
+            state,
+            events,
+            shutdown,
+            stop,
+            handle,
+        }
+    }
+
+    pub async fn client(&self) -> Option<Arc<Mutex<Client>>> {
+        self.client.lock().await.clone()
+    }
+
+    pub async fn state(&self) -> ConnectionState {
+        self.state.lock().await.clone()
+    }
+
+    pub fn subscribe_state(&self) -> Receiver<ConnectionState> {
+        self.events.subscribe()
+    }
+
+    pub async fn wait_for_connection(
+        &self,
+        timeout: Duration,
+    ) -> Result<Arc<Mutex<Client>>, Error> {
+        let mut events = self.subscribe_state();
+        let wait = async {
+            loop {
+                if let Some(client) = self.client().await {
+                    return Ok(client);
+                }
+                match events.recv().await {
+                    Ok(ConnectionState::Stopped) => {
+                        return Err(Error::new(
+                            ErrorKind::NotConnected,
+                            "Supervised client stopped",
+                        ));
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        if self.handle.is_finished() {
+                            return Err(Error::new(
+                                ErrorKind::NotConnected,
+                                format!("Supervised client stopped: {:?}", e),
+                            ));
+                        }
+                    }
+                }
+            }
+        };
+        select!(
+            res = wait => res,
+            _ = tokio::time::sleep(timeout) => {
+                Err(Error::new(
+                    ErrorKind::TimedOut,
+                    "Timeout before client connected",
+                ))
+            }
+        )
+    }
+
+    pub async fn join(self) -> Result<(), Error> {
+        self.handle.await.map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("Failed to join supervised client: {:?}", e),
+            )
+        })
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.handle.is_finished()
+    }
+}
+#[async_trait]
+impl Websocket for SupervisedClient {
+    async fn send(&self, msg: Message) -> Result<(), Error> {
+        match self.client().await {
+            Some(client) => client.lock().await.send(msg).await,
+            None => Err(Error::new(
+                ErrorKind::NotConnected,
+                "Supervised client is not connected",
+            )),
+        }
+    }
+
+    async fn subscribe(&self, uuid: Uuid, handle: ChiaMessageHandler) {
+        //Holding the client slot keeps this ordered with the supervisor publishing a new client
+        self.subscribers.insert(uuid, handle.clone());
+        if let Some(client) = self.client.lock().await.as_ref() {
+            client.lock().await.subscribe(uuid, handle).await;
+        }
+    }
+
+    async fn unsubscribe(&self, uuid: Uuid) {
+        self.subscribers.remove(&uuid);
+        if let Some(client) = self.client.lock().await.as_ref() {
+            client.lock().await.unsubscribe(uuid).await;
+        }
+    }
+
+    async fn close(&self, msg: Option<Message>) -> Result<(), Error> {
+        self.shutdown.store(true, Ordering::Relaxed);
+        self.stop.notify_waiters();
+        match self.client().await {
+            Some(client) => client.lock().await.close(msg).await,
+            None => Ok(()),
+        }
+    }
+}
+
+struct Supervisor {
+    config: SupervisedClientConfig,
+    client: Arc<Mutex<Option<Arc<Mutex<Client>>>>>,
+    subscribers: Arc<DashMap<Uuid, ChiaMessageHandler>>,
+    state: Arc<Mutex<ConnectionState>>,
+    events: Sender<ConnectionState>,
+    run: Arc<AtomicBool>,
+    shutdown: Arc<AtomicBool>,
+    stop: Arc<Notify>,
+}
+impl Supervisor {
+    fn running(&self) -> bool {
+        self.run.load(Ordering::Relaxed) && !self.shutdown.load(Ordering::Relaxed)
+    }
+
+    //Resolves once close() is called, the shared run flag is picked up by the stream itself
+    async fn stopped(&self) {
+        let notified = self.stop.notified();
+        if self.shutdown.load(Ordering::Relaxed) {
+            return;
+        }
+        notified.await
+    }
+
+    async fn run(self) {
+        let mut attempt = 0;
+        while self.running() {
+            self.set_state(ConnectionState::Connecting { attempt })
+                .await;
+            match self.connect().await {
+                Ok((client, stream_handle)) => {
+                    info!("Connected to {}:{}", self.config.host, self.config.port);
+                    attempt = 0;
+                    self.set_state(ConnectionState::Connected).await;
+                    let mut stream_handle = stream_handle;
+                    select!(
+                        _ = &mut stream_handle => {}
+                        _ = self.stopped() => stream_handle.abort()
+                    );
+                    self.client.lock().await.take();
+                    let _ = client.lock().await.shutdown().await;
+                    self.set_state(ConnectionState::Disconnected).await;
+                }
+                Err(e) => {
+                    error!(
+                        "Failed to connect to {}:{}: {:?}",
+                        self.config.host, self.config.port, e
+                    );
+                }
+            }
+            if !self.running() {
+                break;
+            }
+            attempt += 1;
+            if let Some(max_attempts) = self.config.backoff.max_attempts {
+                if attempt > max_attempts {
+                    warn!(
+                        "Giving up on {}:{} after {max_attempts} attempts",
+                        self.config.host, self.config.port
+                    );
+                    break;
+                }
+            }
+            let delay = self.config.backoff.delay_for_attempt(attempt);
+            self.set_state(ConnectionState::Reconnecting { attempt, delay })
+                .await;
+            select!(
+                _ = tokio::time::sleep(delay) => {}
+                _ = self.stopped() => {}
+            )
+        }
+        self.set_state(ConnectionState::Stopped).await;
+    }
+
+    async fn connect(&self) -> Result<(Arc<Mutex<Client>>, JoinHandle<()>), Error> {
+        let (client, mut stream) = match &self.config.mode {
+            ClientConnectionMode::Default => {
+                get_client(
+                    &self.config.host,
+                    self.config.port,
+                    &self.config.additional_headers,
+                )
+                .await?
+            }
+            ClientConnectionMode::GeneratedTls => {
+                get_client_generated_tls(
+                    &self.config.host,
+                    self.config.port,
+                    &self.config.additional_headers,
+                )
+                .await?
+            }
+            ClientConnectionMode::Tls {
+                ssl_crt_path,
+                ssl_key_path,
+                ssl_ca_crt_path,
+            } => {
+                get_client_tls(
+                    &self.config.host,
+                    self.config.port,
+                    ClientSSLConfig {
+                        ssl_crt_path,
+                        ssl_key_path,
+                        ssl_ca_crt_path,
+                    },
+                    &self.config.additional_headers,
+                )
+                .await?
+            }
+        };
+        //The slot stays locked from replaying subscriptions until the client is published,
+        //so a subscribe() in between is not missed and no message after the handshake is dropped
+        let mut slot = self.client.lock().await;
+        let subscribers: Vec<(Uuid, ChiaMessageHandler)> = self
+            .subscribers
+            .iter()
+            .map(|s| (*s.key(), s.value().clone()))
+            .collect();
+        for (uuid, handler) in subscribers {
+            client.subscribe(uuid, handler).await;
+        }
+        let run = self.run.clone();
+        let stream_handle = tokio::spawn(async move { stream.run(run).await });
+        let client = Arc::new(Mutex::new(client));
+        if let Err(e) = perform_handshake(
+            client.clone(),
+            &self.config.network_id,
+            self.config.port,
+            self.config.node_type.clone(),
+        )
+        .await
+        {
+            stream_handle.abort();
+            let _ = client.lock().await.shutdown().await;
+            return Err(e);
+        }
+        *slot = Some(client.clone());
+        Ok((client, stream_handle))
+    }
+
+    async fn set_state(&self, state: ConnectionState) {
+        *self.state.lock().await = state.clone();
+        let _ = self.events.send(state);
+    }
+}
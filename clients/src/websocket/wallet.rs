@@ -1,3 +1,4 @@
+use crate::websocket::wallet_sync::WalletSync;
 use crate::websocket::{
     get_client, get_client_tls, perform_handshake, Client, ClientSSLConfig, NodeType,
 };
@@ -38,4 +39,7 @@ impl WalletClient {
         let _ = perform_handshake(client.clone(), network_id, port, NodeType::Wallet).await;
         Ok(WalletClient { client })
     }
+    pub async fn sync_engine(&self) -> WalletSync {
+        WalletSync::new(self.client.clone()).await
+    }
 }
@@ -0,0 +1,444 @@
+use crate::protocols::wallet::{
+    CoinState, CoinStateUpdate, NewPeakWallet, PuzzleSolutionResponse, RegisterForCoinUpdates,
+    RegisterForPhUpdates, RejectBlockHeaders, RejectPuzzleSolution, RequestBlockHeaders,
+    RequestChildren, RequestPuzzleSolution, RespondBlockHeaders, RespondChildren,
+    RespondPuzzleSolution, RespondToCoinUpdates, RespondToPhUpdates, SendTransaction,
+    TransactionAck,
+};
+use crate::protocols::ProtocolMessageTypes;
+use crate::websocket::{
+    ChiaMessage, ChiaMessageFilter, ChiaMessageHandler, Client, MessageHandler, Websocket,
+};
+use async_trait::async_trait;
+use dg_xch_core::blockchain::header_block::HeaderBlock;
+use dg_xch_core::blockchain::sized_bytes::Bytes32;
+use dg_xch_core::blockchain::spend_bundle::SpendBundle;
+use dg_xch_serialize::ChiaSerialize;
+use log::debug;
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Error, ErrorKind};
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::{channel, Receiver, Sender};
+use tokio::sync::mpsc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+pub const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 15000;
+
+#[derive(Debug, Default, Clone)]
+pub struct CoinStateCache {
+    coin_states: HashMap<Bytes32, CoinState>,
+    puzzle_hashes: HashSet<Bytes32>,
+    coin_ids: HashSet<Bytes32>,
+    height: u32,
+    peak_hash: Option<Bytes32>,
+}
+impl CoinStateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    pub fn peak_hash(&self) -> Option<Bytes32> {
+        self.peak_hash
+    }
+    pub fn puzzle_hashes(&self) -> &HashSet<Bytes32> {
+        &self.puzzle_hashes
+    }
+    pub fn coin_ids(&self) -> &HashSet<Bytes32> {
+        &self.coin_ids
+    }
+    pub fn watch_puzzle_hashes(&mut self, puzzle_hashes: impl IntoIterator<Item = Bytes32>) {
+        self.puzzle_hashes.extend(puzzle_hashes);
+    }
+    pub fn watch_coin_ids(&mut self, coin_ids: impl IntoIterator<Item = Bytes32>) {
+        self.coin_ids.extend(coin_ids);
+    }
+    pub fn is_watched(&self, coin_state: &CoinState) -> bool {
+        self.puzzle_hashes.contains(&coin_state.coin.puzzle_hash)
+            || self.coin_ids.contains(&coin_state.coin.name())
+    }
+    pub fn get(&self, coin_id: &Bytes32) -> Option<&CoinState> {
+        self.coin_states.get(coin_id)
+    }
+    pub fn len(&self) -> usize {
+        self.coin_states.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.coin_states.is_empty()
+    }
+    pub fn coin_states(&self) -> Vec<CoinState> {
+        self.coin_states.values().cloned().collect()
+    }
+    pub fn unspent(&self) -> Vec<CoinState> {
+        self.coin_states
+            .values()
+            .filter(|c| c.created_height.is_some() && c.spent_height.is_none())
+            .cloned()
+            .collect()
+    }
+    pub fn unspent_for_puzzle_hash(&self, puzzle_hash: &Bytes32) -> Vec<CoinState> {
+        self.unspent()
+            .into_iter()
+            .filter(|c| c.coin.puzzle_hash == *puzzle_hash)
+            .collect()
+    }
+    pub fn balance(&self) -> u64 {
+        self.unspent().iter().map(|c| c.coin.amount).sum()
+    }
+    //Stores the given states, skipping any coin that is not for a watched puzzle hash or coin id.
+    //Returns the number of states stored.
+    pub fn apply(&mut self, coin_states: impl IntoIterator<Item = CoinState>) -> usize {
+        let mut applied = 0;
+        for coin_state in coin_states {
+            if self.is_watched(&coin_state) {
+                self.coin_states.insert(coin_state.coin.name(), coin_state);
+                applied += 1;
+            } else {
+                debug!("Ignoring unwatched coin state: {:?}", coin_state.coin);
+            }
+        }
+        applied
+    }
+    //Forgets everything that happened above `fork_height`.
+    //Coins created after the fork are removed and spends after the fork are undone.
+    pub fn rollback(&mut self, fork_height: u32) {
+        self.coin_states.retain(|_, c| match c.created_height {
+            Some(created) => created <= fork_height,
+            None => true,
+        });
+        for coin_state in self.coin_states.values_mut() {
+            if matches!(coin_state.spent_height, Some(spent) if spent > fork_height) {
+                coin_state.spent_height = None;
+            }
+        }
+        if self.height > fork_height {
+            self.height = fork_height;
+            self.peak_hash = None;
+        }
+    }
+    pub fn apply_update(&mut self, update: &CoinStateUpdate) -> usize {
+        if update.fork_height < self.height {
+            self.rollback(update.fork_height);
+        }
+        let applied = self.apply(update.items.iter().cloned());
+        self.height = update.height;
+        self.peak_hash = Some(update.peak_hash);
+        applied
+    }
+}
+
+struct CoinStateUpdateHandler {
+    cache: Arc<Mutex<CoinStateCache>>,
+    updates: Sender<CoinStateUpdate>,
+}
+#[async_trait]
+impl MessageHandler for CoinStateUpdateHandler {
+    async fn handle(&self, msg: Arc<ChiaMessage>) -> Result<(), Error> {
+        let mut cursor = Cursor::new(&msg.data);
+        let update = CoinStateUpdate::from_bytes(&mut cursor)?;
+        self.cache.lock().await.apply_update(&update);
+        let _ = self.updates.send(update);
+        Ok(())
+    }
+}
+
+struct NewPeakHandler {
+    peak: Arc<Mutex<Option<NewPeakWallet>>>,
+}
+#[async_trait]
+impl MessageHandler for NewPeakHandler {
+    async fn handle(&self, msg: Arc<ChiaMessage>) -> Result<(), Error> {
+        let mut cursor = Cursor::new(&msg.data);
+        let new_peak = NewPeakWallet::from_bytes(&mut cursor)?;
+        let mut peak = self.peak.lock().await;
+        if peak
+            .as_ref()
+            .map(|p| p.weight < new_peak.weight)
+            .unwrap_or(true)
+        {
+            *peak = Some(new_peak);
+        }
+        Ok(())
+    }
+}
+
+struct ResponseHandler {
+    channel: mpsc::Sender<Arc<ChiaMessage>>,
+}
+#[async_trait]
+impl MessageHandler for ResponseHandler {
+    async fn handle(&self, msg: Arc<ChiaMessage>) -> Result<(), Error> {
+        let _ = self.channel.send(msg).await;
+        Ok(())
+    }
+}
+
+pub struct WalletSync {
+    client: Arc<Mutex<Client>>,
+    cache: Arc<Mutex<CoinStateCache>>,
+    peak: Arc<Mutex<Option<NewPeakWallet>>>,
+    updates: Sender<CoinStateUpdate>,
+    handler_ids: Vec<Uuid>,
+    next_msg_id: AtomicU16,
+    timeout: u64,
+}
+impl WalletSync {
+    pub async fn new(client: Arc<Mutex<Client>>) -> Self {
+        let cache = Arc::new(Mutex::new(CoinStateCache::new()));
+        let peak = Arc::new(Mutex::new(None));
+        let (updates, _) = channel(64);
+        let update_id = Uuid::new_v4();
+        let peak_id = Uuid::new_v4();
+        {
+            let client = client.lock().await;
+            client
+                .subscribe(
+                    update_id,
+                    ChiaMessageHandler::new(
+                        ChiaMessageFilter {
+                            msg_type: Some(ProtocolMessageTypes::CoinStateUpdate),
+                            id: None,
+                        },
+                        Arc::new(CoinStateUpdateHandler {
+                            cache: cache.clone(),
+                            updates: updates.clone(),
+                        }),
+                    ),
+                )
+                .await;
+            client
+                .subscribe(
+                    peak_id,
+                    ChiaMessageHandler::new(
+                        ChiaMessageFilter {
+                            msg_type: Some(ProtocolMessageTypes::NewPeakWallet),
+                            id: None,
+                        },
+                        Arc::new(NewPeakHandler { peak: peak.clone() }),
+                    ),
+                )
+                .await;
+        }
+        WalletSync {
+            client,
+            cache,
+            peak,
+            updates,
+            handler_ids: vec![update_id, peak_id],
+            next_msg_id: AtomicU16::new(1),
+            timeout: DEFAULT_REQUEST_TIMEOUT_MS,
+        }
+    }
+    pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout = timeout_ms;
+        self
+    }
+    pub fn cache(&self) -> Arc<Mutex<CoinStateCache>> {
+        self.cache.clone()
+    }
+    pub async fn peak(&self) -> Option<NewPeakWallet> {
+        self.peak.lock().await.clone()
+    }
+    //Receives every `CoinStateUpdate` after it has been applied to the cache.
+    pub fn subscribe_updates(&self) -> Receiver<CoinStateUpdate> {
+        self.updates.subscribe()
+    }
+    pub async fn register_puzzle_hashes(
+        &self,
+        puzzle_hashes: Vec<Bytes32>,
+        min_height: u32,
+    ) -> Result<Vec<CoinState>, Error> {
+        self.cache
+            .lock()
+            .await
+            .watch_puzzle_hashes(puzzle_hashes.iter().copied());
+        let resp: RespondToPhUpdates = self
+            .request(
+                ProtocolMessageTypes::RegisterInterestInPuzzleHash,
+                &RegisterForPhUpdates {
+                    puzzle_hashes,
+                    min_height,
+                },
+                ProtocolMessageTypes::RespondToPhUpdate,
+                None,
+            )
+            .await?;
+        self.cache
+            .lock()
+            .await
+            .apply(resp.coin_states.iter().cloned());
+        Ok(resp.coin_states)
+    }
+    pub async fn register_coin_ids(
+        &self,
+        coin_ids: Vec<Bytes32>,
+        min_height: u32,
+    ) -> Result<Vec<CoinState>, Error> {
+        self.cache
+            .lock()
+            .await
+            .watch_coin_ids(coin_ids.iter().copied());
+        let resp: RespondToCoinUpdates = self
+            .request(
+                ProtocolMessageTypes::RegisterInterestInCoin,
+                &RegisterForCoinUpdates {
+                    coin_ids,
+                    min_height,
+                },
+                ProtocolMessageTypes::RespondToCoinUpdate,
+                None,
+            )
+            .await?;
+        self.cache
+            .lock()
+            .await
+            .apply(resp.coin_states.iter().cloned());
+        Ok(resp.coin_states)
+    }
+    pub async fn request_children(&self, coin_name: Bytes32) -> Result<Vec<CoinState>, Error> {
+        let resp: RespondChildren = self
+            .request(
+                ProtocolMessageTypes::RequestChildren,
+                &RequestChildren { coin_name },
+                ProtocolMessageTypes::RespondChildren,
+                None,
+            )
+            .await?;
+        Ok(resp.coin_states)
+    }
+    pub async fn request_puzzle_solution(
+        &self,
+        coin_name: Bytes32,
+        height: u32,
+    ) -> Result<PuzzleSolutionResponse, Error> {
+        let resp: RespondPuzzleSolution = self
+            .request(
+                ProtocolMessageTypes::RequestPuzzleSolution,
+                &RequestPuzzleSolution { coin_name, height },
+                ProtocolMessageTypes::RespondPuzzleSolution,
+                Some(ProtocolMessageTypes::RejectPuzzleSolution),
+            )
+            .await?;
+        Ok(resp.response)
+    }
+    pub async fn request_block_headers(
+        &self,
+        start_height: u32,
+        end_height: u32,
+        return_filter: bool,
+    ) -> Result<Vec<HeaderBlock>, Error> {
+        let resp: RespondBlockHeaders = self
+            .request(
+                ProtocolMessageTypes::RequestBlockHeaders,
+                &RequestBlockHeaders {
+                    start_height,
+                    end_height,
+                    return_filter,
+                },
+                ProtocolMessageTypes::RespondBlockHeaders,
+                Some(ProtocolMessageTypes::RejectBlockHeaders),
+            )
+            .await?;
+        Ok(resp.header_blocks)
+    }
+    pub async fn send_transaction(
+        &self,
+        transaction: SpendBundle,
+    ) -> Result<TransactionAck, Error> {
+        self.request(
+            ProtocolMessageTypes::SendTransaction,
+            &SendTransaction { transaction },
+            ProtocolMessageTypes::TransactionAck,
+            None,
+        )
+        .await
+    }
+    pub async fn close(self) {
+        let client = self.client.lock().await;
+        for id in &self.handler_ids {
+            client.unsubscribe(*id).await;
+        }
+    }
+
+    async fn request<T: ChiaSerialize, R: ChiaSerialize>(
+        &self,
+        msg_type: ProtocolMessageTypes,
+        msg: &T,
+        resp_type: ProtocolMessageTypes,
+        reject_type: Option<ProtocolMessageTypes>,
+    ) -> Result<R, Error> {
+        let msg_id = self.next_msg_id.fetch_add(1, Ordering::Relaxed);
+        let handle_id = Uuid::new_v4();
+        let (tx, mut rx) = mpsc::channel(1);
+        self.client
+            .lock()
+            .await
+            .subscribe(
+                handle_id,
+                ChiaMessageHandler::new(
+                    ChiaMessageFilter {
+                        msg_type: None,
+                        id: Some(msg_id),
+                    },
+                    Arc::new(ResponseHandler { channel: tx }),
+                ),
+            )
+            .await;
+        let sent = self
+            .client
+            .lock()
+            .await
+            .send(ChiaMessage::new(msg_type, msg, Some(msg_id)).into())
+            .await;
+        let res = match sent {
+            Ok(()) => tokio::time::timeout(Duration::from_millis(self.timeout), rx.recv())
+                .await
+                .map_err(|_| {
+                    Error::new(
+                        ErrorKind::TimedOut,
+                        format!("Timeout waiting for {:?}", resp_type),
+                    )
+                }),
+            Err(e) => Err(e),
+        };
+        self.client.lock().await.unsubscribe(handle_id).await;
+        let resp = res?.ok_or_else(|| {
+            Error::new(ErrorKind::Other, "Channel Closed before response received")
+        })?;
+        let mut cursor = Cursor::new(&resp.data);
+        if resp.msg_type == resp_type {
+            R::from_bytes(&mut cursor)
+        } else if Some(resp.msg_type) == reject_type {
+            let reason = match resp.msg_type {
+                ProtocolMessageTypes::RejectPuzzleSolution => {
+                    let reject = RejectPuzzleSolution::from_bytes(&mut cursor)?;
+                    format!(
+                        "Peer has no solution for coin {} at height {}",
+                        reject.coin_name, reject.height
+                    )
+                }
+                ProtocolMessageTypes::RejectBlockHeaders => {
+                    let reject = RejectBlockHeaders::from_bytes(&mut cursor)?;
+                    format!(
+                        "Peer rejected block headers {} to {}",
+                        reject.start_height, reject.end_height
+                    )
+                }
+                other => format!("Peer rejected request with {:?}", other),
+            };
+            Err(Error::new(ErrorKind::NotFound, reason))
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Expected {:?} in response to {:?}, got {:?}",
+                    resp_type, msg_type, resp.msg_type
+                ),
+            ))
+        }
+    }
+}
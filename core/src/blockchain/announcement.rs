@@ -1,6 +1,6 @@
 use crate::blockchain::sized_bytes::{Bytes32, SizedBytes};
 use dg_xch_macros::ChiaSerial;
-use dg_xch_serialize::{hash_256, ChiaSerialize};
+use dg_xch_serialize::hash_256;
 use serde::{Deserialize, Serialize};
 
 #[derive(ChiaSerial, PartialEq, Eq, Serialize, Deserialize, Debug)]
@@ -16,6 +16,8 @@ impl Announcement {
     where
         Self: Sized,
     {
-        hash_256(self.to_bytes())
+        let mut buf = self.origin_info.to_sized_bytes().to_vec();
+        buf.extend(&self.message);
+        hash_256(buf)
     }
 }
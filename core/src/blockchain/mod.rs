@@ -33,6 +33,7 @@ pub mod sized_bytes;
 pub mod spend;
 pub mod spend_bundle;
 pub mod spend_bundle_conditions;
+pub mod spend_bundle_simulator;
 pub mod sub_epoch_summary;
 pub mod subslot_bundle;
 pub mod subslot_proofs;
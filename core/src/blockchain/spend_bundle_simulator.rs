@@ -0,0 +1,350 @@
+use crate::blockchain::announcement::Announcement;
+use crate::blockchain::coin::Coin;
+use crate::blockchain::condition_opcode::{ConditionCost, ConditionOpcode};
+use crate::blockchain::condition_with_args::ConditionWithArgs;
+use crate::blockchain::sized_bytes::{Bytes32, Bytes48, SizedBytes};
+use crate::blockchain::spend_bundle::SpendBundle;
+use crate::blockchain::utils::{atom_to_int, pkm_pairs_for_conditions_dict};
+use crate::clvm::bls_bindings::aggregate_verify_signature;
+use crate::clvm::condition_utils::{conditions_by_opcode, conditions_for_solution};
+use crate::consensus::constants::ConsensusConstants;
+use blst::min_pk::Signature;
+use num_traits::ToPrimitive;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulationError {
+    DuplicateSpend(Bytes32),
+    PuzzleHashMismatch {
+        coin_id: Bytes32,
+        expected: Bytes32,
+        actual: Bytes32,
+    },
+    ExecutionFailed {
+        coin_id: Bytes32,
+        message: String,
+    },
+    InvalidCondition {
+        coin_id: Bytes32,
+        opcode: ConditionOpcode,
+    },
+    FailedAssertion {
+        coin_id: Bytes32,
+        opcode: ConditionOpcode,
+    },
+    DuplicateOutput(Bytes32),
+    MissingCoinAnnouncement(Bytes32),
+    MissingPuzzleAnnouncement(Bytes32),
+    OutputsExceedInputs {
+        inputs: u128,
+        outputs: u128,
+    },
+    ReserveFeeNotMet {
+        reserved: u128,
+        available: u128,
+    },
+    CostExceeded {
+        cost: u64,
+        max_cost: u64,
+    },
+    InvalidSignature,
+}
+
+#[derive(Debug, Clone)]
+pub struct CoinSpendReport {
+    pub coin: Coin,
+    pub coin_id: Bytes32,
+    pub puzzle_hash: Bytes32,
+    pub execution_cost: u64,
+    pub conditions: Vec<ConditionWithArgs>,
+    pub additions: Vec<Coin>,
+    pub reserve_fee: u128,
+    pub signature_pairs: Vec<(Bytes48, Vec<u8>)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpendBundleReport {
+    pub name: Bytes32,
+    pub spends: Vec<CoinSpendReport>,
+    pub removals: Vec<Coin>,
+    pub additions: Vec<Coin>,
+    pub input_amount: u128,
+    pub output_amount: u128,
+    //Inputs minus outputs, what the farmer actually receives
+    pub fee: u128,
+    pub reserve_fee: u128,
+    pub execution_cost: u64,
+    pub condition_cost: u64,
+    pub byte_cost: u64,
+    pub total_cost: u64,
+    pub coin_announcements: HashSet<Bytes32>,
+    pub puzzle_announcements: HashSet<Bytes32>,
+    pub signature_valid: bool,
+    pub errors: Vec<SimulationError>,
+}
+impl SpendBundleReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+//Runs every spend in the bundle locally and checks it the way a full node would before
+//admitting it to the mempool, except for anything needing chain state (coin existence,
+//height and time locks). Problems are collected in the report rather than returned early.
+pub fn simulate_spend_bundle(
+    spend_bundle: &SpendBundle,
+    constants: &ConsensusConstants,
+) -> SpendBundleReport {
+    let max_cost = constants.max_block_cost_clvm.to_u64().unwrap_or(u64::MAX);
+    let cost_per_byte = constants.cost_per_byte.to_u64().unwrap_or_default();
+    let mut errors = vec![];
+    let mut spends = vec![];
+    let mut seen_spends = HashSet::new();
+    let mut execution_cost = 0u64;
+    let mut condition_cost = 0u64;
+    let mut byte_cost = 0u64;
+    let mut coin_announcements = HashSet::new();
+    let mut puzzle_announcements = HashSet::new();
+    let mut coin_asserts = vec![];
+    let mut puzzle_asserts = vec![];
+    for coin_spend in &spend_bundle.coin_spends {
+        let coin_id = coin_spend.coin.name();
+        if !seen_spends.insert(coin_id) {
+            errors.push(SimulationError::DuplicateSpend(coin_id));
+        }
+        byte_cost = byte_cost.saturating_add(
+            ((coin_spend.puzzle_reveal.to_bytes().len() + coin_spend.solution.to_bytes().len())
+                as u64)
+                .saturating_mul(cost_per_byte),
+        );
+        let puzzle_hash = coin_spend.puzzle_reveal.to_program().tree_hash();
+        if puzzle_hash != coin_spend.coin.puzzle_hash {
+            errors.push(SimulationError::PuzzleHashMismatch {
+                coin_id,
+                expected: coin_spend.coin.puzzle_hash,
+                actual: puzzle_hash,
+            });
+        }
+        let (conditions, cost) = match conditions_for_solution(
+            &coin_spend.puzzle_reveal,
+            &coin_spend.solution,
+            max_cost,
+        ) {
+            Ok(v) => v,
+            Err(e) => {
+                errors.push(SimulationError::ExecutionFailed {
+                    coin_id,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        execution_cost = execution_cost.saturating_add(cost);
+        let mut additions = vec![];
+        let mut reserve_fee = 0u128;
+        for condition in &conditions {
+            let invalid = || SimulationError::InvalidCondition {
+                coin_id,
+                opcode: condition.opcode,
+            };
+            let failed = || SimulationError::FailedAssertion {
+                coin_id,
+                opcode: condition.opcode,
+            };
+            match condition.opcode {
+                ConditionOpcode::CreateCoin => {
+                    condition_cost += ConditionCost::CreateCoin as u64;
+                    match (condition.vars.first(), condition.vars.get(1)) {
+                        (Some(puzzle_hash), Some(amount)) if puzzle_hash.len() == 32 => {
+                            match atom_to_int(amount).to_u64() {
+                                Some(amount) => additions.push(Coin {
+                                    parent_coin_info: coin_id,
+                                    puzzle_hash: Bytes32::new(puzzle_hash),
+                                    amount,
+                                }),
+                                None => errors.push(invalid()),
+                            }
+                        }
+                        _ => errors.push(invalid()),
+                    }
+                }
+                ConditionOpcode::AggSigMe | ConditionOpcode::AggSigUnsafe => {
+                    condition_cost += ConditionCost::AggSig as u64;
+                }
+                ConditionOpcode::ReserveFee => match condition.vars.first() {
+                    Some(fee) => match atom_to_int(fee).to_u64() {
+                        Some(fee) => reserve_fee += fee as u128,
+                        None => errors.push(invalid()),
+                    },
+                    None => errors.push(invalid()),
+                },
+                ConditionOpcode::CreateCoinAnnouncement => match condition.vars.first() {
+                    Some(message) => {
+                        coin_announcements.insert(
+                            Announcement {
+                                origin_info: coin_id,
+                                message: message.clone(),
+                            }
+                            .name(),
+                        );
+                    }
+                    None => errors.push(invalid()),
+                },
+                ConditionOpcode::CreatePuzzleAnnouncement => match condition.vars.first() {
+                    Some(message) => {
+                        puzzle_announcements.insert(
+                            Announcement {
+                                origin_info: coin_spend.coin.puzzle_hash,
+                                message: message.clone(),
+                            }
+                            .name(),
+                        );
+                    }
+                    None => errors.push(invalid()),
+                },
+                ConditionOpcode::AssertCoinAnnouncement => match condition.vars.first() {
+                    Some(id) if id.len() == 32 => coin_asserts.push(Bytes32::new(id)),
+                    _ => errors.push(invalid()),
+                },
+                ConditionOpcode::AssertPuzzleAnnouncement => match condition.vars.first() {
+                    Some(id) if id.len() == 32 => puzzle_asserts.push(Bytes32::new(id)),
+                    _ => errors.push(invalid()),
+                },
+                ConditionOpcode::AssertMyCoinId
+                | ConditionOpcode::AssertMyParentId
+                | ConditionOpcode::AssertMyPuzzlehash => {
+                    let expected = match condition.opcode {
+                        ConditionOpcode::AssertMyCoinId => coin_id,
+                        ConditionOpcode::AssertMyParentId => coin_spend.coin.parent_coin_info,
+                        _ => coin_spend.coin.puzzle_hash,
+                    };
+                    if condition.vars.first().map(|v| v.as_slice()) != Some(expected.as_slice()) {
+                        errors.push(failed());
+                    }
+                }
+                ConditionOpcode::AssertMyAmount => {
+                    let amount = condition.vars.first().and_then(|a| atom_to_int(a).to_u64());
+                    if amount != Some(coin_spend.coin.amount) {
+                        errors.push(failed());
+                    }
+                }
+                _ => {}
+            }
+        }
+        let signature_pairs = match pkm_pairs_for_conditions_dict(
+            conditions_by_opcode(conditions.clone()),
+            coin_id,
+            &constants.agg_sig_me_additional_data,
+        ) {
+            Ok(pairs) => pairs,
+            Err(_) => {
+                errors.push(SimulationError::InvalidCondition {
+                    coin_id,
+                    opcode: ConditionOpcode::AggSigMe,
+                });
+                vec![]
+            }
+        };
+        spends.push(CoinSpendReport {
+            coin: coin_spend.coin.clone(),
+            coin_id,
+            puzzle_hash,
+            execution_cost: cost,
+            conditions,
+            additions,
+            reserve_fee,
+            signature_pairs,
+        });
+    }
+    for id in coin_asserts {
+        if !coin_announcements.contains(&id) {
+            errors.push(SimulationError::MissingCoinAnnouncement(id));
+        }
+    }
+    for id in puzzle_asserts {
+        if !puzzle_announcements.contains(&id) {
+            errors.push(SimulationError::MissingPuzzleAnnouncement(id));
+        }
+    }
+    let removals = spend_bundle.removals();
+    let additions: Vec<Coin> = spends
+        .iter()
+        .flat_map(|s| s.additions.iter().cloned())
+        .collect();
+    let mut seen_additions = HashSet::new();
+    for addition in &additions {
+        let name = addition.name();
+        if !seen_additions.insert(name) {
+            errors.push(SimulationError::DuplicateOutput(name));
+        }
+    }
+    let input_amount: u128 = removals.iter().map(|c| c.amount as u128).sum();
+    let output_amount: u128 = additions.iter().map(|c| c.amount as u128).sum();
+    let reserve_fee: u128 = spends.iter().map(|s| s.reserve_fee).sum();
+    if output_amount > input_amount {
+        errors.push(SimulationError::OutputsExceedInputs {
+            inputs: input_amount,
+            outputs: output_amount,
+        });
+    }
+    let fee = input_amount.saturating_sub(output_amount);
+    if reserve_fee > fee {
+        errors.push(SimulationError::ReserveFeeNotMet {
+            reserved: reserve_fee,
+            available: fee,
+        });
+    }
+    let total_cost = execution_cost
+        .saturating_add(condition_cost)
+        .saturating_add(byte_cost);
+    if total_cost > max_cost {
+        errors.push(SimulationError::CostExceeded {
+            cost: total_cost,
+            max_cost,
+        });
+    }
+    let signature_valid = verify_aggregate_signature(spend_bundle, &spends);
+    if !signature_valid {
+        errors.push(SimulationError::InvalidSignature);
+    }
+    SpendBundleReport {
+        name: spend_bundle.name(),
+        spends,
+        removals,
+        additions,
+        input_amount,
+        output_amount,
+        fee,
+        reserve_fee,
+        execution_cost,
+        condition_cost,
+        byte_cost,
+        total_cost,
+        coin_announcements,
+        puzzle_announcements,
+        signature_valid,
+        errors,
+    }
+}
+
+fn verify_aggregate_signature(spend_bundle: &SpendBundle, spends: &[CoinSpendReport]) -> bool {
+    let pairs: Vec<&(Bytes48, Vec<u8>)> = spends
+        .iter()
+        .flat_map(|s| s.signature_pairs.iter())
+        .collect();
+    let signature: Option<Signature> = (&spend_bundle.aggregated_signature).try_into().ok();
+    if pairs.is_empty() {
+        //Nothing to sign, only the empty or infinity signature is acceptable
+        let bytes = spend_bundle.aggregated_signature.to_sized_bytes();
+        return spend_bundle.aggregated_signature.is_null()
+            || (bytes[0] == 0xc0 && bytes[1..].iter().all(|b| *b == 0));
+    }
+    match signature {
+        Some(signature) => {
+            let public_keys: Vec<&Bytes48> = pairs.iter().map(|(pk, _)| pk).collect();
+            let msgs: Vec<&[u8]> = pairs.iter().map(|(_, msg)| msg.as_slice()).collect();
+            aggregate_verify_signature(&public_keys, &msgs, &signature)
+        }
+        None => false,
+    }
+}
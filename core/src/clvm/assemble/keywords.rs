@@ -1,42 +1,58 @@
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 
-const PAIRS: [(u8, &str); 32] = [
-    (0x01, "q"),
-    (0x02, "a"),
-    (0x03, "i"),
-    (0x04, "c"),
-    (0x05, "f"),
-    (0x06, "r"),
-    (0x07, "l"),
-    (0x08, "x"),
-    (0x09, "="),
-    (0x0a, ">s"),
-    (0x0b, "sha256"),
-    (0x0c, "substr"),
-    (0x0d, "strlen"),
-    (0x0e, "concat"),
-    (0x10, "+"),
-    (0x11, "-"),
-    (0x12, "*"),
-    (0x13, "/"),
-    (0x14, "divmod"),
-    (0x15, ">"),
-    (0x16, "ash"),
-    (0x17, "lsh"),
-    (0x18, "logand"),
-    (0x19, "logior"),
-    (0x1a, "logxor"),
-    (0x1b, "lognot"),
-    (0x1d, "point_add"),
-    (0x1e, "pubkey_for_exp"),
-    (0x20, "not"),
-    (0x21, "any"),
-    (0x22, "all"),
-    (0x24, "softfork"),
+const PAIRS: [(&[u8], &str); 48] = [
+    (&[0x01], "q"),
+    (&[0x02], "a"),
+    (&[0x03], "i"),
+    (&[0x04], "c"),
+    (&[0x05], "f"),
+    (&[0x06], "r"),
+    (&[0x07], "l"),
+    (&[0x08], "x"),
+    (&[0x09], "="),
+    (&[0x0a], ">s"),
+    (&[0x0b], "sha256"),
+    (&[0x0c], "substr"),
+    (&[0x0d], "strlen"),
+    (&[0x0e], "concat"),
+    (&[0x10], "+"),
+    (&[0x11], "-"),
+    (&[0x12], "*"),
+    (&[0x13], "/"),
+    (&[0x14], "divmod"),
+    (&[0x15], ">"),
+    (&[0x16], "ash"),
+    (&[0x17], "lsh"),
+    (&[0x18], "logand"),
+    (&[0x19], "logior"),
+    (&[0x1a], "logxor"),
+    (&[0x1b], "lognot"),
+    (&[0x1d], "point_add"),
+    (&[0x1e], "pubkey_for_exp"),
+    (&[0x20], "not"),
+    (&[0x21], "any"),
+    (&[0x22], "all"),
+    (&[0x24], "softfork"),
+    (&[0x30], "coinid"),
+    (&[0x31], "g1_subtract"),
+    (&[0x32], "g1_multiply"),
+    (&[0x33], "g1_negate"),
+    (&[0x34], "g2_add"),
+    (&[0x35], "g2_subtract"),
+    (&[0x36], "g2_multiply"),
+    (&[0x37], "g2_negate"),
+    (&[0x38], "g1_map"),
+    (&[0x39], "g2_map"),
+    (&[0x3a], "bls_pairing_identity"),
+    (&[0x3b], "bls_verify"),
+    (&[0x3c], "modpow"),
+    (&[0x3d], "%"),
+    (&[0x13, 0xd6, 0x1f, 0x00], "secp256k1_verify"),
+    (&[0x1c, 0x3a, 0x8f, 0x00], "secp256r1_verify"),
 ];
 
 pub static KEYWORD_FROM_ATOM: Lazy<HashMap<Vec<u8>, String>> =
-    Lazy::new(|| HashMap::from(PAIRS.map(|(k, v)| (vec![k], v.to_string()))));
+    Lazy::new(|| HashMap::from(PAIRS.map(|(k, v)| (k.to_vec(), v.to_string()))));
 pub static KEYWORD_TO_ATOM: Lazy<HashMap<String, Vec<u8>>> =
-    Lazy::new(|| HashMap::from(PAIRS.map(|(k, v)| (v.to_string(), vec![k]))));
+    Lazy::new(|| HashMap::from(PAIRS.map(|(k, v)| (v.to_string(), k.to_vec()))));
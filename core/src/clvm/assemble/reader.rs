@@ -3,7 +3,7 @@ use log::error;
 const EOL_CHARS: [u8; 2] = [b'\r', b'\n'];
 const QUOTE_CHARS: [u8; 2] = [b'\'', b'"'];
 const CONS_CHARS: [u8; 3] = [b'(', b'.', b')'];
-const SPACE_CHARS: [u8; 2] = [b' ', b'\t'];
+const SPACE_CHARS: [u8; 4] = [b' ', b'\t', b'\r', b'\n'];
 
 pub const START_CONS: Token = Token {
     bytes: &[b'('],
@@ -50,9 +50,9 @@ impl<'a> Reader<'a> {
             }
             for c in &self.stream[self.index..] {
                 if EOL_CHARS.contains(c) {
-                    self.index += 1;
-                } else {
                     break;
+                } else {
+                    self.index += 1;
                 }
             }
         }
@@ -60,7 +60,7 @@ impl<'a> Reader<'a> {
     pub fn consume_until_whitespace(&mut self) {
         for c in &self.stream[self.index..] {
             match c {
-                b' ' | b'\t' | b')' => {
+                b' ' | b'\t' | b'\r' | b'\n' | b')' => {
                     break;
                 }
                 _ => {
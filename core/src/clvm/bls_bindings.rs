@@ -57,3 +57,41 @@ pub fn sign(local_sk: &SecretKey, msg: &[u8]) -> Signature {
 pub fn sign_prepend(local_sk: &SecretKey, msg: &[u8], prepend_pk: &PublicKey) -> Signature {
     local_sk.sign(msg, AUG_SCHEME_DST, &prepend_pk.to_bytes())
 }
+
+pub const G1_MAP_DST: &[u8; 43] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_AUG_";
+
+pub fn hash_to_g1(msg: &[u8], dst: &[u8]) -> [u8; 48] {
+    let mut point = blst::blst_p1::default();
+    let mut out = [0u8; 48];
+    unsafe {
+        blst::blst_hash_to_g1(
+            &mut point,
+            msg.as_ptr(),
+            msg.len(),
+            dst.as_ptr(),
+            dst.len(),
+            std::ptr::null(),
+            0,
+        );
+        blst::blst_p1_compress(out.as_mut_ptr(), &point);
+    }
+    out
+}
+
+pub fn hash_to_g2(msg: &[u8], dst: &[u8]) -> [u8; 96] {
+    let mut point = blst::blst_p2::default();
+    let mut out = [0u8; 96];
+    unsafe {
+        blst::blst_hash_to_g2(
+            &mut point,
+            msg.as_ptr(),
+            msg.len(),
+            dst.as_ptr(),
+            dst.len(),
+            std::ptr::null(),
+            0,
+        );
+        blst::blst_p2_compress(out.as_mut_ptr(), &point);
+    }
+    out
+}
@@ -0,0 +1,263 @@
+use bls12_381::{
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Gt,
+};
+use std::io::{Error, ErrorKind};
+
+use crate::clvm::bls_bindings::{hash_to_g1, hash_to_g2, AUG_SCHEME_DST, G1_MAP_DST};
+use crate::clvm::more_ops::{mod_group_order, new_atom_and_cost, number_to_scalar};
+use crate::clvm::sexp::{SExp, NULL};
+use crate::clvm::utils::{arg_count, atom, check_arg_count, check_cost, int_atom, number_from_u8};
+
+const BLS_G1_SUBTRACT_BASE_COST: u64 = 101094;
+const BLS_G1_SUBTRACT_COST_PER_ARG: u64 = 1343980;
+const BLS_G1_MULTIPLY_BASE_COST: u64 = 705500;
+const BLS_G1_MULTIPLY_COST_PER_BYTE: u64 = 10;
+const BLS_G1_NEGATE_BASE_COST: u64 = 1396;
+const BLS_G2_ADD_BASE_COST: u64 = 80000;
+const BLS_G2_ADD_COST_PER_ARG: u64 = 1950000;
+const BLS_G2_SUBTRACT_BASE_COST: u64 = 80000;
+const BLS_G2_SUBTRACT_COST_PER_ARG: u64 = 1950000;
+const BLS_G2_MULTIPLY_BASE_COST: u64 = 2100000;
+const BLS_G2_MULTIPLY_COST_PER_BYTE: u64 = 5;
+const BLS_G2_NEGATE_BASE_COST: u64 = 2164;
+const BLS_MAP_TO_G1_BASE_COST: u64 = 195000;
+const BLS_MAP_TO_G1_COST_PER_BYTE: u64 = 4;
+const BLS_MAP_TO_G1_COST_PER_DST_BYTE: u64 = 4;
+const BLS_MAP_TO_G2_BASE_COST: u64 = 815000;
+const BLS_MAP_TO_G2_COST_PER_BYTE: u64 = 4;
+const BLS_MAP_TO_G2_COST_PER_DST_BYTE: u64 = 4;
+const BLS_PAIRING_BASE_COST: u64 = 3000000;
+const BLS_PAIRING_COST_PER_ARG: u64 = 1200000;
+
+fn g1_atom(arg: &SExp, op_name: &str) -> Result<G1Affine, Error> {
+    let blob = atom(arg, op_name)?;
+    let as_array: [u8; 48] = blob.try_into().map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("{op_name} atom is not G1 size, 48 bytes"),
+        )
+    })?;
+    Option::from(G1Affine::from_compressed(&as_array)).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("{op_name} atom is not a valid G1 point"),
+        )
+    })
+}
+
+fn g2_atom(arg: &SExp, op_name: &str) -> Result<G2Affine, Error> {
+    let blob = atom(arg, op_name)?;
+    let as_array: [u8; 96] = blob.try_into().map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("{op_name} atom is not G2 size, 96 bytes"),
+        )
+    })?;
+    Option::from(G2Affine::from_compressed(&as_array)).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("{op_name} atom is not a valid G2 point"),
+        )
+    })
+}
+
+// the optional second argument of the map operators overrides the DST
+fn map_args<'a>(
+    args: &'a SExp,
+    default_dst: &'a [u8],
+    op_name: &str,
+) -> Result<(&'a [u8], &'a [u8]), Error> {
+    match arg_count(args, 2) {
+        1 => Ok((atom(args.first()?, op_name)?, default_dst)),
+        2 => Ok((
+            atom(args.first()?, op_name)?,
+            atom(args.rest()?.first()?, op_name)?,
+        )),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("{op_name} takes 1 or 2 arguments"),
+        )),
+    }
+}
+
+pub fn op_bls_g1_subtract(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    let mut cost = BLS_G1_SUBTRACT_BASE_COST;
+    check_cost(cost, max_cost)?;
+    let mut total = G1Projective::identity();
+    for (index, arg) in args.iter().enumerate() {
+        let point = g1_atom(arg, "g1_subtract")?;
+        cost += BLS_G1_SUBTRACT_COST_PER_ARG;
+        check_cost(cost, max_cost)?;
+        if index == 0 {
+            total = point.into();
+        } else {
+            total -= &point;
+        }
+    }
+    let total: G1Affine = total.into();
+    new_atom_and_cost(cost, &total.to_compressed())
+}
+
+pub fn op_bls_g1_multiply(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    check_arg_count(&args, 2, "g1_multiply")?;
+    let mut cost = BLS_G1_MULTIPLY_BASE_COST;
+    check_cost(cost, max_cost)?;
+    let point = g1_atom(args.first()?, "g1_multiply")?;
+    let scalar = int_atom(args.rest()?.first()?, "g1_multiply")?;
+    cost += scalar.len() as u64 * BLS_G1_MULTIPLY_COST_PER_BYTE;
+    check_cost(cost, max_cost)?;
+    let scalar = number_to_scalar(mod_group_order(number_from_u8(scalar)));
+    let total: G1Affine = (point * scalar).into();
+    new_atom_and_cost(cost, &total.to_compressed())
+}
+
+pub fn op_bls_g1_negate(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    check_arg_count(&args, 1, "g1_negate")?;
+    let cost = BLS_G1_NEGATE_BASE_COST;
+    check_cost(cost, max_cost)?;
+    let point = g1_atom(args.first()?, "g1_negate")?;
+    new_atom_and_cost(cost, &(-point).to_compressed())
+}
+
+pub fn op_bls_g2_add(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    let mut cost = BLS_G2_ADD_BASE_COST;
+    check_cost(cost, max_cost)?;
+    let mut total = G2Projective::identity();
+    for arg in args.iter() {
+        let point = g2_atom(arg, "g2_add")?;
+        cost += BLS_G2_ADD_COST_PER_ARG;
+        check_cost(cost, max_cost)?;
+        total += &point;
+    }
+    let total: G2Affine = total.into();
+    new_atom_and_cost(cost, &total.to_compressed())
+}
+
+pub fn op_bls_g2_subtract(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    let mut cost = BLS_G2_SUBTRACT_BASE_COST;
+    check_cost(cost, max_cost)?;
+    let mut total = G2Projective::identity();
+    for (index, arg) in args.iter().enumerate() {
+        let point = g2_atom(arg, "g2_subtract")?;
+        cost += BLS_G2_SUBTRACT_COST_PER_ARG;
+        check_cost(cost, max_cost)?;
+        if index == 0 {
+            total = point.into();
+        } else {
+            total -= &point;
+        }
+    }
+    let total: G2Affine = total.into();
+    new_atom_and_cost(cost, &total.to_compressed())
+}
+
+pub fn op_bls_g2_multiply(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    check_arg_count(&args, 2, "g2_multiply")?;
+    let mut cost = BLS_G2_MULTIPLY_BASE_COST;
+    check_cost(cost, max_cost)?;
+    let point = g2_atom(args.first()?, "g2_multiply")?;
+    let scalar = int_atom(args.rest()?.first()?, "g2_multiply")?;
+    cost += scalar.len() as u64 * BLS_G2_MULTIPLY_COST_PER_BYTE;
+    check_cost(cost, max_cost)?;
+    let scalar = number_to_scalar(mod_group_order(number_from_u8(scalar)));
+    let total: G2Affine = (point * scalar).into();
+    new_atom_and_cost(cost, &total.to_compressed())
+}
+
+pub fn op_bls_g2_negate(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    check_arg_count(&args, 1, "g2_negate")?;
+    let cost = BLS_G2_NEGATE_BASE_COST;
+    check_cost(cost, max_cost)?;
+    let point = g2_atom(args.first()?, "g2_negate")?;
+    new_atom_and_cost(cost, &(-point).to_compressed())
+}
+
+pub fn op_bls_map_to_g1(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    let (msg, dst) = map_args(&args, G1_MAP_DST, "g1_map")?;
+    let mut cost = BLS_MAP_TO_G1_BASE_COST;
+    cost += msg.len() as u64 * BLS_MAP_TO_G1_COST_PER_BYTE;
+    cost += dst.len() as u64 * BLS_MAP_TO_G1_COST_PER_DST_BYTE;
+    check_cost(cost, max_cost)?;
+    new_atom_and_cost(cost, &hash_to_g1(msg, dst))
+}
+
+pub fn op_bls_map_to_g2(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    let (msg, dst) = map_args(&args, AUG_SCHEME_DST, "g2_map")?;
+    let mut cost = BLS_MAP_TO_G2_BASE_COST;
+    cost += msg.len() as u64 * BLS_MAP_TO_G2_COST_PER_BYTE;
+    cost += dst.len() as u64 * BLS_MAP_TO_G2_COST_PER_DST_BYTE;
+    check_cost(cost, max_cost)?;
+    new_atom_and_cost(cost, &hash_to_g2(msg, dst))
+}
+
+fn pairing_is_identity(items: &[(G1Affine, G2Affine)]) -> bool {
+    let prepared: Vec<(G1Affine, G2Prepared)> = items
+        .iter()
+        .map(|(g1, g2)| (*g1, G2Prepared::from(*g2)))
+        .collect();
+    let terms: Vec<(&G1Affine, &G2Prepared)> = prepared.iter().map(|(g1, g2)| (g1, g2)).collect();
+    multi_miller_loop(&terms).final_exponentiation() == Gt::identity()
+}
+
+pub fn op_bls_pairing_identity(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    let mut cost = BLS_PAIRING_BASE_COST;
+    check_cost(cost, max_cost)?;
+    let mut items = vec![];
+    let mut rest = &args;
+    while let Ok(pair) = rest.pair() {
+        let g2_pair = pair.rest.pair().map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "bls_pairing_identity missing g2 argument",
+            )
+        })?;
+        let g1 = g1_atom(&pair.first, "bls_pairing_identity")?;
+        let g2 = g2_atom(&g2_pair.first, "bls_pairing_identity")?;
+        cost += BLS_PAIRING_COST_PER_ARG;
+        check_cost(cost, max_cost)?;
+        items.push((g1, g2));
+        rest = &g2_pair.rest;
+    }
+    if pairing_is_identity(&items) {
+        Ok((cost, NULL.clone()))
+    } else {
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            "bls_pairing_identity failed",
+        ))
+    }
+}
+
+// (bls_verify signature pk1 msg1 pk2 msg2 ...) using the augmented scheme,
+// so each message is prefixed with its public key before hashing to G2
+pub fn op_bls_verify(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    let mut cost = BLS_PAIRING_BASE_COST;
+    check_cost(cost, max_cost)?;
+    let signature = g2_atom(args.first()?, "bls_verify")?;
+    let mut items = vec![(-G1Affine::generator(), signature)];
+    let mut rest = args.rest()?;
+    while let Ok(pair) = rest.pair() {
+        let msg_pair = pair.rest.pair().map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "bls_verify missing message argument",
+            )
+        })?;
+        let public_key = g1_atom(&pair.first, "bls_verify")?;
+        let msg = atom(&msg_pair.first, "bls_verify")?;
+        cost += BLS_PAIRING_COST_PER_ARG;
+        cost += msg.len() as u64 * BLS_MAP_TO_G2_COST_PER_BYTE;
+        cost += AUG_SCHEME_DST.len() as u64 * BLS_MAP_TO_G2_COST_PER_DST_BYTE;
+        check_cost(cost, max_cost)?;
+        let mut augmented = public_key.to_compressed().to_vec();
+        augmented.extend_from_slice(msg);
+        let hashed = G2Affine::from_compressed(&hash_to_g2(&augmented, AUG_SCHEME_DST)).unwrap();
+        items.push((public_key, hashed));
+        rest = &msg_pair.rest;
+    }
+    if pairing_is_identity(&items) {
+        Ok((cost, NULL.clone()))
+    } else {
+        Err(Error::new(ErrorKind::InvalidData, "bls_verify failed"))
+    }
+}
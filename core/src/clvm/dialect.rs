@@ -5,13 +5,19 @@ pub trait Dialect {
     fn apply_kw(&self) -> &[u8];
     fn op(&self, op: SExp, args: SExp, max_cost: u64) -> Result<(u64, SExp), Error>;
 }
+use crate::clvm::bls_ops::{
+    op_bls_g1_multiply, op_bls_g1_negate, op_bls_g1_subtract, op_bls_g2_add, op_bls_g2_multiply,
+    op_bls_g2_negate, op_bls_g2_subtract, op_bls_map_to_g1, op_bls_map_to_g2,
+    op_bls_pairing_identity, op_bls_verify,
+};
 use crate::clvm::core_ops::{op_cons, op_eq, op_first, op_if, op_listp, op_raise, op_rest};
 use crate::clvm::more_ops::{
-    op_add, op_all, op_any, op_ash, op_concat, op_div, op_div_deprecated, op_divmod, op_gr,
-    op_gr_bytes, op_logand, op_logior, op_lognot, op_logxor, op_lsh, op_multiply, op_not,
-    op_point_add, op_pubkey_for_exp, op_sha256, op_softfork, op_strlen, op_substr, op_subtract,
-    op_unknown,
+    op_add, op_all, op_any, op_ash, op_coinid, op_concat, op_div, op_div_deprecated, op_divmod,
+    op_gr, op_gr_bytes, op_logand, op_logior, op_lognot, op_logxor, op_lsh, op_mod, op_modpow,
+    op_multiply, op_not, op_point_add, op_pubkey_for_exp, op_sha256, op_softfork, op_strlen,
+    op_substr, op_subtract, op_unknown,
 };
+use crate::clvm::secp_ops::{op_secp256k1_verify, op_secp256r1_verify};
 
 // division with negative numbers are disallowed
 pub const NO_NEG_DIV: u32 = 0x0001;
@@ -20,6 +26,18 @@ pub const NO_NEG_DIV: u32 = 0x0001;
 // (otherwise they are no-ops with well defined cost)
 pub const NO_UNKNOWN_OPS: u32 = 0x0002;
 
+// enables coinid, the BLS operators (g1/g2 arithmetic, map, pairing and verify),
+// modpow and %. Without it they are treated as unknown operators
+pub const ENABLE_BLS_OPS: u32 = 0x0004;
+
+// enables secp256k1_verify and secp256r1_verify
+pub const ENABLE_SECP_OPS: u32 = 0x0008;
+
+type OpFn = fn(SExp, u64) -> Result<(u64, SExp), Error>;
+
+const SECP256K1_VERIFY_OPCODE: [u8; 4] = [0x13, 0xd6, 0x1f, 0x00];
+const SECP256R1_VERIFY_OPCODE: [u8; 4] = [0x1c, 0x3a, 0x8f, 0x00];
+
 pub struct ChiaDialect {
     flags: u32,
 }
@@ -28,6 +46,43 @@ impl ChiaDialect {
     pub fn new(flags: u32) -> ChiaDialect {
         ChiaDialect { flags }
     }
+
+    fn bls_op(&self, opcode: u8) -> Option<OpFn> {
+        if (self.flags & ENABLE_BLS_OPS) == 0 {
+            return None;
+        }
+        let f: OpFn = match opcode {
+            48 => op_coinid,
+            49 => op_bls_g1_subtract,
+            50 => op_bls_g1_multiply,
+            51 => op_bls_g1_negate,
+            52 => op_bls_g2_add,
+            53 => op_bls_g2_subtract,
+            54 => op_bls_g2_multiply,
+            55 => op_bls_g2_negate,
+            56 => op_bls_map_to_g1,
+            57 => op_bls_map_to_g2,
+            58 => op_bls_pairing_identity,
+            59 => op_bls_verify,
+            60 => op_modpow,
+            61 => op_mod,
+            _ => return None,
+        };
+        Some(f)
+    }
+
+    fn secp_op(&self, opcode: &[u8]) -> Option<OpFn> {
+        if (self.flags & ENABLE_SECP_OPS) == 0 {
+            return None;
+        }
+        if opcode == SECP256K1_VERIFY_OPCODE {
+            Some(op_secp256k1_verify)
+        } else if opcode == SECP256R1_VERIFY_OPCODE {
+            Some(op_secp256r1_verify)
+        } else {
+            None
+        }
+    }
 }
 
 impl Dialect for ChiaDialect {
@@ -35,6 +90,9 @@ impl Dialect for ChiaDialect {
         match &o {
             SExp::Atom(buf) => {
                 let b = &buf.data;
+                if let Some(f) = self.secp_op(b) {
+                    return f(argument_list, max_cost);
+                }
                 if b.len() != 1 {
                     return if (self.flags & NO_UNKNOWN_OPS) != 0 {
                         return Err(Error::new(
@@ -45,6 +103,9 @@ impl Dialect for ChiaDialect {
                         op_unknown(o, &argument_list, max_cost)
                     };
                 }
+                if let Some(f) = self.bls_op(b[0]) {
+                    return f(argument_list, max_cost);
+                }
                 let f = match b[0] {
                     3 => op_if,
                     4 => op_cons,
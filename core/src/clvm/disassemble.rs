@@ -0,0 +1,136 @@
+use crate::clvm::assemble::keywords::KEYWORD_FROM_ATOM;
+use crate::clvm::sexp::SExp;
+use hex::encode;
+use num_bigint::{BigInt, Sign};
+
+// lines longer than this are broken up when pretty printing
+const LINE_WIDTH: usize = 80;
+
+// Intermediate form so the same tree can be rendered on one line or indented
+enum Ir {
+    Atom(String),
+    List(Vec<Ir>, Option<Box<Ir>>),
+}
+
+pub fn disassemble(sexp: &SExp) -> String {
+    let mut buffer = String::new();
+    write_flat(&to_ir(sexp, false), &mut buffer);
+    buffer
+}
+
+// Same output as `disassemble` but lists that do not fit on a line are split
+// with one argument per line, indented by `indent` spaces per level
+pub fn disassemble_pretty(sexp: &SExp, indent: usize) -> String {
+    let mut buffer = String::new();
+    write_pretty(&to_ir(sexp, false), 0, indent, &mut buffer);
+    buffer
+}
+
+// Renders an atom the way the assembler reads it back: () for nil, ints for
+// short canonical values, quoted strings for printable text and hex otherwise
+pub fn disassemble_atom(atom: &[u8]) -> String {
+    if atom.is_empty() {
+        "()".to_string()
+    } else if atom.len() > 2 {
+        match std::str::from_utf8(atom) {
+            Ok(as_utf8) if as_utf8.bytes().all(is_printable) => {
+                if !as_utf8.contains('"') {
+                    format!("\"{as_utf8}\"")
+                } else if !as_utf8.contains('\'') {
+                    format!("'{as_utf8}'")
+                } else {
+                    format!("0x{}", encode(atom))
+                }
+            }
+            _ => format!("0x{}", encode(atom)),
+        }
+    } else {
+        let as_int = BigInt::from_signed_bytes_be(atom);
+        // zero is always the empty atom, so a single 0x00 byte stays hex
+        if as_int.sign() != Sign::NoSign && as_int.to_signed_bytes_be() == atom {
+            as_int.to_string()
+        } else {
+            format!("0x{}", encode(atom))
+        }
+    }
+}
+
+// backslashes are excluded because the reader treats them as escapes
+fn is_printable(c: u8) -> bool {
+    (c.is_ascii_graphic() || c == b' ') && c != b'\\'
+}
+
+// Only the head of a list is shown as an operator keyword, arguments and
+// quoted values keep their plain representation
+fn to_ir(sexp: &SExp, allow_keyword: bool) -> Ir {
+    match sexp {
+        SExp::Atom(a) => {
+            if allow_keyword {
+                if let Some(kw) = KEYWORD_FROM_ATOM.get(&a.data) {
+                    return Ir::Atom(kw.clone());
+                }
+            }
+            Ir::Atom(disassemble_atom(&a.data))
+        }
+        SExp::Pair(pair) => {
+            let mut items = vec![to_ir(&pair.first, true)];
+            let mut current = pair.rest.as_ref();
+            while let SExp::Pair(p) = current {
+                items.push(to_ir(&p.first, false));
+                current = p.rest.as_ref();
+            }
+            let tail = if current.non_nil() {
+                Some(Box::new(to_ir(current, false)))
+            } else {
+                None
+            };
+            Ir::List(items, tail)
+        }
+    }
+}
+
+fn write_flat(ir: &Ir, buffer: &mut String) {
+    match ir {
+        Ir::Atom(s) => buffer.push_str(s),
+        Ir::List(items, tail) => {
+            buffer.push('(');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    buffer.push(' ');
+                }
+                write_flat(item, buffer);
+            }
+            if let Some(tail) = tail {
+                buffer.push_str(" . ");
+                write_flat(tail, buffer);
+            }
+            buffer.push(')');
+        }
+    }
+}
+
+fn write_pretty(ir: &Ir, depth: usize, indent: usize, buffer: &mut String) {
+    let mut flat = String::new();
+    write_flat(ir, &mut flat);
+    match ir {
+        Ir::List(items, tail) if depth * indent + flat.len() > LINE_WIDTH => {
+            let padding = " ".repeat((depth + 1) * indent);
+            buffer.push('(');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    buffer.push('\n');
+                    buffer.push_str(&padding);
+                }
+                write_pretty(item, depth + 1, indent, buffer);
+            }
+            if let Some(tail) = tail {
+                buffer.push('\n');
+                buffer.push_str(&padding);
+                buffer.push_str(". ");
+                write_pretty(tail, depth + 1, indent, buffer);
+            }
+            buffer.push(')');
+        }
+        _ => buffer.push_str(&flat),
+    }
+}
@@ -1,13 +1,17 @@
 pub mod assemble;
 pub mod bls_bindings;
+pub mod bls_ops;
 pub mod casts;
 pub mod condition_utils;
 pub mod core_ops;
 pub mod curry_utils;
 pub mod dialect;
+pub mod disassemble;
 pub mod more_ops;
 pub mod parser;
 pub mod program;
 pub mod run_program;
+pub mod secp_ops;
 pub mod sexp;
+pub mod trace;
 pub mod utils;
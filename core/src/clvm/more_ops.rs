@@ -18,7 +18,7 @@ use crate::clvm::utils::{
     number_from_u8, ptr_from_number, two_ints, u32_from_u8,
 };
 
-const MALLOC_COST_PER_BYTE: u64 = 10;
+pub(crate) const MALLOC_COST_PER_BYTE: u64 = 10;
 
 const ARITH_BASE_COST: u64 = 99;
 const ARITH_COST_PER_ARG: u64 = 320;
@@ -68,6 +68,15 @@ const LSHIFT_COST_PER_BYTE: u64 = 3;
 const BOOL_BASE_COST: u64 = 200;
 const BOOL_COST_PER_ARG: u64 = 300;
 
+// the same as a sha256 of the parent, puzzle hash and 8 byte amount
+const COINID_COST: u64 =
+    SHA256_BASE_COST + SHA256_COST_PER_ARG * 3 + SHA256_COST_PER_BYTE * (32 + 32 + 8);
+
+const MODPOW_BASE_COST: u64 = 17000;
+const MODPOW_COST_PER_BYTE_BASE_VALUE: u64 = 38;
+const MODPOW_COST_PER_BYTE_EXPONENT: u64 = 3;
+const MODPOW_COST_PER_BYTE_MOD: u64 = 21;
+
 // Raspberry PI 4 is about 7.679960 / 1.201742 = 6.39 times slower
 // in the point_add benchmark
 
@@ -88,12 +97,12 @@ fn limbs_for_int(v: &BigInt) -> usize {
     ((v.bits() + 7) / 8) as usize
 }
 
-fn new_atom_and_cost(cost: u64, buf: &[u8]) -> Result<(u64, SExp), Error> {
+pub(crate) fn new_atom_and_cost(cost: u64, buf: &[u8]) -> Result<(u64, SExp), Error> {
     let c = buf.len() as u64 * MALLOC_COST_PER_BYTE;
     Ok((cost + c, SExp::Atom(buf.to_vec().into())))
 }
 
-fn malloc_cost(cost: u64, ptr: SExp) -> Result<(u64, SExp), Error> {
+pub(crate) fn malloc_cost(cost: u64, ptr: SExp) -> Result<(u64, SExp), Error> {
     let c = ptr.atom()?.data.len() as u64 * MALLOC_COST_PER_BYTE;
     Ok((cost + c, ptr))
 }
@@ -189,6 +198,52 @@ pub fn op_sha256(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
     new_atom_and_cost(cost, &hasher.finalize())
 }
 
+pub fn op_coinid(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    check_arg_count(&args, 3, "coinid")?;
+    check_cost(COINID_COST, max_cost)?;
+    let parent_id = atom(args.first()?, "coinid")?;
+    let puzzle_hash = atom(args.rest()?.first()?, "coinid")?;
+    let amount = int_atom(args.rest()?.rest()?.first()?, "coinid")?;
+    if parent_id.len() != 32 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "coinid: invalid parent coin id (must be 32 bytes)",
+        ));
+    }
+    if puzzle_hash.len() != 32 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "coinid: invalid puzzle hash (must be 32 bytes)",
+        ));
+    }
+    // the amount must be the canonical encoding of a non-negative u64
+    if !amount.is_empty() {
+        if amount[0] & 0x80 != 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "coinid: invalid amount (may not be negative)",
+            ));
+        }
+        if amount == [0] || (amount.len() > 1 && amount[0] == 0 && amount[1] & 0x80 == 0) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "coinid: invalid amount (may not have redundant leading zero)",
+            ));
+        }
+        if amount.len() > 9 || (amount.len() == 9 && amount[0] != 0) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "coinid: invalid amount (may not exceed max coin amount)",
+            ));
+        }
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(parent_id);
+    hasher.update(puzzle_hash);
+    hasher.update(amount);
+    new_atom_and_cost(COINID_COST, &hasher.finalize())
+}
+
 pub fn op_add(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
     let mut cost = ARITH_BASE_COST;
     let mut byte_count: usize = 0;
@@ -312,6 +367,47 @@ pub fn op_divmod(args: SExp, _max_cost: u64) -> Result<(u64, SExp), Error> {
     }
 }
 
+pub fn op_mod(args: SExp, _max_cost: u64) -> Result<(u64, SExp), Error> {
+    let (a0, l0, a1, l1) = two_ints(&args, "mod")?;
+    let cost = DIV_BASE_COST + ((l0 + l1) as u64) * DIV_COST_PER_BYTE;
+    if a1.sign() == Sign::NoSign {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            format!("mod with 0 : {:?}", args.first()?),
+        ))
+    } else {
+        malloc_cost(cost, ptr_from_number(&a0.mod_floor(&a1))?)
+    }
+}
+
+pub fn op_modpow(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    check_arg_count(&args, 3, "modpow")?;
+    let mut cost = MODPOW_BASE_COST;
+    let base = int_atom(args.first()?, "modpow")?;
+    cost += base.len() as u64 * MODPOW_COST_PER_BYTE_BASE_VALUE;
+    let exponent = int_atom(args.rest()?.first()?, "modpow")?;
+    cost += (exponent.len() * exponent.len()) as u64 * MODPOW_COST_PER_BYTE_EXPONENT;
+    check_cost(cost, max_cost)?;
+    let modulus = int_atom(args.rest()?.rest()?.first()?, "modpow")?;
+    cost += (modulus.len() * modulus.len()) as u64 * MODPOW_COST_PER_BYTE_MOD;
+    check_cost(cost, max_cost)?;
+    let (base, exponent, modulus) = (
+        number_from_u8(base),
+        number_from_u8(exponent),
+        number_from_u8(modulus),
+    );
+    if exponent.sign() == Sign::Minus {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "modpow with negative exponent",
+        ));
+    }
+    if modulus.sign() == Sign::NoSign {
+        return Err(Error::new(ErrorKind::InvalidData, "modpow with 0 modulus"));
+    }
+    malloc_cost(cost, ptr_from_number(&base.modpow(&exponent, &modulus))?)
+}
+
 pub fn op_gr(args: SExp, _max_cost: u64) -> Result<(u64, SExp), Error> {
     check_arg_count(&args, 2, ">")?;
     let a0 = args.first()?;
@@ -586,7 +682,7 @@ static GROUP_ORDER: Lazy<BigInt> = Lazy::new(|| {
     n.into()
 });
 
-fn mod_group_order(n: BigInt) -> BigInt {
+pub(crate) fn mod_group_order(n: BigInt) -> BigInt {
     let order = GROUP_ORDER.clone();
     let mut remainder = n.mod_floor(&order);
     if remainder.sign() == Sign::Minus {
@@ -595,7 +691,7 @@ fn mod_group_order(n: BigInt) -> BigInt {
     remainder
 }
 
-fn number_to_scalar(n: BigInt) -> Scalar {
+pub(crate) fn number_to_scalar(n: BigInt) -> Scalar {
     let (sign, as_u8): (Sign, Vec<u8>) = n.to_bytes_le();
     let mut scalar_array: [u8; 32] = [0; 32];
     scalar_array[..as_u8.len()].clone_from_slice(&as_u8[..]);
@@ -1,13 +1,20 @@
 use crate::clvm::sexp::AtomBuf;
 use crate::clvm::sexp::{SExp, NULL};
 use bytes::Buf;
+use dg_xch_serialize::hash_256;
+use std::collections::HashMap;
 use std::io::Read;
 use std::io::{Cursor, Write};
 use std::io::{Error, ErrorKind};
 
 const MAX_SINGLE_BYTE: u8 = 0x7f;
 const CONS_BOX_MARKER: u8 = 0xff;
+const BACK_REFERENCE: u8 = 0xfe;
 const MAX_DECODE_SIZE: u64 = 0x400000000;
+// Back references are expanded into copies, so the classic serialized size of
+// everything produced while parsing is capped to keep small inputs from
+// expanding exponentially
+const MAX_BACKREF_EXPANSION: u64 = 0x1000000;
 
 enum ParserOp {
     Exp,
@@ -27,18 +34,8 @@ pub fn sexp_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<SExp, Error> {
                     op_buf.push(ParserOp::Cons);
                     op_buf.push(ParserOp::Exp);
                     op_buf.push(ParserOp::Exp);
-                } else if byte_buf[0] == 0x80 {
-                    val_buf.push(NULL.clone());
-                } else if byte_buf[0] <= MAX_SINGLE_BYTE {
-                    val_buf.push(SExp::Atom(AtomBuf::new(byte_buf.to_vec())));
                 } else {
-                    let blob_size = decode_size(&mut stream, byte_buf[0])?;
-                    if stream.remaining() < blob_size as usize {
-                        return Err(Error::new(ErrorKind::InvalidInput, "bad encoding"));
-                    }
-                    let mut blob: Vec<u8> = vec![0; blob_size as usize];
-                    stream.read_exact(&mut blob)?;
-                    val_buf.push(SExp::Atom(AtomBuf::new(blob)));
+                    val_buf.push(parse_atom(&mut stream, byte_buf[0])?);
                 }
             }
             ParserOp::Cons => {
@@ -59,6 +56,180 @@ pub fn sexp_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<SExp, Error> {
         .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Failed to Parse SExp"))
 }
 
+// Parses both the classic serialization and the compressed form where 0xfe
+// followed by an atom is a path into the stack of already parsed values
+pub fn sexp_from_bytes_backrefs<T: AsRef<[u8]>>(bytes: T) -> Result<SExp, Error> {
+    let mut stream = Cursor::new(bytes);
+    let mut byte_buf = [0; 1];
+    let mut op_buf = vec![ParserOp::Exp];
+    let mut val_buf: Vec<SExp> = vec![];
+    // expanded serialized size of each value in val_buf
+    let mut size_buf: Vec<u64> = vec![];
+    let mut expanded = 0u64;
+    while let Some(op) = op_buf.pop() {
+        match op {
+            ParserOp::Exp => {
+                stream.read_exact(&mut byte_buf)?;
+                if byte_buf[0] == CONS_BOX_MARKER {
+                    op_buf.push(ParserOp::Cons);
+                    op_buf.push(ParserOp::Exp);
+                    op_buf.push(ParserOp::Exp);
+                    continue;
+                }
+                let (value, size) = if byte_buf[0] == BACK_REFERENCE {
+                    stream.read_exact(&mut byte_buf)?;
+                    let path = parse_atom(&mut stream, byte_buf[0])?;
+                    let path = path.atom()?;
+                    traverse_stack(
+                        &val_buf,
+                        &size_buf,
+                        &path.data,
+                        MAX_BACKREF_EXPANSION - expanded,
+                    )?
+                } else {
+                    let atom = parse_atom(&mut stream, byte_buf[0])?;
+                    let size = atom_len(&atom.atom()?.data);
+                    (atom, size)
+                };
+                expanded += size;
+                if expanded > MAX_BACKREF_EXPANSION {
+                    return Err(expansion_limit());
+                }
+                val_buf.push(value);
+                size_buf.push(size);
+            }
+            ParserOp::Cons => {
+                if let (Some(second), Some(second_size)) = (val_buf.pop(), size_buf.pop()) {
+                    if let (Some(first), Some(first_size)) = (val_buf.pop(), size_buf.pop()) {
+                        expanded += 1;
+                        if expanded > MAX_BACKREF_EXPANSION {
+                            return Err(expansion_limit());
+                        }
+                        val_buf.push(SExp::Pair((&first, &second).into()));
+                        size_buf.push(1 + first_size + second_size);
+                    } else {
+                        return Err(Error::new(ErrorKind::InvalidInput, "bad encoding"));
+                    }
+                } else {
+                    return Err(Error::new(ErrorKind::InvalidInput, "bad encoding"));
+                }
+            }
+        }
+    }
+    val_buf
+        .pop()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Failed to Parse SExp"))
+}
+
+fn parse_atom(stream: &mut Cursor<impl AsRef<[u8]>>, first_byte: u8) -> Result<SExp, Error> {
+    if first_byte == 0x80 {
+        Ok(NULL.clone())
+    } else if first_byte <= MAX_SINGLE_BYTE {
+        Ok(SExp::Atom(AtomBuf::new(vec![first_byte])))
+    } else {
+        let blob_size = decode_size(stream, first_byte)?;
+        if stream.remaining() < blob_size as usize {
+            return Err(Error::new(ErrorKind::InvalidInput, "bad encoding"));
+        }
+        let mut blob: Vec<u8> = vec![0; blob_size as usize];
+        stream.read_exact(&mut blob)?;
+        Ok(SExp::Atom(AtomBuf::new(blob)))
+    }
+}
+
+fn expansion_limit() -> Error {
+    Error::new(
+        ErrorKind::InvalidInput,
+        "back references expand past the size limit",
+    )
+}
+
+// Serialized size of a node, failing once it passes the limit
+fn expanded_size(node: &SExp, limit: u64) -> Result<u64, Error> {
+    let mut size = 0u64;
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        match node {
+            SExp::Atom(atom) => size += atom_len(&atom.data),
+            SExp::Pair(pair) => {
+                size += 1;
+                stack.push(pair.rest.as_ref());
+                stack.push(pair.first.as_ref());
+            }
+        }
+        if size > limit {
+            return Err(expansion_limit());
+        }
+    }
+    Ok(size)
+}
+
+// Path bits from least to most significant, without the sentinel bit
+fn path_bits(path: &[u8]) -> Vec<bool> {
+    let mut bits = vec![];
+    let Some(first) = path.iter().position(|b| *b != 0) else {
+        return bits;
+    };
+    for (index, byte) in path[first..].iter().rev().enumerate() {
+        let width = if index == path.len() - first - 1 {
+            7 - byte.leading_zeros() as usize
+        } else {
+            8
+        };
+        for bit in 0..width {
+            bits.push(byte & (1 << bit) != 0);
+        }
+    }
+    bits
+}
+
+// The parse stack is treated as a list with the most recent value first, so
+// a path of 1 bits walks down the stack and a 0 bit enters the value there.
+// Returns the referenced value and its expanded size, at most limit
+fn traverse_stack(
+    stack: &[SExp],
+    sizes: &[u64],
+    path: &[u8],
+    limit: u64,
+) -> Result<(SExp, u64), Error> {
+    let bad_path = || Error::new(ErrorKind::InvalidInput, "bad back reference");
+    if path.iter().all(|b| *b == 0) {
+        return Ok((NULL.clone(), 1));
+    }
+    let mut depth = 0;
+    let mut node: Option<&SExp> = None;
+    for bit in path_bits(path) {
+        node = match node {
+            None if depth >= stack.len() => return Err(bad_path()),
+            None if bit => {
+                depth += 1;
+                None
+            }
+            None => Some(&stack[stack.len() - 1 - depth]),
+            Some(SExp::Pair(pair)) if bit => Some(pair.rest.as_ref()),
+            Some(SExp::Pair(pair)) => Some(pair.first.as_ref()),
+            Some(SExp::Atom(_)) => return Err(bad_path()),
+        };
+    }
+    match node {
+        Some(node) => {
+            let size = expanded_size(node, limit)?;
+            Ok((node.clone(), size))
+        }
+        None => {
+            let values = stack.len() - depth;
+            let size = sizes[..values].iter().sum::<u64>() + values as u64 + 1;
+            if size > limit {
+                return Err(expansion_limit());
+            }
+            let list = stack[..values].iter().fold(NULL.clone(), |rest, value| {
+                SExp::Pair((value, &rest).into())
+            });
+            Ok((list, size))
+        }
+    }
+}
+
 pub fn sexp_to_bytes(sexp: &SExp) -> std::io::Result<Vec<u8>> {
     let mut buffer = Cursor::new(Vec::new());
     let mut stack: Vec<&SExp> = vec![sexp];
@@ -84,6 +255,206 @@ pub fn sexp_to_bytes(sexp: &SExp) -> std::io::Result<Vec<u8>> {
     Ok(buffer.into_inner())
 }
 
+// Node information used to find repeated subtrees, keyed by node address
+struct NodeInfo {
+    tree_hash: Vec<u8>,
+    serialized_len: u64,
+    // parent node and whether this node is its rest
+    parent: Option<(*const SExp, bool)>,
+}
+
+fn node_info(sexp: &SExp) -> HashMap<*const SExp, NodeInfo> {
+    let mut info: HashMap<*const SExp, NodeInfo> = HashMap::new();
+    let mut stack: Vec<(&SExp, bool)> = vec![(sexp, false)];
+    let mut parents: HashMap<*const SExp, (*const SExp, bool)> = HashMap::new();
+    while let Some((node, visited)) = stack.pop() {
+        let key = node as *const SExp;
+        match node {
+            SExp::Atom(atom) => {
+                let mut preimage = vec![1u8];
+                preimage.extend_from_slice(&atom.data);
+                info.insert(
+                    key,
+                    NodeInfo {
+                        tree_hash: hash_256(preimage),
+                        serialized_len: atom_len(&atom.data),
+                        parent: parents.get(&key).copied(),
+                    },
+                );
+            }
+            SExp::Pair(pair) if visited => {
+                let first = &info[&(pair.first.as_ref() as *const SExp)];
+                let rest = &info[&(pair.rest.as_ref() as *const SExp)];
+                let mut preimage = vec![2u8];
+                preimage.extend_from_slice(&first.tree_hash);
+                preimage.extend_from_slice(&rest.tree_hash);
+                let serialized_len = 1 + first.serialized_len + rest.serialized_len;
+                info.insert(
+                    key,
+                    NodeInfo {
+                        tree_hash: hash_256(preimage),
+                        serialized_len,
+                        parent: parents.get(&key).copied(),
+                    },
+                );
+            }
+            SExp::Pair(pair) => {
+                parents.insert(pair.first.as_ref(), (key, false));
+                parents.insert(pair.rest.as_ref(), (key, true));
+                stack.push((node, true));
+                stack.push((pair.rest.as_ref(), false));
+                stack.push((pair.first.as_ref(), false));
+            }
+        }
+    }
+    info
+}
+
+fn atom_len(atom: &[u8]) -> u64 {
+    match atom.len() {
+        0 => 1,
+        1 if atom[0] <= MAX_SINGLE_BYTE => 1,
+        len if len < 0x40 => 1 + len as u64,
+        len if len < 0x2000 => 2 + len as u64,
+        len if len < 0x10_0000 => 3 + len as u64,
+        len if len < 0x800_0000 => 4 + len as u64,
+        len => 5 + len as u64,
+    }
+}
+
+// Builds the path atom for a sequence of moves, first move in the lowest bit
+fn path_atom(bits: &[bool]) -> Vec<u8> {
+    let mut path = vec![0u8; (bits.len() + 8) / 8];
+    let last = path.len() - 1;
+    for (index, bit) in bits.iter().chain([true].iter()).enumerate() {
+        if *bit {
+            path[last - index / 8] |= 1 << (index % 8);
+        }
+    }
+    path
+}
+
+enum SerializerOp<'a> {
+    Exp(&'a SExp),
+    Cons(&'a SExp),
+}
+
+// Serializes with back references, a subtree identical to one already written
+// is replaced by a path to it whenever the path is shorter
+pub fn sexp_to_bytes_backrefs(sexp: &SExp) -> std::io::Result<Vec<u8>> {
+    let info = node_info(sexp);
+    let mut buffer = Cursor::new(Vec::new());
+    let mut ops = vec![SerializerOp::Exp(sexp)];
+    // mirrors the parser's value stack, the index of each value is kept so
+    // paths into it can be computed
+    let mut stack: Vec<*const SExp> = vec![];
+    let mut on_stack: HashMap<*const SExp, usize> = HashMap::new();
+    let mut seen: HashMap<&[u8], Vec<*const SExp>> = HashMap::new();
+    while let Some(op) = ops.pop() {
+        match op {
+            SerializerOp::Exp(node) => {
+                let key = node as *const SExp;
+                let node_info = &info[&key];
+                // a back reference is at least two bytes long
+                let back_reference = if node_info.serialized_len > 2 {
+                    seen.get(node_info.tree_hash.as_slice())
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|candidate| {
+                            path_to(
+                                *candidate,
+                                &info,
+                                &stack,
+                                &on_stack,
+                                node_info.serialized_len,
+                            )
+                        })
+                        .min_by_key(|path| path.len())
+                        .filter(|path| 1 + atom_len(path) < node_info.serialized_len)
+                } else {
+                    None
+                };
+                if let Some(path) = back_reference {
+                    buffer.write_all(&[BACK_REFERENCE])?;
+                    write_atom(&mut buffer, &path)?;
+                } else {
+                    match node {
+                        SExp::Atom(atom) => write_atom(&mut buffer, &atom.data)?,
+                        SExp::Pair(pair) => {
+                            buffer.write_all(&[CONS_BOX_MARKER])?;
+                            ops.push(SerializerOp::Cons(node));
+                            ops.push(SerializerOp::Exp(pair.rest.as_ref()));
+                            ops.push(SerializerOp::Exp(pair.first.as_ref()));
+                            continue;
+                        }
+                    }
+                }
+                on_stack.insert(key, stack.len());
+                stack.push(key);
+                seen.entry(node_info.tree_hash.as_slice())
+                    .or_default()
+                    .push(key);
+            }
+            SerializerOp::Cons(node) => {
+                for _ in 0..2 {
+                    let value = stack.pop().ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, "bad serializer state")
+                    })?;
+                    on_stack.remove(&value);
+                }
+                let key = node as *const SExp;
+                on_stack.insert(key, stack.len());
+                stack.push(key);
+                seen.entry(info[&key].tree_hash.as_slice())
+                    .or_default()
+                    .push(key);
+            }
+        }
+    }
+    Ok(buffer.into_inner())
+}
+
+// Path from the top of the parse stack to an already written node, None if it
+// is too long to be worth a back reference
+fn path_to(
+    node: *const SExp,
+    info: &HashMap<*const SExp, NodeInfo>,
+    stack: &[*const SExp],
+    on_stack: &HashMap<*const SExp, usize>,
+    max_len: u64,
+) -> Option<Vec<u8>> {
+    let max_bits = max_len as usize * 8;
+    let mut inner = vec![];
+    let mut current = node;
+    while !on_stack.contains_key(&current) {
+        let (parent, is_rest) = info[&current].parent?;
+        inner.push(is_rest);
+        if inner.len() > max_bits {
+            return None;
+        }
+        current = parent;
+    }
+    let depth = stack.len() - 1 - on_stack[&current];
+    if depth + 1 + inner.len() > max_bits {
+        return None;
+    }
+    let mut bits = vec![true; depth];
+    bits.push(false);
+    bits.extend(inner.iter().rev());
+    Some(path_atom(&bits))
+}
+
+fn write_atom(f: &mut dyn Write, atom: &[u8]) -> Result<(), Error> {
+    if atom.is_empty() {
+        f.write_all(&[0x80_u8])
+    } else if atom.len() == 1 && (atom[0] <= MAX_SINGLE_BYTE) {
+        f.write_all(&[atom[0]])
+    } else {
+        encode_size(f, atom.len() as u64)?;
+        f.write_all(atom)
+    }
+}
+
 fn encode_size(f: &mut dyn Write, size: u64) -> Result<(), Error> {
     if size < 0x40 {
         f.write_all(&[(0x80 | size) as u8])?;
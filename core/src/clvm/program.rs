@@ -1,11 +1,15 @@
 use crate::blockchain::sized_bytes::*;
 use crate::clvm::curry_utils::curry;
 use crate::clvm::dialect::ChiaDialect;
-use crate::clvm::parser::{sexp_from_bytes, sexp_to_bytes};
-use crate::clvm::run_program::run_program;
+use crate::clvm::disassemble::{disassemble, disassemble_pretty};
+use crate::clvm::parser::{
+    sexp_from_bytes, sexp_from_bytes_backrefs, sexp_to_bytes, sexp_to_bytes_backrefs,
+};
+use crate::clvm::run_program::{run_program, run_program_with_tracer};
 use crate::clvm::sexp::{AtomBuf, IntoSExp};
 use crate::clvm::sexp::{SExp, NULL as SNULL};
-use crate::clvm::utils::{tree_hash, MEMPOOL_MODE};
+use crate::clvm::trace::ExecutionTrace;
+use crate::clvm::utils::{tree_hash, ENABLE_BLS_OPS, ENABLE_SECP_OPS, MEMPOOL_MODE};
 use dg_xch_macros::ChiaSerial;
 use hex::encode;
 use num_bigint::BigInt;
@@ -56,6 +60,16 @@ impl Program {
             }
         }
     }
+    // Parses a program that may use back references, the stored serialization
+    // is the classic one
+    pub fn from_bytes_backrefs(bytes: &[u8]) -> Result<Self, Error> {
+        let sexp = sexp_from_bytes_backrefs(bytes)?;
+        let serialized = sexp_to_bytes(&sexp)?;
+        Ok(Program { serialized, sexp })
+    }
+    pub fn to_bytes_backrefs(&self) -> Result<Vec<u8>, Error> {
+        sexp_to_bytes_backrefs(&self.sexp)
+    }
     pub fn null() -> Self {
         let serial = match sexp_to_bytes(&SNULL) {
             Ok(bytes) => bytes,
@@ -131,6 +145,14 @@ impl Program {
         Ok(curry(self, args))
     }
 
+    pub fn disassemble(&self) -> String {
+        disassemble(&self.sexp)
+    }
+
+    pub fn disassemble_pretty(&self, indent: usize) -> String {
+        disassemble_pretty(&self.sexp, indent)
+    }
+
     pub fn uncurry(&self) -> Result<(Program, Program), Error> {
         fn inner_match(o: SExp, expected: &[u8]) -> Result<(), Error> {
             if o.atom()? != expected {
@@ -290,6 +312,15 @@ impl Program {
         let sexp = sexp_from_bytes(&serialized)?;
         Ok((cost, Program { serialized, sexp }))
     }
+
+    pub fn run_with_trace(
+        &self,
+        max_cost: u64,
+        flags: u32,
+        args: &Program,
+    ) -> (Result<(u64, Program), Error>, ExecutionTrace) {
+        run_with_trace(&self.serialized, max_cost, flags, args)
+    }
 }
 
 impl TryFrom<Vec<u8>> for Program {
@@ -472,6 +503,20 @@ impl SerializedProgram {
             })?,
         })
     }
+    // Expands a program that may use back references, such as a block
+    // generator, into the classic serialization
+    pub fn from_bytes_backrefs(bytes: &[u8]) -> Result<SerializedProgram, Error> {
+        Ok(SerializedProgram {
+            buffer: sexp_to_bytes(&sexp_from_bytes_backrefs(bytes)?)?,
+        })
+    }
+    pub fn to_bytes_backrefs(&self) -> Result<Vec<u8>, Error> {
+        sexp_to_bytes_backrefs(&sexp_from_bytes_backrefs(&self.buffer)?)
+    }
+    // Like `to_program` but also accepts a buffer using back references
+    pub fn to_program_backrefs(&self) -> Result<Program, Error> {
+        Program::from_bytes_backrefs(&self.buffer)
+    }
     //pub fn uncurry(&self) -> (SerializedProgram, SerializedProgram) {}
     pub fn to_bytes(&self) -> Vec<u8> {
         self.buffer.clone()
@@ -486,13 +531,21 @@ impl SerializedProgram {
     }
 
     pub fn run_with_cost(&self, max_cost: u64, args: &Program) -> Result<(u64, Program), Error> {
-        self.run(max_cost, 0, args)
+        self.run(max_cost, ENABLE_BLS_OPS | ENABLE_SECP_OPS, args)
     }
 
     pub fn to_program(&self) -> Program {
         Program::new(self.buffer.clone())
     }
 
+    pub fn disassemble(&self) -> Result<String, Error> {
+        Ok(disassemble(&sexp_from_bytes(&self.buffer)?))
+    }
+
+    pub fn disassemble_pretty(&self, indent: usize) -> Result<String, Error> {
+        Ok(disassemble_pretty(&sexp_from_bytes(&self.buffer)?, indent))
+    }
+
     pub fn run(&self, max_cost: u64, flags: u32, args: &Program) -> Result<(u64, Program), Error> {
         let program = sexp_from_bytes(&self.buffer)?;
         let args = sexp_from_bytes(&args.serialized)?;
@@ -507,6 +560,50 @@ impl SerializedProgram {
         let sexp = sexp_from_bytes(&serialized)?;
         Ok((cost, Program { serialized, sexp }))
     }
+
+    pub fn run_with_trace(
+        &self,
+        max_cost: u64,
+        flags: u32,
+        args: &Program,
+    ) -> (Result<(u64, Program), Error>, ExecutionTrace) {
+        run_with_trace(&self.buffer, max_cost, flags, args)
+    }
+}
+
+// Runs the program recording every operator call. The trace is returned even
+// when the run fails so the failing operator can be inspected
+fn run_with_trace(
+    program: &[u8],
+    max_cost: u64,
+    flags: u32,
+    args: &Program,
+) -> (Result<(u64, Program), Error>, ExecutionTrace) {
+    let mut trace = ExecutionTrace::default();
+    let result = run_traced(program, max_cost, flags, args, &mut trace);
+    if let Ok((cost, _)) = &result {
+        trace.total_cost = *cost;
+    }
+    (result, trace)
+}
+
+fn run_traced(
+    program: &[u8],
+    max_cost: u64,
+    flags: u32,
+    args: &Program,
+    trace: &mut ExecutionTrace,
+) -> Result<(u64, Program), Error> {
+    let program = sexp_from_bytes(program)?;
+    let args = sexp_from_bytes(&args.serialized)?;
+    let dialect = ChiaDialect::new(flags);
+    let (cost, result) =
+        run_program_with_tracer(dialect, &program, &args, max_cost, None, &mut |op| {
+            trace.record(op)
+        })?;
+    let serialized = sexp_to_bytes(&result)?;
+    let sexp = sexp_from_bytes(&serialized)?;
+    Ok((cost, Program { serialized, sexp }))
 }
 impl TryFrom<String> for SerializedProgram {
     type Error = Error;
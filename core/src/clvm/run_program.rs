@@ -1,5 +1,6 @@
 use crate::clvm::dialect::Dialect;
 use crate::clvm::sexp::{PairBuf, SExp, NULL};
+use crate::clvm::trace::OpTrace;
 use crate::clvm::utils::ptr_from_number;
 use num_bigint::BigInt;
 use std::io::Error;
@@ -21,6 +22,7 @@ const TRAVERSE_COST_PER_BIT: u64 = 4;
 
 pub type PreEval = Box<dyn Fn(&SExp, &SExp) -> Result<Option<Box<PostEval>>, Error>>;
 pub type PostEval = dyn Fn(Option<&SExp>);
+pub type OpTracer<'t> = &'t mut dyn FnMut(&OpTrace);
 
 #[repr(u8)]
 enum Operation {
@@ -33,15 +35,16 @@ enum Operation {
 
 // `run_program` has two stacks: the operand stack (of `Node` objects) and the
 // operator stack (of Operation)
-struct RunProgramContext<D> {
+struct RunProgramContext<'t, D> {
     dialect: D,
     pre_eval: Option<PreEval>,
+    tracer: Option<OpTracer<'t>>,
     posteval_stack: Vec<Box<PostEval>>,
     val_stack: Vec<SExp>,
     op_stack: Vec<Operation>,
 }
 
-impl<D: Dialect> RunProgramContext<D> {
+impl<D: Dialect> RunProgramContext<'_, D> {
     pub fn pop(&mut self) -> Result<SExp, Error> {
         match self.val_stack.pop() {
             None => Err(Error::new(
@@ -139,11 +142,12 @@ fn augment_cost_errors(r: Result<u64, Error>, max_cost: &SExp) -> Result<u64, Er
     }
 }
 
-impl<D: Dialect> RunProgramContext<D> {
-    fn new(dialect: D, pre_eval: Option<PreEval>) -> Self {
+impl<'t, D: Dialect> RunProgramContext<'t, D> {
+    fn new(dialect: D, pre_eval: Option<PreEval>, tracer: Option<OpTracer<'t>>) -> Self {
         RunProgramContext {
             dialect,
             pre_eval,
+            tracer,
             posteval_stack: Vec::new(),
             val_stack: Vec::new(),
             op_stack: Vec::new(),
@@ -162,7 +166,7 @@ impl<D: Dialect> RunProgramContext<D> {
     }
 }
 
-impl<D: Dialect> RunProgramContext<D> {
+impl<D: Dialect> RunProgramContext<'_, D> {
     fn eval_op_atom(
         &mut self,
         operator_node: SExp,
@@ -299,6 +303,8 @@ impl<D: Dialect> RunProgramContext<D> {
                     format!("apply requires exactly 2 parameters: {:?}", operand_list),
                 ))
             }
+        } else if self.tracer.is_some() {
+            self.traced_op(operator, operand_list, max_cost)
         } else {
             let (cost, result) = self.dialect.op(operator, operand_list, max_cost)?;
             self.push(result);
@@ -306,6 +312,38 @@ impl<D: Dialect> RunProgramContext<D> {
         }
     }
 
+    fn traced_op(
+        &mut self,
+        operator: SExp,
+        operand_list: SExp,
+        max_cost: u64,
+    ) -> Result<u64, Error> {
+        let depth = self
+            .op_stack
+            .iter()
+            .filter(|op| matches!(op, Operation::Apply))
+            .count();
+        let outcome = self
+            .dialect
+            .op(operator.clone(), operand_list.clone(), max_cost);
+        let trace = OpTrace {
+            operator,
+            args: operand_list,
+            result: match &outcome {
+                Ok((_, result)) => Ok(result.clone()),
+                Err(e) => Err(e.to_string()),
+            },
+            cost: outcome.as_ref().map(|(cost, _)| *cost).unwrap_or_default(),
+            depth,
+        };
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer(&trace);
+        }
+        let (cost, result) = outcome?;
+        self.push(result);
+        Ok(cost)
+    }
+
     pub fn run_program(
         &mut self,
         program: &SExp,
@@ -358,6 +396,20 @@ pub fn run_program<'a, D: Dialect>(
     max_cost: u64,
     pre_eval: Option<PreEval>,
 ) -> Result<(u64, SExp), Error> {
-    let mut rpc = RunProgramContext::new(dialect, pre_eval);
+    let mut rpc = RunProgramContext::new(dialect, pre_eval, None);
+    rpc.run_program(program, args, max_cost)
+}
+
+// Same as `run_program` but every operator call is passed to `tracer`,
+// including the one that fails the run
+pub fn run_program_with_tracer<'a, D: Dialect>(
+    dialect: D,
+    program: &'a SExp,
+    args: &'a SExp,
+    max_cost: u64,
+    pre_eval: Option<PreEval>,
+    tracer: OpTracer<'a>,
+) -> Result<(u64, SExp), Error> {
+    let mut rpc = RunProgramContext::new(dialect, pre_eval, Some(tracer));
     rpc.run_program(program, args, max_cost)
 }
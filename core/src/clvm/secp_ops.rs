@@ -0,0 +1,61 @@
+use k256::ecdsa::{Signature as K1Signature, VerifyingKey as K1VerifyingKey};
+use p256::ecdsa::signature::hazmat::PrehashVerifier;
+use p256::ecdsa::{Signature as R1Signature, VerifyingKey as R1VerifyingKey};
+use std::io::{Error, ErrorKind};
+
+use crate::clvm::sexp::{SExp, NULL};
+use crate::clvm::utils::{atom, check_arg_count, check_cost};
+
+const SECP256R1_VERIFY_COST: u64 = 1850000;
+const SECP256K1_VERIFY_COST: u64 = 1300000;
+
+type SecpArgs<'a> = (&'a [u8], &'a [u8], &'a [u8]);
+
+// both verify operators take (pubkey msg_digest signature) where the pubkey is
+// a 33 byte compressed SEC1 point, the digest is 32 bytes and the signature is 64 bytes
+fn secp_args<'a>(args: &'a SExp, op_name: &str) -> Result<SecpArgs<'a>, Error> {
+    check_arg_count(args, 3, op_name)?;
+    let pubkey = atom(args.first()?, op_name)?;
+    let msg = atom(args.rest()?.first()?, op_name)?;
+    let sig = atom(args.rest()?.rest()?.first()?, op_name)?;
+    if msg.len() != 32 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("{op_name} message digest is not 32 bytes"),
+        ));
+    }
+    Ok((pubkey, msg, sig))
+}
+
+fn invalid(op_name: &str, what: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("{op_name} {what} is not valid"),
+    )
+}
+
+pub fn op_secp256k1_verify(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    let cost = SECP256K1_VERIFY_COST;
+    check_cost(cost, max_cost)?;
+    let (pubkey, msg, sig) = secp_args(&args, "secp256k1_verify")?;
+    let verifier = K1VerifyingKey::from_sec1_bytes(pubkey)
+        .map_err(|_| invalid("secp256k1_verify", "pubkey"))?;
+    let sig = K1Signature::from_slice(sig).map_err(|_| invalid("secp256k1_verify", "sig"))?;
+    verifier
+        .verify_prehash(msg, &sig)
+        .map_err(|_| Error::new(ErrorKind::InvalidData, "secp256k1_verify failed"))?;
+    Ok((cost, NULL.clone()))
+}
+
+pub fn op_secp256r1_verify(args: SExp, max_cost: u64) -> Result<(u64, SExp), Error> {
+    let cost = SECP256R1_VERIFY_COST;
+    check_cost(cost, max_cost)?;
+    let (pubkey, msg, sig) = secp_args(&args, "secp256r1_verify")?;
+    let verifier = R1VerifyingKey::from_sec1_bytes(pubkey)
+        .map_err(|_| invalid("secp256r1_verify", "pubkey"))?;
+    let sig = R1Signature::from_slice(sig).map_err(|_| invalid("secp256r1_verify", "sig"))?;
+    verifier
+        .verify_prehash(msg, &sig)
+        .map_err(|_| Error::new(ErrorKind::InvalidData, "secp256r1_verify failed"))?;
+    Ok((cost, NULL.clone()))
+}
@@ -0,0 +1,87 @@
+use crate::clvm::assemble::keywords::KEYWORD_FROM_ATOM;
+use crate::clvm::disassemble::{disassemble, disassemble_atom};
+use crate::clvm::sexp::SExp;
+use std::collections::HashMap;
+
+// A single operator invocation seen by the interpreter. Quote and apply are
+// handled by the interpreter itself and are not reported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpTrace {
+    pub operator: SExp,
+    pub args: SExp,
+    pub result: Result<SExp, String>,
+    pub cost: u64,
+    // number of enclosing operator calls still waiting on their arguments
+    pub depth: usize,
+}
+
+impl OpTrace {
+    pub fn operator_name(&self) -> String {
+        match &self.operator {
+            SExp::Atom(a) => KEYWORD_FROM_ATOM
+                .get(&a.data)
+                .cloned()
+                .unwrap_or_else(|| disassemble_atom(&a.data)),
+            SExp::Pair(_) => disassemble(&self.operator),
+        }
+    }
+
+    pub fn is_err(&self) -> bool {
+        self.result.is_err()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatorCost {
+    pub operator: String,
+    pub calls: u64,
+    pub cost: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionTrace {
+    pub ops: Vec<OpTrace>,
+    // total cost of the run, zero if it failed
+    pub total_cost: u64,
+}
+
+impl ExecutionTrace {
+    pub fn record(&mut self, trace: &OpTrace) {
+        self.ops.push(trace.clone());
+    }
+
+    // The operator that aborted the run, for example a raise
+    pub fn failure(&self) -> Option<&OpTrace> {
+        self.ops.iter().rev().find(|op| op.is_err())
+    }
+
+    pub fn operator_cost(&self) -> u64 {
+        self.ops.iter().map(|op| op.cost).sum()
+    }
+
+    // Cost not attributed to an operator: quote, apply, path lookups and the
+    // base cost charged for each operator call
+    pub fn interpreter_cost(&self) -> u64 {
+        self.total_cost.saturating_sub(self.operator_cost())
+    }
+
+    // Cost and call count per operator, most expensive first
+    pub fn cost_breakdown(&self) -> Vec<OperatorCost> {
+        let mut by_operator: HashMap<String, OperatorCost> = HashMap::new();
+        for op in &self.ops {
+            let name = op.operator_name();
+            let entry = by_operator
+                .entry(name.clone())
+                .or_insert_with(|| OperatorCost {
+                    operator: name,
+                    calls: 0,
+                    cost: 0,
+                });
+            entry.calls += 1;
+            entry.cost += op.cost;
+        }
+        let mut breakdown: Vec<OperatorCost> = by_operator.into_values().collect();
+        breakdown.sort_by(|a, b| b.cost.cmp(&a.cost).then(a.operator.cmp(&b.operator)));
+        breakdown
+    }
+}
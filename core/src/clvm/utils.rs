@@ -10,12 +10,19 @@ use std::io::{Error, ErrorKind};
 
 pub const NO_NEG_DIV: u32 = 0x0001;
 pub const NO_UNKNOWN_OPS: u32 = 0x0002;
+pub const ENABLE_BLS_OPS: u32 = 0x0004;
+pub const ENABLE_SECP_OPS: u32 = 0x0008;
 pub const COND_CANON_INTS: u32 = 0x010000;
 pub const NO_UNKNOWN_CONDS: u32 = 0x20000;
 pub const COND_ARGS_NIL: u32 = 0x40000;
 pub const STRICT_ARGS_COUNT: u32 = 0x80000;
-pub const MEMPOOL_MODE: u32 =
-    NO_NEG_DIV | COND_CANON_INTS | NO_UNKNOWN_CONDS | NO_UNKNOWN_OPS | COND_ARGS_NIL;
+pub const MEMPOOL_MODE: u32 = NO_NEG_DIV
+    | COND_CANON_INTS
+    | NO_UNKNOWN_CONDS
+    | NO_UNKNOWN_OPS
+    | COND_ARGS_NIL
+    | ENABLE_BLS_OPS
+    | ENABLE_SECP_OPS;
 pub const INFINITE_COST: u64 = 0x7FFFFFFFFFFFFFFF;
 
 pub fn tree_hash(sexp: &SExp) -> Vec<u8> {
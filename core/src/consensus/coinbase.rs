@@ -1,4 +1,5 @@
 use crate::blockchain::coin::Coin;
+use crate::blockchain::coin_record::CoinRecord;
 use crate::blockchain::sized_bytes::Bytes32;
 
 pub fn pool_parent_id(block_height: u32, genesis_challenge: &Bytes32) -> Bytes32 {
@@ -15,6 +16,20 @@ pub fn farmer_parent_id(block_height: u32, genesis_challenge: &Bytes32) -> Bytes
     Bytes32::from_sized_bytes(buf)
 }
 
+// Pool rewards can be confirmed up to 128 blocks after the block that farmed them,
+// returns the farmed height or None if the coin is not a pool reward
+pub fn get_farmed_height(
+    reward_coin_record: &CoinRecord,
+    genesis_challenge: &Bytes32,
+) -> Option<u32> {
+    let confirmed = reward_coin_record.confirmed_block_index;
+    (confirmed.saturating_sub(127)..=confirmed)
+        .rev()
+        .find(|height| {
+            pool_parent_id(*height, genesis_challenge) == reward_coin_record.coin.parent_coin_info
+        })
+}
+
 pub fn create_pool_coin(
     block_height: u32,
     puzzle_hash: &Bytes32,
@@ -1,7 +1,11 @@
 use crate::blockchain::coin_record::CoinRecord;
 use crate::blockchain::sized_bytes::{Bytes32, Bytes48, SizedBytes};
 use crate::clvm::program::Program;
-use crate::pool::{PoolState, DELAY_PUZZLEHASH_IDENTIFIER, DELAY_TIME_IDENTIFIER};
+use crate::clvm::sexp::{AtomBuf, IntoSExp, SExp};
+use crate::pool::{
+    PoolState, DELAY_PUZZLEHASH_IDENTIFIER, DELAY_TIME_IDENTIFIER, POOL_STATE_IDENTIFIER,
+};
+use dg_xch_serialize::ChiaSerialize;
 use hex::encode;
 use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
@@ -323,4 +327,20 @@ impl PlotNftExtraData {
             ),
         })
     }
+    pub fn to_program(&self) -> Program {
+        Program::to(vec![
+            (
+                POOL_STATE_IDENTIFIER.to_string().to_sexp(),
+                SExp::Atom(AtomBuf::new(self.pool_state.to_bytes())),
+            ),
+            (
+                DELAY_TIME_IDENTIFIER.to_string().to_sexp(),
+                self.delay_time.to_sexp(),
+            ),
+            (
+                DELAY_PUZZLEHASH_IDENTIFIER.to_string().to_sexp(),
+                self.delay_puzzle_hash.to_sexp(),
+            ),
+        ])
+    }
 }
@@ -1,8 +1,8 @@
-use crate::plots::plot_reader::read_plot_header_async;
+use crate::plots::plot_reader::{parse_plot_header, read_plot_header_async};
 use crate::utils::open_read_only_async;
 use dg_xch_core::plots::{PlotFile, PlotHeader};
 use std::fmt::{Display, Formatter};
-use std::io::{Error, SeekFrom};
+use std::io::{Cursor, Error, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt};
@@ -29,6 +29,47 @@ impl DiskPlot<tokio::fs::File> {
         })
     }
 }
+
+// Plot contents loaded into memory, cheap to clone so several readers can
+// share one copy of the plot
+#[derive(Debug, Clone)]
+pub struct PlotBytes(Arc<Vec<u8>>);
+impl From<Vec<u8>> for PlotBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(Arc::new(bytes))
+    }
+}
+impl AsRef<[u8]> for PlotBytes {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+impl DiskPlot<Cursor<PlotBytes>> {
+    pub async fn new_in_ram(filename: &Path) -> Result<Self, Error> {
+        let bytes = PlotBytes::from(tokio::fs::read(filename).await?);
+        Self::from_bytes(filename, bytes)
+    }
+    pub fn from_bytes(filename: &Path, bytes: PlotBytes) -> Result<Self, Error> {
+        let _header = parse_plot_header(bytes.as_ref())?;
+        let _plot_size = bytes.as_ref().len() as u64;
+        Ok(Self {
+            file: Arc::new(Mutex::new(Cursor::new(bytes))),
+            filename: Arc::new(filename.to_path_buf()),
+            _header,
+            _plot_size,
+        })
+    }
+    // Another handle on the same bytes with its own cursor, the plot is not copied
+    pub async fn share(&self) -> Self {
+        let bytes = self.file.lock().await.get_ref().clone();
+        Self {
+            file: Arc::new(Mutex::new(Cursor::new(bytes))),
+            filename: self.filename.clone(),
+            _header: self._header.clone(),
+            _plot_size: self._plot_size,
+        }
+    }
+}
 impl<F: AsyncSeek + AsyncRead> Display for DiskPlot<F> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(
@@ -816,7 +816,7 @@ impl<
             8,
         )
     }
-    async fn read_lp_park_components(
+    pub(crate) async fn read_lp_park_components(
         &self,
         table: &PlotTable,
         park_index: u64,
@@ -854,7 +854,7 @@ impl<
         let mut encoded_deltas_buf: [u8; 2] = [0u8; 2];
         file_lock.read_exact(&mut encoded_deltas_buf).await?;
         let mut encoded_deltas_size = u16::from_le_bytes(encoded_deltas_buf);
-        if !(encoded_deltas_size & 0x8000) > 0 && encoded_deltas_size as u32 > max_deltas_size {
+        if encoded_deltas_size & 0x8000 == 0 && encoded_deltas_size as u32 > max_deltas_size {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
                 format!("Invalid size for deltas: {}", encoded_deltas_size),
@@ -949,10 +949,20 @@ pub fn read_plot_header(file: &mut std::fs::File) -> Result<PlotHeader, Error> {
     use std::io::Read;
     let mut full_buffer = [0; 320];
     file.read_exact(&mut full_buffer)?;
+    parse_plot_header(&full_buffer)
+}
+
+pub fn parse_plot_header(full_buffer: &[u8]) -> Result<PlotHeader, Error> {
+    if full_buffer.len() < 320 {
+        return Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            format!("Plot header needs 320 bytes, got {}", full_buffer.len()),
+        ));
+    }
     if HEADER_V2_MAGIC == full_buffer[0..4] {
-        Ok(PlotHeader::V2(parse_v2(&full_buffer)?))
+        Ok(PlotHeader::V2(parse_v2(full_buffer)?))
     } else if HEADER_MAGIC == full_buffer[0..19] {
-        Ok(PlotHeader::V1(parse_v1(&full_buffer)?))
+        Ok(PlotHeader::V1(parse_v1(full_buffer)?))
     } else {
         Err(Error::new(
             ErrorKind::InvalidInput,
@@ -1028,16 +1038,7 @@ fn parse_v2(full_buffer: &[u8]) -> Result<PlotHeaderV2, Error> {
 pub async fn read_plot_header_async(file: &mut tokio::fs::File) -> Result<PlotHeader, Error> {
     let mut full_buffer = [0; 320];
     file.read_exact(&mut full_buffer).await?;
-    if HEADER_V2_MAGIC == full_buffer[0..4] {
-        Ok(PlotHeader::V2(parse_v2(&full_buffer)?))
-    } else if HEADER_MAGIC == full_buffer[0..19] {
-        Ok(PlotHeader::V1(parse_v1(&full_buffer)?))
-    } else {
-        Err(Error::new(
-            ErrorKind::InvalidInput,
-            "Invalid plot header magic",
-        ))
-    }
+    parse_plot_header(&full_buffer)
 }
 
 pub async fn read_plot_file_header_async(
@@ -1,4 +1,5 @@
 use crate::constants::{K_CHECKPOINT1INTERVAL, K_ENTRIES_PER_PARK, K_EXTRA_BITS};
+use crate::entry_sizes::EntrySizes;
 use crate::plots::decompressor::DecompressorPool;
 use crate::plots::disk_plot::DiskPlot;
 use crate::plots::fx_generator::{forward_prop_f1_to_f7, get_proof_f1_and_meta};
@@ -13,13 +14,15 @@ use log::{debug, error, info, warn};
 use std::cmp::{max, min};
 use std::io::{Error, ErrorKind};
 use std::mem::size_of;
+use std::ops::Range;
 use std::path::Path;
-use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::Instant;
 use tokio::io::{AsyncRead, AsyncSeek};
 
+pub type ValidationProgressCallback = Arc<dyn Fn(&ValidationProgress) + Send + Sync>;
+
 pub struct ValidatePlotOptions {
     pub in_ram: bool,
     pub unpacked: bool,
@@ -27,6 +30,9 @@ pub struct ValidatePlotOptions {
     pub start_offset: f64,
     pub use_cuda: bool,
     pub f7: i64,
+    // f7 values checked in each C3 park, 0 checks every entry
+    pub samples_per_park: usize,
+    pub progress: Option<ValidationProgressCallback>,
 }
 impl Default for ValidatePlotOptions {
     fn default() -> Self {
@@ -37,175 +43,446 @@ impl Default for ValidatePlotOptions {
             start_offset: 0.0,
             use_cuda: false,
             f7: -1,
+            samples_per_park: 0,
+            progress: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadPark {
+    pub table: PlotTable,
+    pub park_index: u64,
+    // offset of the start of the park in the plot file
+    pub offset: u64,
+    // first failing f7 index when proofs in a C3 park failed
+    pub f7_index: Option<u64>,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlotValidationReport {
+    pub parks_checked: u64,
+    pub proofs_checked: u64,
+    pub proofs_failed: u64,
+    pub bad_parks: Vec<BadPark>,
+}
+impl PlotValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.proofs_failed == 0 && self.bad_parks.is_empty()
+    }
+    pub fn merge(&mut self, other: PlotValidationReport) {
+        self.parks_checked += other.parks_checked;
+        self.proofs_checked += other.proofs_checked;
+        self.proofs_failed += other.proofs_failed;
+        self.bad_parks.extend(other.bad_parks);
+        self.bad_parks.sort_by_key(|p| p.offset);
+    }
+}
+
+// Progress of a single validator thread through one table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationProgress {
+    pub thread: usize,
+    pub table: PlotTable,
+    pub parks_done: u64,
+    pub park_count: u64,
+    pub proofs_failed: u64,
+}
+
+struct ValidatorSettings {
+    index: usize,
+    thread_count: usize,
+    start_offset: f64,
+    samples_per_park: usize,
+    progress: Option<ValidationProgressCallback>,
+}
+impl ValidatorSettings {
+    fn report_progress(&self, table: PlotTable, parks_done: u64, park_count: u64, failed: u64) {
+        if let Some(progress) = &self.progress {
+            progress(&ValidationProgress {
+                thread: self.index,
+                table,
+                parks_done,
+                park_count,
+                proofs_failed: failed,
+            });
         }
     }
 }
 
-pub async fn validate_plot(path: &Path, options: ValidatePlotOptions) -> Result<(), Error> {
+pub async fn validate_plot(
+    path: &Path,
+    options: ValidatePlotOptions,
+) -> Result<PlotValidationReport, Error> {
+    if options.use_cuda {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "CUDA plot validation is not supported",
+        ));
+    }
     let count = thread::available_parallelism()?.get();
     let thread_count = max(min(options.thread_count, count), 1);
-    let mut plot_files = vec![];
-    for _ in 0..thread_count {
-        plot_files.push(DiskPlot::new(path).await?);
-    }
     info!("Validating Plot: {:?}", path);
     info!("Mode: {}", if options.in_ram { "Ram" } else { "Disk" });
-    info!("K Size: {}", plot_files[0].k());
-    info!("Unpacked: {}", options.unpacked);
-    let plot_c3park_count =
-        plot_files[0].table_size(&PlotTable::C1) as usize / size_of::<u32>() - 1;
-    info!("Maximum C3 Parks: {}", plot_c3park_count);
-    if options.unpacked {
-        todo!()
+    if options.in_ram {
+        let mut plot_files = vec![DiskPlot::new_in_ram(path).await?];
+        for _ in 1..thread_count {
+            let shared = plot_files[0].share().await;
+            plot_files.push(shared);
+        }
+        validate_plot_files(plot_files, &options).await
     } else {
-        let fail_count = Arc::new(AtomicU64::new(0));
-        let mut tasks = vec![];
-        for (index, plot_file) in plot_files.into_iter().enumerate() {
-            let fail_count = fail_count.clone();
-            tasks.push(tokio::task::spawn(async move {
-                validate_disk(
-                    index,
-                    thread_count,
-                    plot_file,
-                    fail_count,
-                    options.start_offset,
-                )
-                .await
-            }));
+        let mut plot_files = vec![];
+        for _ in 0..thread_count {
+            plot_files.push(DiskPlot::new(path).await?);
         }
-        for results in join_all(&mut tasks).await {
-            match results {
-                Ok(res) => match res {
-                    Ok(_) => {
-                        info!("Validator Thread Finished");
-                    }
-                    Err(e) => {
-                        error!("Error in Validator: {:?}", e);
-                    }
-                },
+        validate_plot_files(plot_files, &options).await
+    }
+}
+
+async fn validate_plot_files<F: AsyncSeek + AsyncRead + Unpin + Send + Sync + 'static>(
+    plot_files: Vec<DiskPlot<F>>,
+    options: &ValidatePlotOptions,
+) -> Result<PlotValidationReport, Error> {
+    let thread_count = plot_files.len();
+    let mut readers = vec![];
+    for plot_file in plot_files {
+        let pool = Arc::new(DecompressorPool::new(1, thread_count as u8));
+        readers.push(PlotReader::new(plot_file, Some(pool.clone()), Some(pool)).await?);
+    }
+    info!("K Size: {}", readers[0].plot_file().k());
+    info!("Unpacked: {}", options.unpacked);
+    info!("Maximum C3 Parks: {}", readers[0].get_c3_park_count());
+    if options.f7 >= 0 {
+        return validate_f7(&readers[0], options.f7 as u64).await;
+    }
+    let mut tasks = vec![];
+    for (index, reader) in readers.into_iter().enumerate() {
+        let settings = ValidatorSettings {
+            index,
+            thread_count,
+            start_offset: options.start_offset,
+            samples_per_park: options.samples_per_park,
+            progress: options.progress.clone(),
+        };
+        let unpacked = options.unpacked;
+        tasks.push(tokio::task::spawn(async move {
+            let mut report = PlotValidationReport::default();
+            if unpacked {
+                validate_tables(&reader, &settings, &mut report).await?;
+            }
+            validate_c3_parks(&reader, &settings, &mut report).await?;
+            Ok::<PlotValidationReport, Error>(report)
+        }));
+    }
+    let mut report = PlotValidationReport::default();
+    for results in join_all(&mut tasks).await {
+        match results {
+            Ok(res) => match res {
+                Ok(thread_report) => {
+                    info!("Validator Thread Finished");
+                    report.merge(thread_report);
+                }
                 Err(e) => {
-                    error!("Join Error for Plot Read Thread: {:?}", e);
+                    error!("Error in Validator: {:?}", e);
+                    return Err(e);
                 }
+            },
+            Err(e) => {
+                error!("Join Error for Plot Read Thread: {:?}", e);
+                return Err(Error::new(ErrorKind::Other, e));
             }
         }
     }
-    Ok(())
+    info!(
+        "Plot Validated, {} parks and {} proofs checked, {} proofs failed, {} bad parks",
+        report.parks_checked,
+        report.proofs_checked,
+        report.proofs_failed,
+        report.bad_parks.len()
+    );
+    Ok(report)
 }
 
-async fn validate_disk<F: AsyncSeek + AsyncRead + Unpin>(
+// The parks a validator thread is responsible for, skipping the first
+// start_offset fraction of them when resuming an earlier run
+pub fn thread_park_range(
     index: usize,
     thread_count: usize,
-    plot_file: DiskPlot<F>,
-    fail_counter: Arc<AtomicU64>,
+    park_count: u64,
     start_offset: f64,
-) -> Result<(), Error> {
-    let plot_c3park_count = plot_file.table_size(&PlotTable::C1) as usize / size_of::<u32>() - 1;
-    let mut c3park_count = plot_c3park_count / thread_count;
-    let mut start_c3park = index * c3park_count;
-    let trailing_parks = plot_c3park_count - c3park_count * thread_count;
+) -> Range<u64> {
+    let (index, thread_count) = (index as u64, max(thread_count, 1) as u64);
+    let mut count = park_count / thread_count;
+    let trailing_parks = park_count - count * thread_count;
+    let mut start = index * count + min(trailing_parks, index);
     if index < trailing_parks {
-        c3park_count += 1;
+        count += 1;
     }
-    start_c3park += min(trailing_parks, index);
-    let c3park_end = start_c3park + c3park_count;
+    let end = start + count;
     if start_offset > 0.0f64 {
-        start_c3park += min(c3park_count, (c3park_count as f64 * start_offset) as usize);
-        c3park_count = c3park_end - start_c3park;
+        start += min(count, (count as f64 * start_offset) as u64);
+    }
+    start..end
+}
+
+// Evenly spaced entries of a park to validate, every entry when samples is 0
+pub fn sample_park_entries(entry_count: usize, samples: usize) -> Vec<usize> {
+    if samples == 0 || samples >= entry_count {
+        (0..entry_count).collect()
+    } else {
+        (0..samples).map(|i| i * entry_count / samples).collect()
+    }
+}
+
+// Decodes every park of the stored line point tables and table 7
+async fn validate_tables<F: AsyncSeek + AsyncRead + Unpin>(
+    reader: &PlotReader<F, DiskPlot<F>>,
+    settings: &ValidatorSettings,
+    report: &mut PlotValidationReport,
+) -> Result<(), Error> {
+    let k = *reader.plot_file().k() as u32;
+    let lowest_table = reader.get_lowest_stored_table();
+    for table in [
+        PlotTable::Table1,
+        PlotTable::Table2,
+        PlotTable::Table3,
+        PlotTable::Table4,
+        PlotTable::Table5,
+        PlotTable::Table6,
+        PlotTable::Table7,
+    ] {
+        if table < lowest_table {
+            continue;
+        }
+        let park_size = if table == PlotTable::Table7 {
+            EntrySizes::calculate_park7_size(k) as u64
+        } else {
+            reader.get_park_size_for_table(&table)
+        };
+        let table_address = *reader.plot_file().table_address(&table);
+        let park_count = reader.plot_file().table_size(&table) / park_size;
+        let parks = thread_park_range(
+            settings.index,
+            settings.thread_count,
+            park_count,
+            settings.start_offset,
+        );
+        let (table_start, bad_before) = (Instant::now(), report.bad_parks.len());
+        for (done, park_index) in parks.clone().enumerate() {
+            let result = if table == PlotTable::Table7 {
+                reader.read_p7park(park_index as usize).await
+            } else {
+                reader
+                    .read_lp_park_components(&table, park_index)
+                    .await
+                    .map(|_| ())
+            };
+            if let Err(e) = result {
+                let offset = table_address + park_index * park_size;
+                error!("Failed to read {table:?} park {park_index} at offset {offset}: {e:?}");
+                report.bad_parks.push(BadPark {
+                    table,
+                    park_index,
+                    offset,
+                    f7_index: None,
+                    error: e.to_string(),
+                });
+            }
+            report.parks_checked += 1;
+            settings.report_progress(
+                table,
+                done as u64 + 1,
+                parks.end - parks.start,
+                report.proofs_failed,
+            );
+        }
+        info!(
+            "Index: {} {:?} parks {}..{} validated in {} seconds | Bad Parks: {}",
+            settings.index,
+            table,
+            parks.start,
+            parks.end,
+            table_start.elapsed().as_millis() as f64 / 1000.0,
+            report.bad_parks.len() - bad_before
+        );
     }
+    Ok(())
+}
+
+async fn validate_c3_parks<F: AsyncSeek + AsyncRead + Unpin>(
+    reader: &PlotReader<F, DiskPlot<F>>,
+    settings: &ValidatorSettings,
+    report: &mut PlotValidationReport,
+) -> Result<(), Error> {
+    let k = *reader.plot_file().k() as u32;
+    let c3address = *reader.plot_file().table_address(&PlotTable::C3);
+    let c3park_size = EntrySizes::calculate_c3size(k) as u64;
+    let parks = thread_park_range(
+        settings.index,
+        settings.thread_count,
+        reader.get_c3_park_count(),
+        settings.start_offset,
+    );
+    let c3park_count = parks.end - parks.start;
     info!(
         "Index: {} Park range: {}..{}  Park count: {}",
-        index, start_c3park, c3park_end, c3park_count
+        settings.index, parks.start, parks.end, c3park_count
     );
-    let mut f7_entries;
     let mut fx: [u64; PROOF_X_COUNT] = [0; PROOF_X_COUNT];
     let mut meta: Vec<BitReader> = Vec::with_capacity(PROOF_X_COUNT);
-    let mut cur_park7 = 0usize;
-    let pool = Arc::new(DecompressorPool::new(1, thread_count as u8));
-    let reader = PlotReader::new(plot_file, Some(pool.clone()), Some(pool)).await?;
-    if index == 0 {
-        reader.read_p7entries(0).await?;
-    }
-    let mut total_proofs = 0u128;
-    let mut total_millis = 0u128;
-    for c3_park_index in start_c3park..c3park_end {
+    let mut cur_park7 = usize::MAX;
+    for c3_park_index in parks.clone() {
         let c3_start = Instant::now();
-        f7_entries = reader.read_c3park(c3_park_index as u64).await?;
-        assert!(f7_entries.len() <= K_CHECKPOINT1INTERVAL as usize);
-        let f7idx_base = c3_park_index * K_CHECKPOINT1INTERVAL as usize;
-        let entry_count = f7_entries.len();
-        let mut threshold = 0;
-        info!("Validating c3 Park: {}", c3_park_index);
-        for (index, f7) in f7_entries.iter().enumerate() {
-            if index / entry_count > threshold {
-                info!(
-                    "Progress: {}% ({}/{}), Avg Proof Lookup: {} millis",
-                    index as f64 / entry_count as f64 * 100.0,
-                    index,
-                    entry_count,
-                    if index > 0 {
-                        total_proofs / total_millis
-                    } else {
-                        0
-                    }
-                );
-                threshold += entry_count / 20;
+        let offset = c3address + c3_park_index * c3park_size;
+        let f7_entries = match reader.read_c3park(c3_park_index).await {
+            Ok(entries) if entries.len() <= K_CHECKPOINT1INTERVAL as usize => entries,
+            Ok(entries) => {
+                error!("C3 park {c3_park_index} has {} entries", entries.len());
+                report.bad_parks.push(BadPark {
+                    table: PlotTable::C3,
+                    park_index: c3_park_index,
+                    offset,
+                    f7_index: None,
+                    error: format!("Invalid c3 park entry count: {}", entries.len()),
+                });
+                vec![]
             }
-            let p_start = Instant::now();
-            let f7idx = f7idx_base + index;
-            let p7park_index = f7idx / K_ENTRIES_PER_PARK as usize;
-            if p7park_index != cur_park7 {
-                cur_park7 = p7park_index;
-                reader.read_p7entries(p7park_index).await?;
+            Err(e) => {
+                error!("Failed to read C3 park {c3_park_index} at offset {offset}: {e:?}");
+                report.bad_parks.push(BadPark {
+                    table: PlotTable::C3,
+                    park_index: c3_park_index,
+                    offset,
+                    f7_index: None,
+                    error: e.to_string(),
+                });
+                vec![]
             }
-            let p7local_idx = f7idx - p7park_index * K_ENTRIES_PER_PARK as usize;
-            let t6index = reader.p7_entries.lock().await[p7local_idx];
-            match reader.fetch_proof(t6index).await {
-                Ok(proof) => {
-                    // Now we can validate the proof
-                    match get_f7_from_proof(
-                        *reader.plot_file().k() as u32,
-                        reader.plot_id().to_sized_bytes(),
-                        &proof,
-                        &mut fx,
-                        &mut meta,
-                    ) {
-                        Ok(v_f7) => {
-                            if v_f7 != *f7 {
-                                error!("Failed to validate F7 v_f7({}) != f7({})", v_f7, f7);
-                                fail_counter.fetch_add(1, Ordering::SeqCst);
-                            }
-                        }
-                        Err(err) => {
-                            error!("Error Validating Proof: {:?}", err);
-                            fail_counter.fetch_add(1, Ordering::SeqCst);
-                        }
-                    }
-                }
-                Err(err) => {
-                    error!(
-                        "Park [{}][{index}] proof fetch failed for f7[{}] local({}) = {}: {:?}, {:?}",
-                        c3_park_index, f7idx, index, f7, index, err
-                    );
-                    fail_counter.fetch_add(1, Ordering::SeqCst);
-                }
+        };
+        let f7idx_base = c3_park_index * K_CHECKPOINT1INTERVAL as u64;
+        let samples = sample_park_entries(f7_entries.len(), settings.samples_per_park);
+        let mut park_failures = 0;
+        let mut first_failure = None;
+        for local_index in &samples {
+            let f7idx = f7idx_base + *local_index as u64;
+            let f7 = f7_entries[*local_index];
+            report.proofs_checked += 1;
+            if let Err(e) =
+                validate_f7_index(reader, f7idx, f7, &mut cur_park7, &mut fx, &mut meta).await
+            {
+                error!("Park [{c3_park_index}] proof failed for f7[{f7idx}] = {f7}: {e:?}");
+                park_failures += 1;
+                first_failure.get_or_insert((f7idx, e.to_string()));
             }
-            let p_elapsed = Instant::now().duration_since(p_start).as_millis();
-            total_proofs += 1;
-            total_millis += p_elapsed;
         }
-        let c3_elapsed = Instant::now().duration_since(c3_start).as_millis();
+        if let Some((f7_index, e)) = first_failure {
+            report.proofs_failed += park_failures;
+            report.bad_parks.push(BadPark {
+                table: PlotTable::C3,
+                park_index: c3_park_index,
+                offset,
+                f7_index: Some(f7_index),
+                error: format!("{park_failures}/{} proofs failed: {e}", samples.len()),
+            });
+        }
+        report.parks_checked += 1;
+        let parks_done = c3_park_index - parks.start + 1;
         info!(
             "{}..{} ( {} ) C3 Park Validated in {} seconds | Proofs Failed: {}",
             c3_park_index,
-            c3park_end - 1,
-            (c3_park_index - start_c3park) as f64 / c3park_count as f64 * 100.0,
-            c3_elapsed as f64 / 1000.0,
-            fail_counter.load(Ordering::Relaxed)
+            parks.end - 1,
+            parks_done as f64 / c3park_count as f64 * 100.0,
+            c3_start.elapsed().as_millis() as f64 / 1000.0,
+            report.proofs_failed
+        );
+        settings.report_progress(
+            PlotTable::C3,
+            parks_done,
+            c3park_count,
+            report.proofs_failed,
         );
     }
     Ok(())
 }
 
+// Validates only the proofs stored for a single f7 value
+async fn validate_f7<F: AsyncSeek + AsyncRead + Unpin>(
+    reader: &PlotReader<F, DiskPlot<F>>,
+    f7: u64,
+) -> Result<PlotValidationReport, Error> {
+    let (match_count, p7base_index) = reader.get_p7indices_for_f7(f7).await?;
+    if match_count == 0 {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("Could not find f7 {} in plot.", f7),
+        ));
+    }
+    let k = *reader.plot_file().k() as u32;
+    let c3address = *reader.plot_file().table_address(&PlotTable::C3);
+    let c3park_size = EntrySizes::calculate_c3size(k) as u64;
+    let mut report = PlotValidationReport::default();
+    let mut fx: [u64; PROOF_X_COUNT] = [0; PROOF_X_COUNT];
+    let mut meta: Vec<BitReader> = Vec::with_capacity(PROOF_X_COUNT);
+    let mut cur_park7 = usize::MAX;
+    for f7idx in p7base_index as u64..(p7base_index + match_count) as u64 {
+        report.proofs_checked += 1;
+        if let Err(e) =
+            validate_f7_index(reader, f7idx, f7, &mut cur_park7, &mut fx, &mut meta).await
+        {
+            error!("Proof failed for f7[{f7idx}] = {f7}: {e:?}");
+            let park_index = f7idx / K_CHECKPOINT1INTERVAL as u64;
+            report.proofs_failed += 1;
+            report.bad_parks.push(BadPark {
+                table: PlotTable::C3,
+                park_index,
+                offset: c3address + park_index * c3park_size,
+                f7_index: Some(f7idx),
+                error: e.to_string(),
+            });
+        }
+    }
+    info!(
+        "Validated {} proofs for f7 {f7} | Proofs Failed: {}",
+        report.proofs_checked, report.proofs_failed
+    );
+    Ok(report)
+}
+
+// Fetches the proof stored at an f7 index and checks it hashes back to the f7
+async fn validate_f7_index<F: AsyncSeek + AsyncRead + Unpin>(
+    reader: &PlotReader<F, DiskPlot<F>>,
+    f7idx: u64,
+    f7: u64,
+    cur_park7: &mut usize,
+    fx: &mut [u64],
+    meta: &mut Vec<BitReader>,
+) -> Result<(), Error> {
+    let p7park_index = (f7idx / K_ENTRIES_PER_PARK as u64) as usize;
+    if p7park_index != *cur_park7 {
+        reader.read_p7entries(p7park_index).await?;
+        *cur_park7 = p7park_index;
+    }
+    let p7local_idx = f7idx as usize - p7park_index * K_ENTRIES_PER_PARK as usize;
+    let t6index = reader.p7_entries.lock().await[p7local_idx];
+    let proof = reader.fetch_proof(t6index).await?;
+    let v_f7 = get_f7_from_proof(
+        *reader.plot_file().k() as u32,
+        reader.plot_id().to_sized_bytes(),
+        &proof,
+        fx,
+        meta,
+    )?;
+    if v_f7 != f7 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Failed to validate F7 v_f7({}) != f7({})", v_f7, f7),
+        ));
+    }
+    Ok(())
+}
+
 pub fn uncompress_proof(proof: &[u8], k: usize) -> Vec<u64> {
     let mut index = 0;
     let proof_bits = BitReader::from_bytes_be(proof, proof.len() * 8);
@@ -4,6 +4,7 @@ use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, SizedBytes};
 use dg_xch_core::blockchain::utils::atom_to_int;
 use dg_xch_core::clvm::program::{Program, SerializedProgram};
 use dg_xch_core::clvm::sexp::{AtomBuf, IntoSExp, SExp};
+use dg_xch_core::consensus::coinbase::create_pool_coin;
 use dg_xch_core::plots::PlotNftExtraData;
 use dg_xch_core::pool::PoolState;
 use dg_xch_serialize::ChiaSerialize;
@@ -296,6 +297,95 @@ pub fn create_travel_spend(
     ))
 }
 
+// Builds the spends claiming a pool reward farmed at `height` into the pool: the
+// singleton spend paying `reward_amount` to the pool and the p2_singleton spend of
+// the reward coin. The singleton spend is the next `last_coin_spend` when chaining
+#[allow(clippy::too_many_arguments)]
+pub fn create_absorb_spend(
+    last_coin_spend: &CoinSpend,
+    current_state: &PoolState,
+    launcher_coin: &Coin,
+    height: u32,
+    reward_amount: u64,
+    genesis_challenge: &Bytes32,
+    delay_time: u64,
+    delay_ph: &Bytes32,
+) -> Result<Vec<CoinSpend>, Error> {
+    let launcher_id = launcher_coin.name();
+    let inner_puzzle = pool_state_to_inner_puzzle(
+        current_state,
+        &launcher_id,
+        genesis_challenge,
+        delay_time,
+        delay_ph,
+    )?;
+    let inner_solution = if is_pool_member_inner_puzzle(&inner_puzzle)? {
+        Program::to(vec![reward_amount.to_sexp(), height.to_sexp()])
+    } else if is_pool_waitingroom_inner_puzzle(&inner_puzzle)? {
+        Program::to(vec![0.to_sexp(), reward_amount.to_sexp(), height.to_sexp()])
+    } else {
+        return Err(Error::new(ErrorKind::InvalidInput, "Invalid Inner Puzzle"));
+    };
+    let current_singleton = get_most_recent_singleton_coin_from_coin_spend(last_coin_spend)?
+        .ok_or(Error::new(
+            ErrorKind::InvalidInput,
+            "Failed to find singleton",
+        ))?;
+    let parent_info_list = if current_singleton.parent_coin_info == launcher_id {
+        Program::to(vec![
+            launcher_coin.parent_coin_info.to_sexp(),
+            launcher_coin.amount.to_sexp(),
+        ])
+    } else {
+        let p = last_coin_spend.puzzle_reveal.to_program();
+        let last_coin_spend_inner_puzzle = get_inner_puzzle_from_puzzle(&p)?.ok_or(Error::new(
+            ErrorKind::InvalidInput,
+            "Failed to get inner puzzle for last_coin_spend_inner_puzzle",
+        ))?;
+        Program::to(vec![
+            last_coin_spend.coin.parent_coin_info.to_sexp(),
+            last_coin_spend_inner_puzzle.tree_hash().to_sexp(),
+            last_coin_spend.coin.amount.to_sexp(),
+        ])
+    };
+    let full_puzzle = create_full_puzzle(&inner_puzzle, &launcher_id)?;
+    if full_puzzle.tree_hash() != current_singleton.puzzle_hash {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Pool state does not match the current singleton",
+        ));
+    }
+    let full_solution = Program::to(vec![
+        parent_info_list.to_sexp(),
+        current_singleton.amount.to_sexp(),
+        inner_solution.to_sexp(),
+    ]);
+    let p2_singleton_puzzle =
+        create_p2_singleton_puzzle(&SINGLETON_MOD_HASH, &launcher_id, delay_time, delay_ph)?;
+    let reward_coin = create_pool_coin(
+        height,
+        &p2_singleton_puzzle.tree_hash(),
+        reward_amount,
+        genesis_challenge,
+    );
+    let p2_singleton_solution = Program::to(vec![
+        inner_puzzle.tree_hash().to_sexp(),
+        reward_coin.name().to_sexp(),
+    ]);
+    Ok(vec![
+        CoinSpend {
+            coin: current_singleton,
+            puzzle_reveal: SerializedProgram::from_bytes(&full_puzzle.serialized),
+            solution: SerializedProgram::from_bytes(&full_solution.serialized),
+        },
+        CoinSpend {
+            coin: reward_coin,
+            puzzle_reveal: SerializedProgram::from_bytes(&p2_singleton_puzzle.serialized),
+            solution: SerializedProgram::from_bytes(&p2_singleton_solution.serialized),
+        },
+    ])
+}
+
 pub fn get_most_recent_singleton_coin_from_coin_spend(
     coin_solution: &CoinSpend,
 ) -> Result<Option<Coin>, Error> {
@@ -0,0 +1,188 @@
+#[cfg(test)]
+fn coin_records(amounts: &[(u64, u32)]) -> Vec<dg_xch_core::blockchain::coin_record::CoinRecord> {
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::coin_record::CoinRecord;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+
+    amounts
+        .iter()
+        .enumerate()
+        .map(|(i, (amount, height))| CoinRecord {
+            coin: Coin {
+                parent_coin_info: Bytes32::new(&[i as u8 + 1; 32]),
+                puzzle_hash: Bytes32::new(&[0u8; 32]),
+                amount: *amount,
+            },
+            confirmed_block_index: *height,
+            spent_block_index: 0,
+            timestamp: 0,
+            coinbase: false,
+            spent: false,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+fn sorted_amounts(
+    coins: std::collections::HashSet<dg_xch_core::blockchain::coin::Coin>,
+) -> Vec<u64> {
+    let mut amounts: Vec<u64> = coins.into_iter().map(|c| c.amount).collect();
+    amounts.sort();
+    amounts
+}
+
+#[test]
+pub fn test_coin_selectors() {
+    use dg_xch_cli::wallets::coin_selection::{
+        BranchAndBoundSelector, CoinSelector, DefaultCoinSelector, DustConsolidationSelector,
+        LargestFirstSelector, OldestFirstSelector,
+    };
+    use std::io::ErrorKind;
+
+    let records = coin_records(&[
+        (1, 50),
+        (2, 40),
+        (3, 30),
+        (600, 20),
+        (1_000, 10),
+        (5_000, 60),
+    ]);
+    let max = u64::MAX;
+
+    //The default keeps the existing exact match and knapsack behaviour
+    let selected = DefaultCoinSelector::default()
+        .select(&records, 600, max)
+        .unwrap();
+    assert_eq!(sorted_amounts(selected), vec![600]);
+    let selected = DefaultCoinSelector::default()
+        .select(&records, 5, max)
+        .unwrap();
+    assert!(sorted_amounts(selected).iter().sum::<u64>() >= 5);
+
+    let selected = BranchAndBoundSelector::default()
+        .select(&records, 1_603, max)
+        .unwrap();
+    assert_eq!(sorted_amounts(selected), vec![3, 600, 1_000]);
+    let err = BranchAndBoundSelector::default()
+        .select(&records, 1_607, max)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    let selected = BranchAndBoundSelector {
+        max_waste: 10,
+        ..Default::default()
+    }
+    .select(&records, 1_598, max)
+    .unwrap();
+    assert_eq!(sorted_amounts(selected), vec![600, 1_000]);
+
+    let selected = LargestFirstSelector::default()
+        .select(&records, 5_500, max)
+        .unwrap();
+    assert_eq!(sorted_amounts(selected), vec![1_000, 5_000]);
+    let err = LargestFirstSelector { max_num_coins: 2 }
+        .select(&records, 6_500, max)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    let selected = OldestFirstSelector::default()
+        .select(&records, 1_500, max)
+        .unwrap();
+    assert_eq!(sorted_amounts(selected), vec![600, 1_000]);
+
+    //Dust is always swept up, larger coins are only added to cover the amount
+    let selected = DustConsolidationSelector::new(10)
+        .select(&records, 0, max)
+        .unwrap();
+    assert_eq!(sorted_amounts(selected), vec![1, 2, 3]);
+    let selected = DustConsolidationSelector::new(10)
+        .select(&records, 900, max)
+        .unwrap();
+    assert_eq!(sorted_amounts(selected), vec![1, 2, 3, 5_000]);
+    let selected = DustConsolidationSelector {
+        dust_threshold: 10,
+        max_num_coins: 2,
+    }
+    .select(&records, 0, max)
+    .unwrap();
+    assert_eq!(sorted_amounts(selected), vec![1, 2]);
+}
+
+#[test]
+pub fn test_coin_selectors_without_coins() {
+    use dg_xch_cli::wallets::coin_selection::{
+        BranchAndBoundSelector, CoinSelector, DefaultCoinSelector, DustConsolidationSelector,
+        LargestFirstSelector, OldestFirstSelector,
+    };
+    use std::io::ErrorKind;
+
+    //An empty wallet is an error for every strategy, never a panic
+    let err = DefaultCoinSelector::default()
+        .select(&[], 0, u64::MAX)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    let selectors: Vec<Box<dyn CoinSelector>> = vec![
+        Box::new(DefaultCoinSelector::default()),
+        Box::new(BranchAndBoundSelector::default()),
+        Box::new(LargestFirstSelector::default()),
+        Box::new(OldestFirstSelector::default()),
+        Box::new(DustConsolidationSelector::new(10)),
+    ];
+    for selector in selectors {
+        assert!(selector.select(&[], 10, u64::MAX).is_err());
+    }
+}
+
+#[tokio::test]
+pub async fn test_select_coins_with_strategy() {
+    use blst::min_pk::SecretKey;
+    use dg_xch_cli::wallets::coin_selection::{LargestFirstSelector, OldestFirstSelector};
+    use dg_xch_cli::wallets::memory_wallet::MemoryWalletStore;
+    use dg_xch_cli::wallets::WalletStore;
+
+    let master_sk = SecretKey::key_gen(&[7u8; 32], &[]).unwrap();
+    let mut store = MemoryWalletStore::new(master_sk, 0);
+    let records = coin_records(&[(100, 30), (200, 10), (300, 20), (400, 40)]);
+    store.update_coin_records(records.clone());
+
+    //Filters are applied before the strategy sees the coins
+    let selected = store
+        .select_coins_with_strategy(
+            250,
+            Some(&[records[1].coin.clone()]),
+            None,
+            u64::MAX,
+            None,
+            &OldestFirstSelector::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(sorted_amounts(selected), vec![300]);
+    let selected = store
+        .select_coins_with_strategy(
+            250,
+            None,
+            None,
+            350,
+            Some(&[300]),
+            &LargestFirstSelector::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(sorted_amounts(selected), vec![100, 200]);
+    let selected = store.select_coins(300, None, None, u64::MAX, None).await;
+    assert_eq!(sorted_amounts(selected.unwrap()), vec![300]);
+    assert!(store
+        .select_coins_with_strategy(
+            2_000,
+            None,
+            None,
+            u64::MAX,
+            None,
+            &LargestFirstSelector::default(),
+        )
+        .await
+        .is_err());
+}
@@ -0,0 +1,275 @@
+#[tokio::test]
+pub async fn test_file_wallet_store() {
+    use blst::min_pk::SecretKey;
+    use dg_xch_cli::wallets::file_wallet::FileWalletStore;
+    use dg_xch_cli::wallets::WalletStore;
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::coin_record::CoinRecord;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+    use dg_xch_core::blockchain::transaction_record::TransactionRecord;
+    use serde_json::json;
+    use std::fs::{create_dir_all, read_to_string, write};
+    use std::io::ErrorKind;
+    use uuid::Uuid;
+
+    let wallet_dir = std::env::temp_dir().join(format!("dg_xch_file_wallet_{}", Uuid::new_v4()));
+    create_dir_all(&wallet_dir).unwrap();
+    let path = wallet_dir.join("wallet.json");
+    let master_sk = SecretKey::key_gen(&[11u8; 32], &[]).unwrap();
+    let passphrase = "correct horse battery staple";
+
+    let mut store = FileWalletStore::create(&path, master_sk.clone(), passphrase).unwrap();
+    let mut records = vec![];
+    for _ in 0..3 {
+        records.push(store.get_unused_derivation_record(false).await.unwrap());
+    }
+    let far_record = store
+        .get_derivation_record_at_index(150, true)
+        .await
+        .unwrap();
+    let coin = Coin {
+        parent_coin_info: Bytes32::new(&[1u8; 32]),
+        puzzle_hash: records[1].puzzle_hash,
+        amount: 2_000,
+    };
+    store
+        .update_chain_state(
+            vec![CoinRecord {
+                coin,
+                confirmed_block_index: 90,
+                spent_block_index: 0,
+                timestamp: 0,
+                coinbase: false,
+                spent: false,
+            }],
+            100,
+        )
+        .await
+        .unwrap();
+    let transaction: TransactionRecord = serde_json::from_value(json!({
+        "confirmed_at_height": 90,
+        "created_at_time": 1_700_000_000u64,
+        "to_puzzle_hash": records[1].puzzle_hash,
+        "amount": 2000,
+        "fee_amount": 0,
+        "confirmed": true,
+        "sent": 0,
+        "spend_bundle": null,
+        "additions": [],
+        "removals": [],
+        "wallet_id": 1,
+        "sent_to": [],
+        "trade_id": null,
+        "type": 0,
+        "name": Bytes32::new(&[2u8; 32]),
+        "memos": []
+    }))
+    .unwrap();
+    store.add_transaction(transaction.clone()).await.unwrap();
+    store.add_transaction(transaction).await.unwrap();
+    drop(store);
+
+    //Secret material is never written in the clear
+    let contents = read_to_string(&path).unwrap();
+    assert!(!contents.contains(&hex::encode(master_sk.to_bytes())));
+    assert!(!wallet_dir.join("wallet.json.tmp").exists());
+
+    let err = FileWalletStore::create(&path, master_sk.clone(), passphrase)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    let err = FileWalletStore::open(&path, "wrong passphrase")
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+    //A temp file left behind by an interrupted save does not affect the wallet
+    write(wallet_dir.join("wallet.json.tmp"), "{\"partial").unwrap();
+    let mut store = FileWalletStore::open(&path, passphrase).unwrap();
+    assert_eq!(store.get_master_sk().to_bytes(), master_sk.to_bytes());
+    assert_eq!(store.sync_height(), 100);
+    assert_eq!(store.derivation_records().len(), 4);
+    assert!(store.is_our_puzzle_hash(&records[2].puzzle_hash));
+    assert_eq!(store.transactions().len(), 1);
+    assert_eq!(store.get_confirmed_balance().await, 2_000);
+    assert_eq!(
+        store
+            .get_unused_derivation_record(false)
+            .await
+            .unwrap()
+            .index,
+        3
+    );
+    assert_eq!(
+        store
+            .populate_secret_key_for_puzzle_hash(&far_record.puzzle_hash)
+            .await
+            .unwrap(),
+        far_record.pubkey
+    );
+
+    store.change_passphrase("new passphrase").await.unwrap();
+    drop(store);
+    assert!(FileWalletStore::open(&path, passphrase).is_err());
+    let store = FileWalletStore::open(&path, "new passphrase").unwrap();
+    assert_eq!(store.derivation_records().len(), 5);
+    drop(store);
+
+    //Files written by a newer schema are refused rather than misread
+    let mut value: serde_json::Value =
+        serde_json::from_str(&read_to_string(&path).unwrap()).unwrap();
+    value["schema_version"] = json!(99);
+    write(&path, value.to_string()).unwrap();
+    let err = FileWalletStore::open(&path, "new passphrase")
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[tokio::test]
+pub async fn test_file_wallet_sync() {
+    use crate::clients::mock_server::MockRpcServer;
+    use blst::min_pk::SecretKey;
+    use dg_xch_cli::wallets::file_wallet::{FileWallet, FileWalletStore};
+    use dg_xch_cli::wallets::memory_wallet::{MemoryWalletConfig, MemoryWalletStore};
+    use dg_xch_cli::wallets::{Wallet, WalletInfo, WalletStore};
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+    use dg_xch_core::blockchain::transaction_record::TransactionRecord;
+    use dg_xch_core::blockchain::tx_status::TXStatus;
+    use dg_xch_core::blockchain::wallet_type::WalletType;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::fs::{create_dir_all, read_to_string};
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+    use uuid::Uuid;
+
+    let wallet_dir = std::env::temp_dir().join(format!("dg_xch_file_wallet_{}", Uuid::new_v4()));
+    create_dir_all(&wallet_dir).unwrap();
+    let path = wallet_dir.join("wallet.json");
+    let master_sk = SecretKey::key_gen(&[12u8; 32], &[]).unwrap();
+    let store = FileWalletStore::create(&path, master_sk.clone(), "passphrase").unwrap();
+    let used_ph = MemoryWalletStore::new(master_sk.clone(), 0)
+        .get_derivation_record_at_index(4, false)
+        .await
+        .unwrap()
+        .puzzle_hash;
+    let mut responses = HashMap::new();
+    responses.insert(
+        "get_blockchain_state".to_string(),
+        json!({
+            "blockchain_state": {
+                "peak": null,
+                "genesis_challenge_initialized": true,
+                "sync": {
+                    "sync_mode": false,
+                    "synced": true,
+                    "sync_tip_height": 0,
+                    "sync_progress_height": 0
+                },
+                "difficulty": 1,
+                "sub_slot_iters": 1,
+                "space": 0,
+                "mempool_size": 0,
+                "mempool_cost": 0,
+                "mempool_min_fees": {"cost_5000000": 0},
+                "mempool_max_total_cost": 0,
+                "block_max_cost": 0,
+                "node_id": Bytes32::new(&[0u8; 32])
+            },
+            "success": true
+        }),
+    );
+    responses.insert(
+        "get_coin_records_by_puzzle_hashes".to_string(),
+        json!({
+            "coin_records": [{
+                "coin": {
+                    "parent_coin_info": Bytes32::new(&[1u8; 32]),
+                    "puzzle_hash": used_ph,
+                    "amount": 750
+                },
+                "confirmed_block_index": 100,
+                "spent_block_index": 0,
+                "timestamp": 1_700_000_000u64,
+                "coinbase": false,
+                "spent": false
+            }],
+            "success": true
+        }),
+    );
+    responses.insert(
+        "get_all_mempool_items".to_string(),
+        json!({"mempool_items": {}, "success": true}),
+    );
+    responses.insert(
+        "push_tx".to_string(),
+        json!({"status": "SUCCESS", "success": true}),
+    );
+    let server = MockRpcServer::start(responses).await.unwrap();
+    let wallet = FileWallet::create(
+        WalletInfo {
+            id: 1,
+            name: "file_wallet".to_string(),
+            wallet_type: WalletType::StandardWallet,
+            constants: Default::default(),
+            master_sk: master_sk.clone(),
+            wallet_store: Arc::new(Mutex::new(store)),
+            data: "".to_string(),
+        },
+        MemoryWalletConfig {
+            fullnode_host: "localhost".to_string(),
+            fullnode_port: server.port,
+            fullnode_ssl_path: Some(server.ssl_path.clone()),
+            additional_headers: None,
+            gap_limit: 5,
+        },
+    );
+    assert!(wallet.sync().await.unwrap());
+    assert!(wallet.is_synced());
+    let transaction: TransactionRecord = serde_json::from_value(json!({
+        "confirmed_at_height": 0,
+        "created_at_time": 1_700_000_000u64,
+        "to_puzzle_hash": used_ph,
+        "amount": 500,
+        "fee_amount": 0,
+        "confirmed": false,
+        "sent": 0,
+        "spend_bundle": {
+            "coin_spends": [],
+            "aggregated_signature": format!("0xc0{}", "00".repeat(95))
+        },
+        "additions": [],
+        "removals": [],
+        "wallet_id": 1,
+        "sent_to": [],
+        "trade_id": null,
+        "type": 1,
+        "name": Bytes32::new(&[3u8; 32]),
+        "memos": []
+    }))
+    .unwrap();
+    assert_eq!(
+        wallet.push_transaction(transaction).await.unwrap(),
+        TXStatus::SUCCESS
+    );
+    drop(wallet);
+
+    //Everything the sync found is on disk, including every index scanned past the last used one
+    let store = FileWalletStore::open(&path, "passphrase").unwrap();
+    assert_eq!(store.get_confirmed_balance().await, 750);
+    assert_eq!(store.derivation_records().len(), 2 * 10);
+    assert!(store.is_our_puzzle_hash(&used_ph));
+    assert_eq!(store.transactions().len(), 1);
+    //Handing out a record the sync already persisted leaves the file alone
+    let contents = read_to_string(&path).unwrap();
+    assert_eq!(
+        store
+            .get_unused_derivation_record(false)
+            .await
+            .unwrap()
+            .index,
+        5
+    );
+    assert_eq!(read_to_string(&path).unwrap(), contents);
+}
@@ -0,0 +1,254 @@
+#[cfg(test)]
+fn mempool_item(
+    removal: &dg_xch_core::blockchain::coin::Coin,
+    create_coin: Vec<(dg_xch_core::blockchain::sized_bytes::Bytes32, u64)>,
+) -> dg_xch_core::blockchain::mempool_item::MempoolItem {
+    use dg_xch_core::blockchain::coin_spend::CoinSpend;
+    use dg_xch_core::blockchain::mempool_item::MempoolItem;
+    use dg_xch_core::blockchain::npc_result::NPCResult;
+    use dg_xch_core::blockchain::spend::Spend;
+    use dg_xch_core::blockchain::spend_bundle::SpendBundle;
+    use dg_xch_core::blockchain::spend_bundle_conditions::SpendBundleConditions;
+    use dg_xch_core::clvm::program::SerializedProgram;
+    use std::collections::HashMap;
+
+    let spend_bundle = SpendBundle {
+        coin_spends: vec![CoinSpend {
+            coin: removal.clone(),
+            puzzle_reveal: SerializedProgram::from_bytes(&[0x80]),
+            solution: SerializedProgram::from_bytes(&[0x80]),
+        }],
+        aggregated_signature: Default::default(),
+    };
+    MempoolItem {
+        spend_bundle_name: spend_bundle.name(),
+        spend_bundle,
+        fee: 0,
+        npc_result: NPCResult {
+            error: None,
+            conds: Some(SpendBundleConditions {
+                spends: vec![Spend {
+                    coin_id: removal.name(),
+                    puzzle_hash: removal.puzzle_hash,
+                    height_relative: None,
+                    seconds_relative: None,
+                    before_height_relative: None,
+                    before_seconds_relative: None,
+                    birth_height: None,
+                    birth_seconds: None,
+                    create_coin: create_coin
+                        .into_iter()
+                        .map(|(puzzle_hash, amount)| (puzzle_hash, amount, None))
+                        .collect(),
+                    agg_sig_me: vec![],
+                    flags: 0,
+                }],
+                reserve_fee: 0,
+                height_absolute: 0,
+                seconds_absolute: 0,
+                before_height_absolute: None,
+                before_seconds_absolute: None,
+                agg_sig_unsafe: vec![],
+                cost: 0,
+                removal_amount: 0,
+                addition_amount: 0,
+            }),
+            cost: 0,
+        },
+        height_added_to_mempool: 0,
+        assert_height: None,
+        assert_before_height: None,
+        assert_before_seconds: None,
+        bundle_coin_spends: HashMap::new(),
+    }
+}
+
+#[tokio::test]
+pub async fn test_memory_wallet_store_balances() {
+    use blst::min_pk::SecretKey;
+    use dg_xch_cli::wallets::memory_wallet::MemoryWalletStore;
+    use dg_xch_cli::wallets::WalletStore;
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::coin_record::CoinRecord;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+
+    let master_sk = SecretKey::key_gen(&[3u8; 32], &[]).unwrap();
+    let mut store = MemoryWalletStore::new(master_sk, 0);
+    let our_ph = store
+        .get_derivation_record_at_index(0, false)
+        .await
+        .unwrap()
+        .puzzle_hash;
+    let their_ph = Bytes32::new(&[9u8; 32]);
+    let record = |parent: u8, amount: u64, spent: bool| CoinRecord {
+        coin: Coin {
+            parent_coin_info: Bytes32::new(&[parent; 32]),
+            puzzle_hash: our_ph,
+            amount,
+        },
+        confirmed_block_index: 10,
+        spent_block_index: if spent { 11 } else { 0 },
+        timestamp: 0,
+        coinbase: false,
+        spent,
+    };
+    let spending = record(1, 1_000, false);
+    let untouched = record(2, 500, false);
+    store.update_coin_records(vec![
+        spending.clone(),
+        untouched.clone(),
+        record(3, 700, true),
+    ]);
+    assert_eq!(store.unspent_coins.len(), 2);
+    assert_eq!(store.spent_coins.len(), 1);
+    assert_eq!(store.get_confirmed_balance().await, 1_500);
+    assert_eq!(store.get_unconfirmed_balance().await, 1_500);
+    assert_eq!(store.get_pending_change_balance().await, 0);
+
+    //Send 300 out of the 1000 coin with 600 change, while someone else sends us 50
+    let incoming = Coin {
+        parent_coin_info: Bytes32::new(&[4u8; 32]),
+        puzzle_hash: their_ph,
+        amount: 50,
+    };
+    store.update_pending(vec![
+        mempool_item(&spending.coin, vec![(their_ph, 300), (our_ph, 600)]),
+        mempool_item(&incoming, vec![(our_ph, 50)]),
+    ]);
+    assert_eq!(store.pending_removals.len(), 1);
+    assert_eq!(store.get_confirmed_balance().await, 1_500);
+    assert_eq!(store.get_unconfirmed_balance().await, 1_150);
+    assert_eq!(store.get_pending_change_balance().await, 600);
+    assert_eq!(store.get_spendable_balance().await, 500);
+    let selected = store.select_coins(100, None, None, u64::MAX, None).await;
+    assert_eq!(
+        selected.unwrap().into_iter().collect::<Vec<Coin>>(),
+        vec![untouched.coin]
+    );
+
+    store.update_pending(vec![]);
+    assert_eq!(store.get_unconfirmed_balance().await, 1_500);
+    assert_eq!(store.get_spendable_balance().await, 1_500);
+}
+
+#[tokio::test]
+pub async fn test_memory_wallet_sync() {
+    use crate::clients::mock_server::MockRpcServer;
+    use blst::min_pk::SecretKey;
+    use dg_xch_cli::wallets::memory_wallet::{MemoryWallet, MemoryWalletConfig, MemoryWalletStore};
+    use dg_xch_cli::wallets::{Wallet, WalletInfo, WalletStore};
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+    use dg_xch_core::blockchain::wallet_type::WalletType;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    let master_sk = SecretKey::key_gen(&[5u8; 32], &[]).unwrap();
+    let store = MemoryWalletStore::new(master_sk.clone(), 0);
+    let used_ph = store
+        .get_derivation_record_at_index(2, false)
+        .await
+        .unwrap()
+        .puzzle_hash;
+    let hardened_ph = store
+        .get_derivation_record_at_index(1, true)
+        .await
+        .unwrap()
+        .puzzle_hash;
+    let coin = |parent: u8, puzzle_hash: Bytes32, amount: u64| Coin {
+        parent_coin_info: Bytes32::new(&[parent; 32]),
+        puzzle_hash,
+        amount,
+    };
+    let coin_record = |coin: Coin, spent: bool| {
+        json!({
+            "coin": coin,
+            "confirmed_block_index": 100,
+            "spent_block_index": if spent { 101 } else { 0 },
+            "timestamp": 1_700_000_000u64,
+            "coinbase": false,
+            "spent": spent
+        })
+    };
+    let mut responses = HashMap::new();
+    responses.insert(
+        "get_blockchain_state".to_string(),
+        json!({
+            "blockchain_state": {
+                "peak": null,
+                "genesis_challenge_initialized": true,
+                "sync": {
+                    "sync_mode": false,
+                    "synced": true,
+                    "sync_tip_height": 0,
+                    "sync_progress_height": 0
+                },
+                "difficulty": 1,
+                "sub_slot_iters": 1,
+                "space": 0,
+                "mempool_size": 0,
+                "mempool_cost": 0,
+                "mempool_min_fees": {"cost_5000000": 0},
+                "mempool_max_total_cost": 0,
+                "block_max_cost": 0,
+                "node_id": Bytes32::new(&[0u8; 32])
+            },
+            "success": true
+        }),
+    );
+    responses.insert(
+        "get_coin_records_by_puzzle_hashes".to_string(),
+        json!({
+            "coin_records": [
+                coin_record(coin(1, used_ph, 1_000), false),
+                coin_record(coin(2, hardened_ph, 250), false),
+                coin_record(coin(3, used_ph, 4_000), true)
+            ],
+            "success": true
+        }),
+    );
+    responses.insert(
+        "get_all_mempool_items".to_string(),
+        json!({"mempool_items": {}, "success": true}),
+    );
+    let server = MockRpcServer::start(responses).await.unwrap();
+    let wallet = MemoryWallet::create(
+        WalletInfo {
+            id: 1,
+            name: "memory_wallet".to_string(),
+            wallet_type: WalletType::StandardWallet,
+            constants: Default::default(),
+            master_sk: master_sk.clone(),
+            wallet_store: Arc::new(Mutex::new(store)),
+            data: "".to_string(),
+        },
+        MemoryWalletConfig {
+            fullnode_host: "localhost".to_string(),
+            fullnode_port: server.port,
+            fullnode_ssl_path: Some(server.ssl_path.clone()),
+            additional_headers: None,
+            gap_limit: 5,
+        },
+    );
+    assert!(!wallet.is_synced());
+    assert!(wallet.sync().await.unwrap());
+    assert!(wallet.is_synced());
+
+    //Index 2 is the last used, so the scan stops after the second batch of 5 indexes
+    let request = server
+        .request_for("get_coin_records_by_puzzle_hashes")
+        .unwrap();
+    assert_eq!(request["puzzle_hashes"].as_array().map(Vec::len), Some(10));
+    assert_eq!(request["include_spent_coins"], Value::Bool(true));
+    let store = wallet.wallet_store();
+    let store = store.lock().await;
+    assert_eq!(store.current_index.load(Ordering::Relaxed), 3);
+    assert_eq!(store.unspent_coins.len(), 2);
+    assert_eq!(store.spent_coins.len(), 1);
+    assert_eq!(store.get_confirmed_balance().await, 1_250);
+    assert_eq!(store.get_unconfirmed_balance().await, 1_250);
+    assert_eq!(store.get_pending_change_balance().await, 0);
+}
@@ -0,0 +1,7 @@
+pub mod coin_selection;
+pub mod file_wallet;
+pub mod memory_wallet;
+pub mod node_commands;
+pub mod pool_rewards;
+pub mod transaction_builder;
+pub mod wallet_commands;
@@ -0,0 +1,96 @@
+#[test]
+pub fn test_puzzle_hash_from_args() {
+    use dg_xch_cli::node_commands::puzzle_hash_from_args;
+    use dg_xch_core::blockchain::sized_bytes::Bytes32;
+    use dg_xch_keys::encode_puzzle_hash;
+
+    let puzzle_hash =
+        Bytes32::from("4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba");
+    let address = encode_puzzle_hash(&puzzle_hash, "xch").unwrap();
+    assert_eq!(
+        puzzle_hash_from_args(None, Some(address.clone())).unwrap(),
+        puzzle_hash
+    );
+    assert_eq!(
+        puzzle_hash_from_args(Some(puzzle_hash.to_string()), None).unwrap(),
+        puzzle_hash
+    );
+    assert!(puzzle_hash_from_args(None, None).is_err());
+    assert!(puzzle_hash_from_args(Some(puzzle_hash.to_string()), Some(address)).is_err());
+    assert!(puzzle_hash_from_args(Some("0x1234".to_string()), None).is_err());
+}
+
+#[test]
+pub fn test_format_table() {
+    use dg_xch_cli::commands::format_table;
+
+    let table = format_table(
+        &["Name", "Amount"],
+        &[
+            vec!["first".to_string(), "1".to_string()],
+            vec!["second_coin".to_string(), "1000".to_string()],
+        ],
+    );
+    assert_eq!(
+        table,
+        "Name        | Amount\n------------+-------\nfirst       | 1\nsecond_coin | 1000"
+    );
+}
+
+#[tokio::test]
+pub async fn test_get_coin_records_command() {
+    use crate::clients::mock_server::MockRpcServer;
+    use dg_xch_cli::commands::OutputFormat;
+    use dg_xch_cli::node_commands::get_coin_records;
+    use dg_xch_clients::rpc::full_node::FullnodeClient;
+    use dg_xch_core::blockchain::sized_bytes::Bytes32;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    let puzzle_hash = "0x4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba";
+    let mut responses = HashMap::new();
+    responses.insert(
+        "get_coin_records_by_puzzle_hash".to_string(),
+        json!({
+            "coin_records": [{
+                "coin": {
+                    "parent_coin_info": "0xccd5bb71183532bff220ba46c268991a00000000000000000000000000000000",
+                    "puzzle_hash": puzzle_hash,
+                    "amount": 1_750_000_000_000u64
+                },
+                "confirmed_block_index": 1,
+                "spent_block_index": 0,
+                "timestamp": 1_700_000_000u64,
+                "coinbase": true,
+                "spent": false
+            }],
+            "success": true
+        }),
+    );
+    let server = MockRpcServer::start(responses).await.unwrap();
+    let client = FullnodeClient::new(
+        "localhost",
+        server.port,
+        Some(server.ssl_path.clone()),
+        &None,
+    );
+    let puzzle_hash = Bytes32::from(puzzle_hash);
+    let table = get_coin_records(&client, &puzzle_hash, false, OutputFormat::Table)
+        .await
+        .unwrap();
+    let lines = table.lines().collect::<Vec<&str>>();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("Coin ID"));
+    assert!(lines[2].contains("1750000000000"));
+    assert_eq!(
+        server
+            .request_for("get_coin_records_by_puzzle_hash")
+            .unwrap()["include_spent_coins"],
+        json!(false)
+    );
+    let json_output = get_coin_records(&client, &puzzle_hash, true, OutputFormat::Json)
+        .await
+        .unwrap();
+    let parsed: Value = serde_json::from_str(&json_output).unwrap();
+    assert_eq!(parsed[0]["coin"]["amount"], json!(1_750_000_000_000u64));
+}
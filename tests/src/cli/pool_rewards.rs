@@ -0,0 +1,200 @@
+#[test]
+pub fn test_absorb_spends() {
+    use blst::min_pk::SecretKey;
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::coin_record::CoinRecord;
+    use dg_xch_core::blockchain::coin_spend::CoinSpend;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, Bytes96, SizedBytes};
+    use dg_xch_core::blockchain::spend_bundle::SpendBundle;
+    use dg_xch_core::blockchain::spend_bundle_simulator::simulate_spend_bundle;
+    use dg_xch_core::clvm::program::{Program, SerializedProgram};
+    use dg_xch_core::clvm::sexp::IntoSExp;
+    use dg_xch_core::consensus::coinbase::{create_pool_coin, get_farmed_height};
+    use dg_xch_core::consensus::constants::MAINNET;
+    use dg_xch_core::pool::PoolState;
+    use dg_xch_puzzles::clvm_puzzles::{
+        create_absorb_spend, create_full_puzzle, launcher_id_to_p2_puzzle_hash,
+        pool_state_to_inner_puzzle, SINGLETON_LAUNCHER, SINGLETON_LAUNCHER_HASH,
+    };
+
+    let genesis = MAINNET.genesis_challenge;
+    let owner = SecretKey::key_gen(&[19u8; 32], &[]).unwrap();
+    let pool_target = Bytes32::new(&[20u8; 32]);
+    let delay_ph = Bytes32::new(&[21u8; 32]);
+    let delay_time = 604800;
+    let state = PoolState {
+        version: 1,
+        state: 3,
+        target_puzzle_hash: pool_target,
+        owner_pubkey: Bytes48::new(&owner.sk_to_pk().to_bytes()),
+        pool_url: Some(String::from("https://pool.example")),
+        relative_lock_height: 100,
+    };
+    let launcher_coin = Coin {
+        parent_coin_info: Bytes32::new(&[22u8; 32]),
+        puzzle_hash: *SINGLETON_LAUNCHER_HASH,
+        amount: 1,
+    };
+    let launcher_id = launcher_coin.name();
+    let inner =
+        pool_state_to_inner_puzzle(&state, &launcher_id, &genesis, delay_time, &delay_ph).unwrap();
+    let full_puzzle_hash = create_full_puzzle(&inner, &launcher_id)
+        .unwrap()
+        .tree_hash();
+    let launcher_solution = Program::to(vec![
+        full_puzzle_hash.to_sexp(),
+        1.to_sexp(),
+        Program::null().to_sexp(),
+    ]);
+    let launcher_spend = CoinSpend {
+        coin: launcher_coin.clone(),
+        puzzle_reveal: SerializedProgram::from_bytes(&SINGLETON_LAUNCHER.serialized),
+        solution: SerializedProgram::from_bytes(&launcher_solution.serialized),
+    };
+    let identity = Bytes96::new(&[[0xc0u8].as_slice(), &[0u8; 95]].concat());
+    let reward = 1_750_000_000_000;
+    let p2_singleton_puzzle_hash =
+        launcher_id_to_p2_puzzle_hash(&launcher_id, delay_time, &delay_ph).unwrap();
+
+    //Claim two rewards, the second spends the singleton created by the first
+    let mut last_spend = launcher_spend;
+    for height in [1000u32, 1001] {
+        let absorb = create_absorb_spend(
+            &last_spend,
+            &state,
+            &launcher_coin,
+            height,
+            reward,
+            &genesis,
+            delay_time,
+            &delay_ph,
+        )
+        .unwrap();
+        assert_eq!(absorb.len(), 2);
+        assert_eq!(
+            absorb[1].coin,
+            create_pool_coin(height, &p2_singleton_puzzle_hash, reward, &genesis)
+        );
+        let report = simulate_spend_bundle(
+            &SpendBundle {
+                coin_spends: absorb.clone(),
+                aggregated_signature: identity,
+            },
+            &MAINNET,
+        );
+        assert!(report.is_valid(), "{:?}", report.errors);
+        assert_eq!(report.fee, 0);
+        assert!(report
+            .additions
+            .iter()
+            .any(|c| c.puzzle_hash == pool_target && c.amount == reward));
+        assert!(report
+            .additions
+            .iter()
+            .any(|c| c.puzzle_hash == full_puzzle_hash && c.amount == 1));
+        last_spend = absorb[0].clone();
+    }
+
+    //The pool state must match the current singleton
+    let mut other_state = state.clone();
+    other_state.target_puzzle_hash = Bytes32::new(&[23u8; 32]);
+    assert!(create_absorb_spend(
+        &last_spend,
+        &other_state,
+        &launcher_coin,
+        1002,
+        reward,
+        &genesis,
+        delay_time,
+        &delay_ph,
+    )
+    .is_err());
+
+    //Rewards are recognised up to 128 blocks after the farmed height
+    let record = |parent_height: u32, confirmed_block_index: u32| CoinRecord {
+        coin: create_pool_coin(parent_height, &p2_singleton_puzzle_hash, reward, &genesis),
+        confirmed_block_index,
+        spent_block_index: 0,
+        timestamp: 0,
+        coinbase: true,
+        spent: false,
+    };
+    assert_eq!(get_farmed_height(&record(1000, 1000), &genesis), Some(1000));
+    assert_eq!(get_farmed_height(&record(1000, 1127), &genesis), Some(1000));
+    assert_eq!(get_farmed_height(&record(1000, 1128), &genesis), None);
+    assert_eq!(get_farmed_height(&record(1000, 999), &genesis), None);
+}
+
+#[test]
+pub fn test_pool_payouts() {
+    use dg_xch_cli::wallets::plotnft_utils::calculate_pool_payouts;
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::coin_spend::CoinSpend;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, SizedBytes};
+    use dg_xch_core::clvm::program::SerializedProgram;
+    use dg_xch_core::pool::{Farmer, PoolState};
+    use dg_xch_keys::encode_puzzle_hash;
+
+    let farmer = |seed: u8, points: u64, payout_instructions: String| Farmer {
+        launcher_id: Bytes32::new(&[seed; 32]),
+        p2_singleton_puzzle_hash: Bytes32::default(),
+        delay_time: 604800,
+        delay_puzzle_hash: Bytes32::default(),
+        authentication_public_key: Bytes48::default(),
+        singleton_tip: CoinSpend {
+            coin: Coin {
+                parent_coin_info: Bytes32::default(),
+                puzzle_hash: Bytes32::default(),
+                amount: 1,
+            },
+            puzzle_reveal: SerializedProgram::from_bytes(&[0x80]),
+            solution: SerializedProgram::from_bytes(&[0x80]),
+        },
+        singleton_tip_state: PoolState {
+            version: 1,
+            state: 3,
+            target_puzzle_hash: Bytes32::default(),
+            owner_pubkey: Bytes48::default(),
+            pool_url: None,
+            relative_lock_height: 100,
+        },
+        balance: 0,
+        points,
+        difficulty: 1,
+        payout_instructions,
+        is_pool_member: true,
+        joined: 0,
+        modified: 0,
+    };
+    let ph = |seed: u8| Bytes32::new(&[seed; 32]);
+    let farmers = vec![
+        farmer(1, 100, hex::encode([31u8; 32])),
+        farmer(2, 300, encode_puzzle_hash(&ph(32), "xch").unwrap()),
+        //Shares payout instructions with the first farmer
+        farmer(3, 100, format!("0x{}", hex::encode([31u8; 32]))),
+        farmer(4, 0, hex::encode([33u8; 32])),
+        farmer(5, 1, hex::encode([34u8; 32])),
+    ];
+    let batches = calculate_pool_payouts(&farmers, 1_000_020, 10, 2).unwrap();
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches[0].fee, 10);
+    assert_eq!(batches[0].additions.len(), 2);
+    assert_eq!(batches[0].additions[0].puzzle_hash, ph(31));
+    assert_eq!(batches[0].additions[0].amount, 399_201);
+    assert_eq!(batches[0].additions[1].puzzle_hash, ph(32));
+    assert_eq!(batches[0].additions[1].amount, 598_802);
+    assert_eq!(batches[1].additions[0].puzzle_hash, ph(34));
+    assert_eq!(batches[1].additions[0].amount, 1_996);
+    let paid: u64 = batches
+        .iter()
+        .map(|b| b.fee + b.additions.iter().map(|a| a.amount).sum::<u64>())
+        .sum();
+    assert!(paid <= 1_000_020);
+
+    assert!(calculate_pool_payouts(&farmers, 15, 10, 2).is_err());
+    assert!(calculate_pool_payouts(&farmers, 1_000, 0, 0).is_err());
+    assert!(calculate_pool_payouts(&[farmer(6, 1, "nope".to_string())], 1_000, 0, 1).is_err());
+    assert!(calculate_pool_payouts(&farmers[3..4], 1_000, 0, 1)
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,137 @@
+#[tokio::test]
+pub async fn test_transaction_builder() {
+    use blst::min_pk::SecretKey;
+    use dg_xch_cli::wallets::coin_selection::LargestFirstSelector;
+    use dg_xch_cli::wallets::memory_wallet::{MemoryWallet, MemoryWalletConfig, MemoryWalletStore};
+    use dg_xch_cli::wallets::transaction_builder::TransactionBuilder;
+    use dg_xch_cli::wallets::{Wallet, WalletInfo, WalletStore};
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::coin_record::CoinRecord;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+    use dg_xch_core::blockchain::wallet_type::WalletType;
+    use std::io::ErrorKind;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    let master_sk = SecretKey::key_gen(&[21u8; 32], &[]).unwrap();
+    let mut store = MemoryWalletStore::new(master_sk.clone(), 0);
+    let first_ph = store
+        .get_derivation_record_at_index(0, false)
+        .await
+        .unwrap()
+        .puzzle_hash;
+    let second_ph = store
+        .get_derivation_record_at_index(1, false)
+        .await
+        .unwrap()
+        .puzzle_hash;
+    let record = |parent: u8, puzzle_hash: Bytes32, amount: u64| CoinRecord {
+        coin: Coin {
+            parent_coin_info: Bytes32::new(&[parent; 32]),
+            puzzle_hash,
+            amount,
+        },
+        confirmed_block_index: 10,
+        spent_block_index: 0,
+        timestamp: 0,
+        coinbase: false,
+        spent: false,
+    };
+    store.update_coin_records(vec![
+        record(1, first_ph, 1_000),
+        record(2, second_ph, 500),
+        record(3, first_ph, 300),
+    ]);
+    store.current_index.store(2, Ordering::Relaxed);
+    let wallet = MemoryWallet::create(
+        WalletInfo {
+            id: 1,
+            name: "memory_wallet".to_string(),
+            wallet_type: WalletType::StandardWallet,
+            constants: Default::default(),
+            master_sk,
+            wallet_store: Arc::new(Mutex::new(store)),
+            data: "".to_string(),
+        },
+        MemoryWalletConfig {
+            fullnode_host: "localhost".to_string(),
+            fullnode_port: 8555,
+            fullnode_ssl_path: None,
+            additional_headers: None,
+            gap_limit: 5,
+        },
+    );
+    let their_ph = Bytes32::new(&[9u8; 32]);
+
+    let tx = TransactionBuilder::new()
+        .add_output(their_ph, 1_200, vec![b"payout".to_vec()])
+        .fee(10)
+        .coin_selector(LargestFirstSelector::default())
+        .create_coin_announcement(b"linked".to_vec())
+        .build(&wallet)
+        .await
+        .unwrap();
+    let mut removed: Vec<u64> = tx.removals.iter().map(|c| c.amount).collect();
+    removed.sort();
+    assert_eq!(removed, vec![500, 1_000]);
+    assert_eq!(tx.spend_bundle.coin_spends.len(), 2);
+    let change = tx.change.clone().unwrap();
+    assert_eq!(change.amount, 290);
+    assert!(tx.additions.contains(&change));
+    let output = tx
+        .additions
+        .iter()
+        .find(|c| c.puzzle_hash == their_ph)
+        .unwrap();
+    assert_eq!(output.amount, 1_200);
+    assert!(wallet
+        .wallet_store()
+        .lock()
+        .await
+        .is_our_puzzle_hash(&change.puzzle_hash));
+    let memos = wallet.compute_memos(&tx.spend_bundle).unwrap();
+    assert_eq!(memos.get(&output.name()).map(Vec::len), Some(1));
+    let record = tx.to_transaction_record(1);
+    assert_eq!(record.amount, 1_200);
+    assert_eq!(record.fee_amount, 10);
+    assert_eq!(record.name, tx.name());
+
+    //Spending exactly the given coins with no change left over
+    let exact = TransactionBuilder::new()
+        .add_output(their_ph, 295, vec![])
+        .fee(5)
+        .coins(vec![Coin {
+            parent_coin_info: Bytes32::new(&[3u8; 32]),
+            puzzle_hash: first_ph,
+            amount: 300,
+        }])
+        .build(&wallet)
+        .await
+        .unwrap();
+    assert!(exact.change.is_none());
+    assert_eq!(exact.additions.len(), 1);
+
+    let err = TransactionBuilder::new()
+        .add_output(their_ph, 100, vec![])
+        .add_output(their_ph, 100, vec![])
+        .build(&wallet)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    let err = TransactionBuilder::new()
+        .add_output(their_ph, 100, vec![])
+        .coins(tx.removals.clone())
+        .exclude_coins(&tx.removals)
+        .build(&wallet)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(TransactionBuilder::new()
+        .add_output(their_ph, 5_000, vec![])
+        .build(&wallet)
+        .await
+        .is_err());
+}
@@ -0,0 +1,237 @@
+#[test]
+pub fn test_create_plotnft_launcher_spend() {
+    use blst::min_pk::SecretKey;
+    use dg_xch_cli::wallet_commands::{create_plotnft_launcher_spend, PLOTNFT_DELAY_TIME};
+    use dg_xch_clients::protocols::pool::{FARMING_TO_POOL, POOL_PROTOCOL_VERSION};
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, SizedBytes};
+    use dg_xch_core::consensus::constants::ConsensusConstants;
+    use dg_xch_core::plots::PlotNftExtraData;
+    use dg_xch_core::pool::PoolState;
+    use dg_xch_keys::master_sk_to_singleton_owner_sk;
+    use dg_xch_puzzles::clvm_puzzles::{
+        create_full_puzzle, launcher_coin_spend_to_extra_data, pool_state_to_inner_puzzle,
+        SINGLETON_LAUNCHER_HASH,
+    };
+    use dg_xch_serialize::hash_256;
+
+    let master_sk = SecretKey::key_gen(&[7u8; 32], &[]).unwrap();
+    let owner_sk = master_sk_to_singleton_owner_sk(&master_sk, 0).unwrap();
+    let delay_ph =
+        Bytes32::from("4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba");
+    let funding_coin = Coin {
+        parent_coin_info: Bytes32::from(
+            "ccd5bb71183532bff220ba46c268991a00000000000000000000000000000000",
+        ),
+        puzzle_hash: delay_ph,
+        amount: 1_000_000,
+    };
+    let pool_state = PoolState {
+        owner_pubkey: Bytes48::from(owner_sk.sk_to_pk().to_bytes()),
+        pool_url: Some("https://pool.example.com".to_string()),
+        relative_lock_height: 100,
+        state: FARMING_TO_POOL,
+        target_puzzle_hash: Bytes32::from(
+            "6bde1e0c6f9d3b93dc5e7e878723257ede573deeed59e3b4a90f5c86de1a0bd3",
+        ),
+        version: POOL_PROTOCOL_VERSION,
+    };
+    let extra_data = PlotNftExtraData {
+        pool_state: pool_state.clone(),
+        delay_time: PLOTNFT_DELAY_TIME,
+        delay_puzzle_hash: delay_ph,
+    };
+    let constants = ConsensusConstants::default();
+    let (launcher_id, announcement, launcher_spend) =
+        create_plotnft_launcher_spend(&funding_coin, &extra_data, &constants.genesis_challenge)
+            .unwrap();
+
+    assert_eq!(launcher_spend.coin.name(), launcher_id);
+    assert_eq!(launcher_spend.coin.parent_coin_info, funding_coin.name());
+    assert_eq!(launcher_spend.coin.puzzle_hash, *SINGLETON_LAUNCHER_HASH);
+    assert_eq!(launcher_spend.coin.amount, 1);
+
+    let parsed = launcher_coin_spend_to_extra_data(&launcher_spend).unwrap();
+    assert_eq!(parsed.pool_state, pool_state);
+    assert_eq!(parsed.delay_time, PLOTNFT_DELAY_TIME);
+    assert_eq!(parsed.delay_puzzle_hash, delay_ph);
+
+    let solution = launcher_spend.solution.to_program();
+    let inner_puzzle = pool_state_to_inner_puzzle(
+        &pool_state,
+        &launcher_id,
+        &constants.genesis_challenge,
+        PLOTNFT_DELAY_TIME as u64,
+        &delay_ph,
+    )
+    .unwrap();
+    let full_puzzle = create_full_puzzle(&inner_puzzle, &launcher_id).unwrap();
+    assert_eq!(
+        Bytes32::new(&solution.first().unwrap().as_vec().unwrap()),
+        full_puzzle.tree_hash()
+    );
+
+    let mut expected = launcher_id.as_slice().to_vec();
+    expected.extend(solution.tree_hash().as_slice());
+    assert_eq!(announcement.name(), Bytes32::new(&hash_256(expected)));
+}
+
+#[test]
+pub fn test_parse_payments_csv() {
+    use dg_xch_cli::wallet_commands::parse_payments_csv;
+    use dg_xch_core::blockchain::sized_bytes::Bytes32;
+    use dg_xch_keys::encode_puzzle_hash;
+
+    let first = Bytes32::from("4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba");
+    let second = Bytes32::from("6bde1e0c6f9d3b93dc5e7e878723257ede573deeed59e3b4a90f5c86de1a0bd3");
+    let csv = format!(
+        "address,amount\n{},1000\n\n# comment\n{}, 2500\n",
+        encode_puzzle_hash(&first, "xch").unwrap(),
+        second
+    );
+    let payments = parse_payments_csv(&csv).unwrap();
+    assert_eq!(payments.len(), 2);
+    assert_eq!(payments[0].puzzle_hash, first);
+    assert_eq!(payments[0].amount, 1000);
+    assert_eq!(payments[1].puzzle_hash, second);
+    assert_eq!(payments[1].amount, 2500);
+
+    assert!(parse_payments_csv("address,amount\n").is_err());
+    assert!(parse_payments_csv(&format!("{second}\n")).is_err());
+    assert!(parse_payments_csv(&format!("{second},ten\n")).is_err());
+}
+
+#[tokio::test]
+pub async fn test_wallet_rpc_commands() {
+    use crate::clients::mock_server::MockRpcServer;
+    use dg_xch_cli::commands::OutputFormat;
+    use dg_xch_cli::wallet_commands::{
+        get_transactions, get_wallet_balance, get_wallet_sync_status, log_in,
+        send_transaction_multi,
+    };
+    use dg_xch_clients::rpc::wallet::WalletClient;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    let puzzle_hash = "0x4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba";
+    let tx_id = "0x5e7b0f1bbd4c9a1e3f06cc20a98e4b37d7c6b4c8c8e4f2a7b58fa6f2d1c3e0a9";
+    let transaction = json!({
+        "confirmed_at_height": 120,
+        "created_at_time": 1_700_000_000u64,
+        "to_puzzle_hash": puzzle_hash,
+        "amount": 3500,
+        "fee_amount": 10,
+        "confirmed": true,
+        "sent": 0,
+        "spend_bundle": null,
+        "additions": [],
+        "removals": [],
+        "wallet_id": 1,
+        "sent_to": [],
+        "trade_id": null,
+        "type": 1,
+        "name": tx_id,
+        "memos": []
+    });
+    let mut responses = HashMap::new();
+    responses.insert(
+        "get_wallet_balance".to_string(),
+        json!({
+            "wallet_balance": {
+                "wallet_id": 1,
+                "pending_coin_removal_count": 0,
+                "unspent_coin_count": 3,
+                "confirmed_wallet_balance": 5000,
+                "max_send_amount": 5000,
+                "pending_change": 0,
+                "spendable_balance": 5000,
+                "unconfirmed_wallet_balance": 5000,
+                "fingerprint": 1234
+            },
+            "success": true
+        }),
+    );
+    responses.insert(
+        "send_transaction_multi".to_string(),
+        json!({"transaction": transaction, "transaction_id": tx_id, "success": true}),
+    );
+    responses.insert(
+        "get_transactions".to_string(),
+        json!({"transactions": [transaction], "wallet_id": 1, "success": true}),
+    );
+    responses.insert(
+        "get_sync_status".to_string(),
+        json!({"genesis_initialized": true, "synced": true, "syncing": false, "success": true}),
+    );
+    responses.insert(
+        "log_in".to_string(),
+        json!({"fingerprint": 1234, "success": true}),
+    );
+    let server = MockRpcServer::start(responses).await.unwrap();
+    let client = WalletClient::new(
+        "localhost",
+        server.port,
+        Some(server.ssl_path.clone()),
+        None,
+    );
+
+    let balance = get_wallet_balance(&client, 1, OutputFormat::Table)
+        .await
+        .unwrap();
+    assert!(balance.contains("Spendable Balance   | 5000"));
+    assert_eq!(
+        server.request_for("get_wallet_balance"),
+        Some(json!({"wallet_id": 1}))
+    );
+
+    let csv_path = std::env::temp_dir().join(format!("payouts_{}.csv", uuid::Uuid::new_v4()));
+    std::fs::write(
+        &csv_path,
+        format!("{puzzle_hash},1000\n{puzzle_hash},2500\n"),
+    )
+    .unwrap();
+    let sent = send_transaction_multi(
+        &client,
+        1,
+        csv_path.to_str().unwrap(),
+        10,
+        OutputFormat::Json,
+    )
+    .await
+    .unwrap();
+    std::fs::remove_file(&csv_path).unwrap();
+    let parsed: Value = serde_json::from_str(&sent).unwrap();
+    assert_eq!(parsed["amount"], json!(3500));
+    assert_eq!(
+        server.request_for("send_transaction_multi"),
+        Some(json!({
+            "wallet_id": 1,
+            "fee": 10,
+            "additions": [
+                {"puzzle_hash": puzzle_hash, "amount": 1000},
+                {"puzzle_hash": puzzle_hash, "amount": 2500}
+            ]
+        }))
+    );
+
+    let transactions = get_transactions(&client, 1, None, Some(10), OutputFormat::Table)
+        .await
+        .unwrap();
+    let lines = transactions.lines().collect::<Vec<&str>>();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[2].starts_with(tx_id));
+    assert_eq!(
+        server.request_for("get_transactions"),
+        Some(json!({"wallet_id": 1, "end": 10}))
+    );
+
+    let sync = get_wallet_sync_status(&client, OutputFormat::Table)
+        .await
+        .unwrap();
+    assert!(sync.contains("Synced              | true"));
+
+    assert_eq!(
+        log_in(&client, 1234, false).await.unwrap(),
+        "Logged in to wallet with fingerprint: 1234"
+    );
+}
@@ -0,0 +1,90 @@
+#[tokio::test]
+pub async fn test_crawler_and_timelord_clients() {
+    use crate::clients::mock_server::MockRpcServer;
+    use dg_xch_clients::api::crawler::CrawlerAPI;
+    use dg_xch_clients::api::timelord::TimelordAPI;
+    use dg_xch_clients::rpc::crawler::CrawlerClient;
+    use dg_xch_clients::rpc::timelord::TimelordClient;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    let mut responses = HashMap::new();
+    responses.insert(
+        "get_peer_counts".to_string(),
+        json!({
+            "peer_counts": {
+                "total_last_5_days": 4210,
+                "reliable_nodes": 1830,
+                "ipv4_last_5_days": 3900,
+                "ipv6_last_5_days": 310,
+                "versions": {"2.1.1": 1200, "2.0.0": 630}
+            },
+            "success": true
+        }),
+    );
+    responses.insert(
+        "get_ips_after_timestamp".to_string(),
+        json!({"ips": ["10.0.0.1", "10.0.0.2"], "total": 2, "success": true}),
+    );
+    responses.insert("healthz".to_string(), json!({"success": true}));
+    responses.insert(
+        "get_connections".to_string(),
+        json!({
+            "connections": [{
+                "type": 1,
+                "local_port": 8446,
+                "peer_host": "127.0.0.1",
+                "peer_port": 51234,
+                "peer_server_port": 8444,
+                "node_id": "0x9a4f3a2c1e7e5bd3b8d0d9d1c2e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4",
+                "creation_time": 1_700_000_000.5,
+                "bytes_read": 1024,
+                "bytes_written": 2048,
+                "last_message_time": 1_700_000_100.25
+            }],
+            "success": true
+        }),
+    );
+    responses.insert(
+        "get_routes".to_string(),
+        json!({"routes": ["/get_connections", "/healthz"], "success": true}),
+    );
+    let server = MockRpcServer::start(responses).await.unwrap();
+
+    let crawler = CrawlerClient::new(
+        "localhost",
+        server.port,
+        Some(server.ssl_path.clone()),
+        &None,
+    );
+    let peer_counts = crawler.get_peer_counts().await.unwrap();
+    assert_eq!(peer_counts.reliable_nodes, 1830);
+    assert_eq!(peer_counts.versions.get("2.1.1"), Some(&1200));
+    let ips = crawler
+        .get_ips_after_timestamp(1_700_000_000, None, Some(100))
+        .await
+        .unwrap();
+    assert_eq!(ips.total, 2);
+    assert_eq!(ips.ips, vec!["10.0.0.1", "10.0.0.2"]);
+    assert_eq!(
+        server.request_for("get_ips_after_timestamp"),
+        Some(json!({"after": 1_700_000_000u64, "limit": 100}))
+    );
+
+    let timelord = TimelordClient::new(
+        "localhost",
+        server.port,
+        Some(server.ssl_path.clone()),
+        &None,
+    );
+    assert!(timelord.healthz().await.unwrap());
+    let connections = timelord.get_connections().await.unwrap();
+    assert_eq!(connections.len(), 1);
+    assert_eq!(connections[0].node_type, 1);
+    assert_eq!(connections[0].peer_server_port, 8444);
+    assert_eq!(
+        timelord.get_routes().await.unwrap(),
+        vec!["/get_connections", "/healthz"]
+    );
+    assert!(timelord.get_network_info().await.is_err());
+}
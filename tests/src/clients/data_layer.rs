@@ -0,0 +1,143 @@
+#[tokio::test]
+pub async fn test_data_layer_client() {
+    use crate::clients::mock_server::MockRpcServer;
+    use dg_xch_clients::api::data_layer::DataLayerAPI;
+    use dg_xch_clients::protocols::data_layer::{DataStoreChange, DiffType};
+    use dg_xch_clients::rpc::data_layer::DataLayerClient;
+    use dg_xch_core::blockchain::sized_bytes::Bytes32;
+    use dg_xch_core::blockchain::unsized_bytes::UnsizedBytes;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    let store_id = "0x1a6de5b8fa2bfa3e7a1df2b4b7c4f3a50d1c0b3d41bb7e2f6d52d5e6e1a6f4c2";
+    let root_hash = "0x7c8e1c5b0a8aee1f63b5cbe3c4b2a4b8d6e8e5b2a3bd90f1a35e8d3f8c6e0b71";
+    let tx_id = "0x5e7b0f1bbd4c9a1e3f06cc20a98e4b37d7c6b4c8c8e4f2a7b58fa6f2d1c3e0a9";
+    let mut responses = HashMap::new();
+    responses.insert(
+        "create_data_store".to_string(),
+        json!({"id": store_id, "txs": [], "success": true}),
+    );
+    responses.insert(
+        "get_value".to_string(),
+        json!({"value": "0xbeef", "success": true}),
+    );
+    responses.insert(
+        "get_keys".to_string(),
+        json!({"keys": ["0x01", "0x02"], "success": true}),
+    );
+    responses.insert(
+        "get_keys_values".to_string(),
+        json!({
+            "keys_values": [{"hash": root_hash, "key": "0x01", "value": "0xbeef"}],
+            "success": true
+        }),
+    );
+    responses.insert(
+        "get_root".to_string(),
+        json!({"hash": root_hash, "confirmed": true, "timestamp": 1_700_000_000u64, "success": true}),
+    );
+    responses.insert(
+        "batch_update".to_string(),
+        json!({"tx_id": tx_id, "success": true}),
+    );
+    responses.insert(
+        "insert".to_string(),
+        json!({"tx_id": tx_id, "success": true}),
+    );
+    responses.insert("subscribe".to_string(), json!({"success": true}));
+    responses.insert(
+        "unsubscribe".to_string(),
+        json!({"success": false, "error": "Store not subscribed"}),
+    );
+    responses.insert(
+        "get_kv_diff".to_string(),
+        json!({
+            "diff": [{"type": "INSERT", "key": "0x03", "value": "0xcafe"}],
+            "success": true
+        }),
+    );
+    responses.insert(
+        "get_sync_status".to_string(),
+        json!({
+            "sync_status": {
+                "root_hash": root_hash,
+                "generation": 3,
+                "target_root_hash": root_hash,
+                "target_generation": 4
+            },
+            "success": true
+        }),
+    );
+    let server = MockRpcServer::start(responses).await.unwrap();
+    let client = DataLayerClient::new(
+        "localhost",
+        server.port,
+        Some(server.ssl_path.clone()),
+        &None,
+    );
+
+    let id = client.create_data_store(Some(100)).await.unwrap();
+    assert_eq!(id, Bytes32::from(store_id));
+    assert_eq!(
+        server.request_for("create_data_store"),
+        Some(json!({"fee": 100}))
+    );
+
+    let key = UnsizedBytes::from("0x01");
+    let value = client.get_value(&id, &key, None).await.unwrap();
+    assert_eq!(value, Some(UnsizedBytes::from("beef")));
+    assert_eq!(
+        server.request_for("get_value"),
+        Some(json!({"id": store_id, "key": "0x01"}))
+    );
+
+    let root = Bytes32::from(root_hash);
+    let keys = client.get_keys(&id, Some(&root)).await.unwrap();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(
+        server.request_for("get_keys"),
+        Some(json!({"id": store_id, "root_hash": root_hash}))
+    );
+    let keys_values = client.get_keys_values(&id, None).await.unwrap();
+    assert_eq!(keys_values[0].value, UnsizedBytes::from("0xbeef"));
+
+    let store_root = client.get_root(&id).await.unwrap();
+    assert_eq!(store_root.hash, root);
+    assert!(store_root.confirmed);
+
+    let changelist = vec![
+        DataStoreChange::insert(UnsizedBytes::from("0x03"), UnsizedBytes::from("0xcafe")),
+        DataStoreChange::delete(UnsizedBytes::from("0x02")),
+    ];
+    assert_eq!(
+        client.batch_update(&id, &changelist, None).await.unwrap(),
+        Bytes32::from(tx_id)
+    );
+    assert_eq!(
+        server.request_for("batch_update"),
+        Some(json!({
+            "id": store_id,
+            "changelist": [
+                {"action": "insert", "key": "0x03", "value": "0xcafe"},
+                {"action": "delete", "key": "0x02"}
+            ]
+        }))
+    );
+    client
+        .insert(&id, &UnsizedBytes::from("0x04"), &key, Some(1))
+        .await
+        .unwrap();
+
+    client
+        .subscribe(&id, &["http://localhost:8575".to_string()])
+        .await
+        .unwrap();
+    assert!(client.unsubscribe(&id, false).await.is_err());
+
+    let diff = client.get_kv_diff(&id, &root, &root).await.unwrap();
+    assert_eq!(diff[0].diff_type, DiffType::Insert);
+
+    let sync_status = client.get_sync_status(&id).await.unwrap();
+    assert_eq!(sync_status.generation, 3);
+    assert_eq!(sync_status.target_generation, 4);
+}
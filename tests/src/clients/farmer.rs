@@ -0,0 +1,201 @@
+#[tokio::test]
+pub async fn test_farmer_client() {
+    use crate::clients::mock_server::MockRpcServer;
+    use dg_xch_clients::api::farmer::FarmerAPI;
+    use dg_xch_clients::protocols::farmer::RewardTargets;
+    use dg_xch_clients::rpc::farmer::FarmerClient;
+    use dg_xch_core::blockchain::sized_bytes::Bytes32;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    let launcher_id =
+        Bytes32::from("ae4ef3b9bfe68949691281a015a9c16630fc8f66d48c19ca548fb80768791afa");
+    let connection = json!({
+        "node_id": "0x3e9ef4fb6d6c0b3a5fb4ec7a6bd3f8ee24b7e2f4fa0d0f0da9e20eb43a5ecf1d",
+        "host": "192.168.1.20",
+        "port": 8448
+    });
+    let signage_point = json!({
+        "challenge_hash": "0x2a6a1ba4b4a5e1bb7d4fb0c0d2cfd5c7e3a4e1d0c9b8a7f6e5d4c3b2a1908070",
+        "challenge_chain_sp": "0x1111111111111111111111111111111111111111111111111111111111111111",
+        "reward_chain_sp": "0x2222222222222222222222222222222222222222222222222222222222222222",
+        "difficulty": 2048,
+        "sub_slot_iters": 147849216,
+        "signage_point_index": 12
+    });
+    let mut responses = HashMap::new();
+    responses.insert(
+        "get_signage_point".to_string(),
+        json!({"signage_point": signage_point, "proofs": [], "success": true}),
+    );
+    responses.insert(
+        "get_signage_points".to_string(),
+        json!({
+            "signage_points": [{"signage_point": signage_point, "proofs": []}],
+            "success": true
+        }),
+    );
+    responses.insert(
+        "get_reward_targets".to_string(),
+        json!({
+            "farmer_target": "xch1farmer",
+            "pool_target": "xch1pool",
+            "have_farmer_sk": true,
+            "have_pool_sk": false,
+            "success": true
+        }),
+    );
+    responses.insert("set_reward_targets".to_string(), json!({"success": true}));
+    responses.insert(
+        "get_pool_state".to_string(),
+        json!({
+            "pool_state": [{
+                "p2_singleton_puzzle_hash": "0x4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba",
+                "points_found_since_start": 30,
+                "points_found_24h": [[1_700_000_000.5, 10]],
+                "points_acknowledged_since_start": 20,
+                "points_acknowledged_24h": [[1_700_000_000.5, 10]],
+                "next_farmer_update": 1_700_000_300.0,
+                "next_pool_info_update": 1_700_000_600.0,
+                "current_points": 20,
+                "current_difficulty": 10,
+                "pool_errors_24h": [[1_700_000_100.0, {"error_code": 5, "error_message": "Too late"}]],
+                "authentication_token_timeout": 5,
+                "pool_config": {
+                    "launcher_id": launcher_id,
+                    "pool_url": "https://pool.example.com",
+                    "payout_instructions": "4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba",
+                    "target_puzzle_hash": "0x4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba",
+                    "p2_singleton_puzzle_hash": "0x4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba",
+                    "owner_public_key": "0xa6c9c3ba8d1bd5e6c3f0f7e0d4ad4b0e1f7e3b6a8e2e2f6d9f0a51ae24d1c9f3c2b1e7f4f0d8c5b2e6c3a9a7e1c5d3f2b4"
+                }
+            }],
+            "success": true
+        }),
+    );
+    responses.insert(
+        "set_payout_instructions".to_string(),
+        json!({"success": false, "error": "Launcher not found"}),
+    );
+    responses.insert(
+        "get_harvesters".to_string(),
+        json!({
+            "harvesters": [{
+                "connection": connection,
+                "plots": [],
+                "failed_to_open_filenames": [],
+                "no_key_filenames": ["/plots/nokey.plot"],
+                "duplicates": [],
+                "total_plot_size": 108_000_000_000u64,
+                "syncing": {"initial": false, "plot_files_processed": 1, "plot_files_total": 1},
+                "last_sync_time": 1_700_000_000.0,
+                "harvesting_mode": 1
+            }],
+            "success": true
+        }),
+    );
+    responses.insert(
+        "get_harvesters_summary".to_string(),
+        json!({
+            "harvesters": [{
+                "connection": connection,
+                "plots": 1,
+                "failed_to_open_filenames": 0,
+                "no_key_filenames": 1,
+                "duplicates": 0,
+                "total_plot_size": 108_000_000_000u64,
+                "total_effective_plot_size": 108_000_000_000u64,
+                "syncing": null,
+                "last_sync_time": null,
+                "harvesting_mode": null
+            }],
+            "success": true
+        }),
+    );
+    responses.insert(
+        "get_pool_login_link".to_string(),
+        json!({"login_link": "https://pool.example.com/login?launcher_id=ae4e", "success": true}),
+    );
+    let server = MockRpcServer::start(responses).await.unwrap();
+    let client = FarmerClient::new(
+        "localhost",
+        server.port,
+        Some(server.ssl_path.clone()),
+        &None,
+    );
+
+    let sp_hash = Bytes32::from("1111111111111111111111111111111111111111111111111111111111111111");
+    let signage_point = client.get_signage_point(&sp_hash).await.unwrap();
+    assert_eq!(signage_point.signage_point.challenge_chain_sp, sp_hash);
+    assert_eq!(signage_point.signage_point.signage_point_index, 12);
+    assert!(signage_point.proofs.is_empty());
+    assert_eq!(
+        server.request_for("get_signage_point"),
+        Some(json!({ "sp_hash": sp_hash }))
+    );
+    assert_eq!(
+        client.get_signage_points().await.unwrap(),
+        vec![signage_point]
+    );
+
+    assert_eq!(
+        client.get_reward_targets(true, Some(500)).await.unwrap(),
+        RewardTargets {
+            farmer_target: "xch1farmer".to_string(),
+            pool_target: "xch1pool".to_string(),
+            have_farmer_sk: Some(true),
+            have_pool_sk: Some(false),
+        }
+    );
+    assert_eq!(
+        server.request_for("get_reward_targets"),
+        Some(json!({"search_for_private_key": true, "max_ph_to_search": 500}))
+    );
+    client
+        .set_reward_targets(Some("xch1farmer".to_string()), None)
+        .await
+        .unwrap();
+    assert_eq!(
+        server.request_for("set_reward_targets"),
+        Some(json!({"farmer_target": "xch1farmer"}))
+    );
+
+    let pool_state = client.get_pool_state().await.unwrap();
+    assert_eq!(pool_state.len(), 1);
+    assert_eq!(pool_state[0].points_found_24h, vec![(1_700_000_000.5, 10)]);
+    assert_eq!(pool_state[0].current_difficulty, Some(10));
+    assert_eq!(pool_state[0].pool_errors_24h[0].1.error_code, 5);
+    assert_eq!(pool_state[0].plot_count, 0);
+    assert_eq!(pool_state[0].pool_config.launcher_id, launcher_id);
+
+    let err = client
+        .set_payout_instructions(&launcher_id, "xch1payout")
+        .await
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("Launcher not found"));
+    assert_eq!(
+        server.request_for("set_payout_instructions"),
+        Some(json!({"launcher_id": launcher_id, "payout_instructions": "xch1payout"}))
+    );
+
+    let harvesters = client.get_harvesters().await.unwrap();
+    assert_eq!(harvesters.len(), 1);
+    assert_eq!(harvesters[0].connection.port, 8448);
+    assert_eq!(harvesters[0].no_key_filenames, vec!["/plots/nokey.plot"]);
+    assert_eq!(harvesters[0].total_effective_plot_size, 0);
+    assert_eq!(harvesters[0].syncing.as_ref().unwrap().plot_files_total, 1);
+    let summaries = client.get_harvesters_summary().await.unwrap();
+    assert_eq!(summaries[0].plots, 1);
+    assert_eq!(summaries[0].syncing, None);
+    assert_eq!(summaries[0].connection, harvesters[0].connection);
+
+    assert_eq!(
+        client.get_pool_login_link(&launcher_id).await.unwrap(),
+        "https://pool.example.com/login?launcher_id=ae4e"
+    );
+    assert_eq!(
+        server.request_for("get_pool_login_link"),
+        Some(json!({ "launcher_id": launcher_id }))
+    );
+}
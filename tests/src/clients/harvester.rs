@@ -0,0 +1,118 @@
+#[tokio::test]
+pub async fn test_harvester_client() {
+    use crate::clients::mock_server::MockRpcServer;
+    use dg_xch_clients::api::harvester::HarvesterAPI;
+    use dg_xch_clients::protocols::harvester::HarvesterConfig;
+    use dg_xch_clients::rpc::harvester::HarvesterClient;
+    use dg_xch_core::blockchain::sized_bytes::Bytes32;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    let config = json!({
+        "use_gpu_harvesting": false,
+        "gpu_index": 0,
+        "enforce_gpu_index": false,
+        "disable_cpu_affinity": false,
+        "parallel_decompressor_count": 1,
+        "decompressor_thread_count": 4,
+        "recursive_plot_scan": true,
+        "refresh_parameter_interval_seconds": 120,
+        "success": true
+    });
+    let mut responses = HashMap::new();
+    responses.insert(
+        "get_plots".to_string(),
+        json!({
+            "plots": [{
+                "filename": "/plots/plot-k32-test.plot",
+                "size": 32,
+                "plot_id": "0x3e9ef4fb6d6c0b3a5fb4ec7a6bd3f8ee24b7e2f4fa0d0f0da9e20eb43a5ecf1d",
+                "pool_public_key": null,
+                "pool_contract_puzzle_hash": "0x4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba",
+                "plot_public_key": "0xa6c9c3ba8d1bd5e6c3f0f7e0d4ad4b0e1f7e3b6a8e2e2f6d9f0a51ae24d1c9f3c2b1e7f4f0d8c5b2e6c3a9a7e1c5d3f2b4",
+                "file_size": 108_000_000_000u64,
+                "time_modified": 1_700_000_000u64,
+                "compression_level": 0
+            }],
+            "failed_to_open_filenames": [],
+            "not_found_filenames": ["/plots/missing.plot"],
+            "success": true
+        }),
+    );
+    responses.insert("refresh_plots".to_string(), json!({"success": true}));
+    responses.insert("delete_plot".to_string(), json!({"success": true}));
+    responses.insert("add_plot_directory".to_string(), json!({"success": true}));
+    responses.insert(
+        "remove_plot_directory".to_string(),
+        json!({"success": false, "error": "Path not found"}),
+    );
+    responses.insert(
+        "get_plot_directories".to_string(),
+        json!({"directories": ["/plots", "/plots2"], "success": true}),
+    );
+    responses.insert("get_harvester_config".to_string(), config);
+    responses.insert(
+        "update_harvester_config".to_string(),
+        json!({"success": true}),
+    );
+    let server = MockRpcServer::start(responses).await.unwrap();
+    let client = HarvesterClient::new(
+        "localhost",
+        server.port,
+        Some(server.ssl_path.clone()),
+        &None,
+    );
+
+    let plots = client.get_plots().await.unwrap();
+    assert_eq!(plots.plots.len(), 1);
+    assert_eq!(plots.plots[0].size, 32);
+    assert_eq!(plots.plots[0].pool_public_key, None);
+    assert_eq!(
+        plots.plots[0].pool_contract_puzzle_hash,
+        Some(Bytes32::from(
+            "4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba"
+        ))
+    );
+    assert_eq!(plots.not_found_filenames, vec!["/plots/missing.plot"]);
+
+    client.refresh_plots().await.unwrap();
+    client.delete_plot("/plots/old.plot").await.unwrap();
+    assert_eq!(
+        server.request_for("delete_plot"),
+        Some(json!({"filename": "/plots/old.plot"}))
+    );
+    client.add_plot_directory("/plots3").await.unwrap();
+    assert_eq!(
+        server.request_for("add_plot_directory"),
+        Some(json!({"dirname": "/plots3"}))
+    );
+    assert!(client.remove_plot_directory("/plots4").await.is_err());
+    assert_eq!(
+        client.get_plot_directories().await.unwrap(),
+        vec!["/plots", "/plots2"]
+    );
+
+    let mut harvester_config = client.get_harvester_config().await.unwrap();
+    assert_eq!(
+        harvester_config,
+        HarvesterConfig {
+            use_gpu_harvesting: false,
+            gpu_index: 0,
+            enforce_gpu_index: false,
+            disable_cpu_affinity: false,
+            parallel_decompressor_count: 1,
+            decompressor_thread_count: 4,
+            recursive_plot_scan: true,
+            refresh_parameter_interval_seconds: 120,
+        }
+    );
+    harvester_config.use_gpu_harvesting = true;
+    client
+        .update_harvester_config(&harvester_config)
+        .await
+        .unwrap();
+    assert_eq!(
+        server.request_for("update_harvester_config").unwrap()["use_gpu_harvesting"],
+        json!(true)
+    );
+}
@@ -0,0 +1,126 @@
+use dg_xch_clients::protocols::shared::{load_certs_from_bytes, load_private_key_from_bytes};
+use dg_xch_core::ssl::{generate_ca_signed_cert_data, CHIA_CA_CRT, CHIA_CA_KEY};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response, StatusCode};
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{RootCertStore, ServerConfig};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::io::{Error, ErrorKind};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+use uuid::Uuid;
+
+pub type RecordedRequests = Arc<Mutex<Vec<(String, Value)>>>;
+
+//Minimal HTTPS RPC server that replies to each endpoint with a canned JSON body.
+//Client certificates must be signed by the Chia CA, same as a real node.
+//The generated ssl directory is removed when the server is dropped.
+pub struct MockRpcServer {
+    pub port: u16,
+    pub ssl_path: String,
+    pub requests: RecordedRequests,
+    handle: JoinHandle<()>,
+}
+impl MockRpcServer {
+    pub async fn start(responses: HashMap<String, Value>) -> Result<Self, Error> {
+        let (cert_data, key_data) = generate_ca_signed_cert_data(CHIA_CA_CRT, CHIA_CA_KEY)?;
+        let ssl_path = create_daemon_ssl_dir(&cert_data, &key_data)?;
+        let mut roots = RootCertStore::empty();
+        for cert in load_certs_from_bytes(CHIA_CA_CRT.as_bytes())? {
+            roots
+                .add(&cert)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{:?}", e)))?;
+        }
+        let config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+            .with_single_cert(
+                load_certs_from_bytes(cert_data.as_bytes())?,
+                load_private_key_from_bytes(key_data.as_bytes())?,
+            )
+            .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{:?}", e)))?;
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        let responses = Arc::new(responses);
+        let requests: RecordedRequests = Default::default();
+        let handle_requests = requests.clone();
+        let handle = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                let responses = responses.clone();
+                let requests = handle_requests.clone();
+                tokio::spawn(async move {
+                    if let Ok(stream) = acceptor.accept(stream).await {
+                        let service = service_fn(move |req| {
+                            handle_request(req, responses.clone(), requests.clone())
+                        });
+                        let _ = Http::new().serve_connection(stream, service).await;
+                    }
+                });
+            }
+        });
+        Ok(MockRpcServer {
+            port,
+            ssl_path: ssl_path.to_string_lossy().to_string(),
+            requests,
+            handle,
+        })
+    }
+
+    pub fn request_for(&self, endpoint: &str) -> Option<Value> {
+        self.requests
+            .lock()
+            .ok()?
+            .iter()
+            .rev()
+            .find(|(e, _)| e == endpoint)
+            .map(|(_, v)| v.clone())
+    }
+}
+impl Drop for MockRpcServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+        let _ = remove_dir_all(&self.ssl_path);
+    }
+}
+
+async fn handle_request(
+    req: Request<Body>,
+    responses: Arc<HashMap<String, Value>>,
+    requests: RecordedRequests,
+) -> Result<Response<Body>, Infallible> {
+    let endpoint = req.uri().path().trim_start_matches('/').to_string();
+    let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .unwrap_or_default();
+    let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+    if let Ok(mut requests) = requests.lock() {
+        requests.push((endpoint.clone(), body));
+    }
+    Ok(match responses.get(&endpoint) {
+        Some(resp) => Response::new(Body::from(resp.to_string())),
+        None => {
+            let mut resp = Response::new(Body::empty());
+            *resp.status_mut() = StatusCode::NOT_FOUND;
+            resp
+        }
+    })
+}
+
+//Writes a CA signed daemon cert/key pair in the layout rpc::get_client expects
+fn create_daemon_ssl_dir(cert_data: &str, key_data: &str) -> Result<PathBuf, Error> {
+    let ssl_path = std::env::temp_dir().join(format!("dg_xch_mock_ssl_{}", Uuid::new_v4()));
+    let daemon_path = ssl_path.join("daemon");
+    create_dir_all(&daemon_path)?;
+    write(daemon_path.join("private_daemon.crt"), cert_data)?;
+    write(daemon_path.join("private_daemon.key"), key_data)?;
+    Ok(ssl_path)
+}
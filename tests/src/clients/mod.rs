@@ -1 +1,12 @@
+pub mod crawler;
+pub mod data_layer;
+pub mod farmer;
 pub mod full_node;
+pub mod harvester;
+pub mod mock_server;
+pub mod pool_client;
+pub mod pool_server;
+pub mod server;
+pub mod supervised;
+pub mod wallet;
+pub mod wallet_sync;
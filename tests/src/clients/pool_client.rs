@@ -0,0 +1,167 @@
+//Plain HTTP pool that fails the first `failures` partials with a 503 and can
+//be told to stall before answering
+#[cfg(test)]
+async fn start_pool(
+    failures: usize,
+    delay: std::time::Duration,
+) -> (
+    String,
+    std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    tokio::task::JoinHandle<()>,
+) {
+    use hyper::server::conn::Http;
+    use hyper::service::service_fn;
+    use hyper::{Body, Request, Response, StatusCode};
+    use std::convert::Infallible;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let hits = Arc::new(AtomicUsize::new(0));
+    let server_hits = hits.clone();
+    let handle = tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let hits = server_hits.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |req: Request<Body>| {
+                    let hits = hits.clone();
+                    async move {
+                        let hit = hits.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(delay).await;
+                        let (status, body) = match req.uri().path() {
+                            "/partial" if hit < failures => {
+                                (StatusCode::SERVICE_UNAVAILABLE, String::new())
+                            }
+                            "/partial" => (StatusCode::OK, r#"{"new_difficulty":5}"#.to_string()),
+                            _ => (StatusCode::BAD_REQUEST, String::new()),
+                        };
+                        Ok::<_, Infallible>(
+                            Response::builder()
+                                .status(status)
+                                .body(Body::from(body))
+                                .unwrap(),
+                        )
+                    }
+                });
+                let _ = Http::new().serve_connection(stream, service).await;
+            });
+        }
+    });
+    (url, hits, handle)
+}
+
+#[cfg(test)]
+fn partial() -> dg_xch_clients::protocols::pool::PostPartialRequest {
+    use dg_xch_clients::protocols::pool::{PostPartialPayload, PostPartialRequest};
+    use dg_xch_core::blockchain::proof_of_space::ProofOfSpace;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, Bytes96};
+    PostPartialRequest {
+        payload: PostPartialPayload {
+            launcher_id: Bytes32::default(),
+            authentication_token: 0,
+            proof_of_space: ProofOfSpace {
+                challenge: Bytes32::default(),
+                pool_public_key: None,
+                pool_contract_puzzle_hash: Some(Bytes32::default()),
+                plot_public_key: Bytes48::default(),
+                size: 32,
+                proof: vec![0u8; 256].into(),
+            },
+            sp_hash: Bytes32::default(),
+            end_of_sub_slot: false,
+            harvester_id: Bytes32::default(),
+        },
+        aggregate_signature: Bytes96::default(),
+    }
+}
+
+#[tokio::test]
+pub async fn test_pool_client_retries() {
+    use dg_xch_clients::api::pool::{DefaultPoolClient, PoolClient};
+    use dg_xch_clients::protocols::pool::PoolErrorCode;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    //Two 503s are retried and the partial is accepted on the third attempt
+    let (url, hits, handle) = start_pool(2, Duration::ZERO).await;
+    let client = DefaultPoolClient::builder()
+        .max_retries(2)
+        .retry_backoff(Duration::from_millis(10), Duration::from_millis(20))
+        .build()
+        .unwrap();
+    assert!(!client.config.accept_invalid_certs);
+    let resp = client.post_partial(&url, partial(), &None).await.unwrap();
+    assert_eq!(resp.new_difficulty, 5);
+    assert_eq!(hits.load(Ordering::SeqCst), 3);
+    let metrics = client.partial_metrics(&url).unwrap();
+    assert_eq!((metrics.accepted, metrics.submitted()), (1, 1));
+    assert!(metrics.average_latency().unwrap() >= Duration::from_millis(20));
+
+    //Retries are bounded and the last status is reported
+    let client = DefaultPoolClient::builder()
+        .max_retries(1)
+        .retry_backoff(Duration::from_millis(1), Duration::from_millis(1))
+        .build()
+        .unwrap();
+    hits.store(0, Ordering::SeqCst);
+    let err = client
+        .post_partial(&url, partial(), &None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.error_code, PoolErrorCode::RequestFailed as u8);
+    assert!(err.error_message.contains("503"));
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+    //Client errors are not retried
+    hits.store(0, Ordering::SeqCst);
+    assert!(client.get_pool_info(&url).await.is_err());
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+    let metrics = client.partial_metrics(&url).unwrap();
+    assert_eq!((metrics.failed, metrics.accepted), (1, 0));
+    assert!(metrics.average_latency().is_none());
+    handle.abort();
+}
+
+#[tokio::test]
+pub async fn test_pool_client_timeout_and_rate_limit() {
+    use dg_xch_clients::api::pool::{DefaultPoolClient, PoolClient, PoolClientConfig};
+    use std::sync::atomic::Ordering;
+    use std::time::{Duration, Instant};
+
+    //A stalled pool is abandoned after the request timeout
+    let (url, hits, handle) = start_pool(0, Duration::from_secs(5)).await;
+    let client = DefaultPoolClient::builder()
+        .request_timeout(Duration::from_millis(100))
+        .max_retries(1)
+        .retry_backoff(Duration::from_millis(10), Duration::from_millis(10))
+        .build()
+        .unwrap();
+    let start = Instant::now();
+    assert!(client.post_partial(&url, partial(), &None).await.is_err());
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+    handle.abort();
+
+    //Requests to the same pool are spaced by the rate limit
+    let (url, _hits, handle) = start_pool(0, Duration::ZERO).await;
+    let client = DefaultPoolClient::builder().rate_limit(10).build().unwrap();
+    let start = Instant::now();
+    for _ in 0..4 {
+        client.post_partial(&url, partial(), &None).await.unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(300));
+    assert_eq!(client.partial_metrics(&url).unwrap().accepted, 4);
+    assert_eq!(client.all_partial_metrics().len(), 1);
+    handle.abort();
+
+    //Backoff doubles up to the configured maximum
+    let config = PoolClientConfig {
+        retry_backoff: Duration::from_millis(100),
+        max_retry_backoff: Duration::from_millis(300),
+        ..Default::default()
+    };
+    assert_eq!(config.backoff(1), Duration::from_millis(100));
+    assert_eq!(config.backoff(2), Duration::from_millis(200));
+    assert_eq!(config.backoff(3), Duration::from_millis(300));
+}
@@ -0,0 +1,410 @@
+#[cfg(test)]
+fn test_farmer(
+    launcher_id: dg_xch_core::blockchain::sized_bytes::Bytes32,
+    auth_key: &blst::min_pk::SecretKey,
+) -> dg_xch_core::pool::Farmer {
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::coin_spend::CoinSpend;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, SizedBytes};
+    use dg_xch_core::clvm::program::SerializedProgram;
+    use dg_xch_core::pool::{Farmer, PoolState};
+
+    Farmer {
+        launcher_id,
+        p2_singleton_puzzle_hash: Bytes32::new(&[2u8; 32]),
+        delay_time: 604800,
+        delay_puzzle_hash: Bytes32::new(&[3u8; 32]),
+        authentication_public_key: Bytes48::new(&auth_key.sk_to_pk().to_bytes()),
+        singleton_tip: CoinSpend {
+            coin: Coin {
+                parent_coin_info: Bytes32::default(),
+                puzzle_hash: Bytes32::default(),
+                amount: 1,
+            },
+            puzzle_reveal: SerializedProgram::from_bytes(&[0x80]),
+            solution: SerializedProgram::from_bytes(&[0x80]),
+        },
+        singleton_tip_state: PoolState {
+            version: 1,
+            state: 3,
+            target_puzzle_hash: Bytes32::new(&[4u8; 32]),
+            owner_pubkey: Bytes48::default(),
+            pool_url: None,
+            relative_lock_height: 100,
+        },
+        balance: 0,
+        points: 0,
+        difficulty: 1,
+        payout_instructions: String::new(),
+        is_pool_member: true,
+        joined: 0,
+        modified: 0,
+    }
+}
+
+#[cfg(test)]
+fn signage_point(
+    challenge: dg_xch_core::blockchain::sized_bytes::Bytes32,
+    time_received: f64,
+) -> dg_xch_core::blockchain::signage_point_or_eos::SignagePointOrEOS {
+    use dg_xch_core::blockchain::class_group_element::ClassgroupElement;
+    use dg_xch_core::blockchain::signage_point::SignagePoint;
+    use dg_xch_core::blockchain::signage_point_or_eos::SignagePointOrEOS;
+    use dg_xch_core::blockchain::sized_bytes::Bytes100;
+    use dg_xch_core::blockchain::unsized_bytes::UnsizedBytes;
+    use dg_xch_core::blockchain::vdf_info::VdfInfo;
+    use dg_xch_core::blockchain::vdf_proof::VdfProof;
+
+    let vdf = VdfInfo {
+        challenge,
+        number_of_iterations: 0,
+        output: ClassgroupElement {
+            data: Bytes100::default(),
+        },
+    };
+    let proof = VdfProof {
+        witness_type: 0,
+        witness: UnsizedBytes::default(),
+        normalized_to_identity: false,
+    };
+    SignagePointOrEOS {
+        signage_point: Some(SignagePoint {
+            cc_vdf: vdf.clone(),
+            cc_proof: proof.clone(),
+            rc_vdf: vdf,
+            rc_proof: proof,
+        }),
+        eos: None,
+        time_received,
+        reverted: false,
+    }
+}
+
+#[test]
+pub fn test_partial_validator() {
+    use blst::min_pk::{AggregateSignature, SecretKey};
+    use dg_xch_clients::protocols::pool::{
+        get_current_authentication_token, PoolErrorCode, PostPartialPayload, PostPartialRequest,
+    };
+    use dg_xch_clients::protocols::pool_server::PartialValidator;
+    use dg_xch_core::blockchain::proof_of_space::ProofOfSpace;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, Bytes96, SizedBytes};
+    use dg_xch_core::clvm::bls_bindings::sign;
+    use dg_xch_core::consensus::constants::MAINNET;
+    use dg_xch_serialize::{hash_256, ChiaSerialize};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let plot_key = SecretKey::key_gen(&[5u8; 32], &[]).unwrap();
+    let auth_key = SecretKey::key_gen(&[6u8; 32], &[]).unwrap();
+    let launcher_id = Bytes32::new(&[1u8; 32]);
+    let farmer = test_farmer(launcher_id, &auth_key);
+    let validator = PartialValidator::new(MAINNET.clone(), 5);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let payload = PostPartialPayload {
+        launcher_id,
+        authentication_token: get_current_authentication_token(5),
+        proof_of_space: ProofOfSpace {
+            challenge: Bytes32::new(&[7u8; 32]),
+            pool_public_key: None,
+            pool_contract_puzzle_hash: Some(farmer.p2_singleton_puzzle_hash),
+            plot_public_key: Bytes48::new(&plot_key.sk_to_pk().to_bytes()),
+            size: 32,
+            proof: vec![0u8; 256].into(),
+        },
+        sp_hash: Bytes32::new(&[8u8; 32]),
+        end_of_sub_slot: false,
+        harvester_id: Bytes32::new(&[9u8; 32]),
+    };
+    let sign_payload = |payload: PostPartialPayload| {
+        let message = hash_256(payload.to_bytes());
+        let signature = AggregateSignature::aggregate(
+            &[&sign(&plot_key, &message), &sign(&auth_key, &message)],
+            true,
+        )
+        .unwrap();
+        PostPartialRequest {
+            payload,
+            aggregate_signature: Bytes96::new(&signature.to_signature().to_bytes()),
+        }
+    };
+    let partial = sign_payload(payload.clone());
+    let sp = signage_point(Bytes32::new(&[10u8; 32]), now as f64);
+    let error_code = |partial: &PostPartialRequest, sp| {
+        validator
+            .validate(partial, &farmer, sp, now)
+            .unwrap_err()
+            .error_code
+    };
+    assert!(validator.validate_signature(&partial, &farmer).is_ok());
+    //The proof does not match the signage point's challenge
+    assert_eq!(
+        error_code(&partial, Some(&sp)),
+        PoolErrorCode::InvalidProof as u8
+    );
+
+    //Signed with only one of the two keys
+    let mut bad_signature = partial.clone();
+    bad_signature.aggregate_signature =
+        Bytes96::new(&sign(&plot_key, &hash_256(payload.to_bytes())).to_bytes());
+    assert_eq!(
+        error_code(&bad_signature, Some(&sp)),
+        PoolErrorCode::InvalidSignature as u8
+    );
+
+    let mut other = payload.clone();
+    other.launcher_id = Bytes32::new(&[11u8; 32]);
+    assert_eq!(
+        error_code(&sign_payload(other), Some(&sp)),
+        PoolErrorCode::FarmerNotKnown as u8
+    );
+    let mut other = payload.clone();
+    other.authentication_token -= 10;
+    assert_eq!(
+        error_code(&sign_payload(other), Some(&sp)),
+        PoolErrorCode::InvalidAuthenticationToken as u8
+    );
+    let mut other = payload.clone();
+    other.proof_of_space.pool_contract_puzzle_hash = Some(Bytes32::new(&[12u8; 32]));
+    assert_eq!(
+        error_code(&sign_payload(other), Some(&sp)),
+        PoolErrorCode::InvalidP2SingletonPuzzleHash as u8
+    );
+
+    //Signage point freshness
+    assert_eq!(error_code(&partial, None), PoolErrorCode::NotFound as u8);
+    let mut reverted = sp.clone();
+    reverted.reverted = true;
+    assert_eq!(
+        error_code(&partial, Some(&reverted)),
+        PoolErrorCode::NotFound as u8
+    );
+    let stale = signage_point(Bytes32::new(&[10u8; 32]), (now - 60) as f64);
+    assert_eq!(
+        error_code(&partial, Some(&stale)),
+        PoolErrorCode::TooLate as u8
+    );
+}
+
+#[cfg(test)]
+fn test_plot_nft(
+    launcher_id: dg_xch_core::blockchain::sized_bytes::Bytes32,
+    owner_key: &blst::min_pk::SecretKey,
+) -> dg_xch_core::plots::PlotNft {
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::coin_record::CoinRecord;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, SizedBytes};
+    use dg_xch_core::plots::PlotNft;
+    use dg_xch_core::pool::PoolState;
+
+    PlotNft {
+        launcher_id,
+        singleton_coin: CoinRecord {
+            coin: Coin {
+                parent_coin_info: launcher_id,
+                puzzle_hash: Bytes32::default(),
+                amount: 1,
+            },
+            confirmed_block_index: 1,
+            spent_block_index: 0,
+            timestamp: 0,
+            coinbase: false,
+            spent: false,
+        },
+        pool_state: PoolState {
+            version: 1,
+            state: 3,
+            target_puzzle_hash: Bytes32::new(&[4u8; 32]),
+            owner_pubkey: Bytes48::new(&owner_key.sk_to_pk().to_bytes()),
+            pool_url: Some(String::from("https://pool.example")),
+            relative_lock_height: 100,
+        },
+        delay_time: 604800,
+        delay_puzzle_hash: Bytes32::new(&[3u8; 32]),
+    }
+}
+
+#[test]
+pub fn test_farmer_request_verification() {
+    use blst::min_pk::SecretKey;
+    use dg_xch_clients::protocols::pool::{
+        get_current_authentication_token, PoolErrorCode, PostFarmerPayload, PostFarmerRequest,
+        PutFarmerPayload, PutFarmerRequest,
+    };
+    use dg_xch_clients::protocols::pool_server::{
+        is_pool_member, verify_post_farmer, verify_put_farmer,
+    };
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, Bytes96, SizedBytes};
+    use dg_xch_core::clvm::bls_bindings::sign;
+    use dg_xch_serialize::{hash_256, ChiaSerialize};
+
+    let owner_key = SecretKey::key_gen(&[13u8; 32], &[]).unwrap();
+    let auth_key = SecretKey::key_gen(&[14u8; 32], &[]).unwrap();
+    let launcher_id = Bytes32::new(&[1u8; 32]);
+    let mut plot_nft = test_plot_nft(launcher_id, &owner_key);
+    let payload = PostFarmerPayload {
+        launcher_id,
+        authentication_token: get_current_authentication_token(5),
+        authentication_public_key: Bytes48::new(&auth_key.sk_to_pk().to_bytes()),
+        payout_instructions: hex::encode([15u8; 32]),
+        suggested_difficulty: Some(10),
+    };
+    let post_farmer = |payload: PostFarmerPayload, key: &SecretKey| PostFarmerRequest {
+        signature: Bytes96::new(&sign(key, &hash_256(payload.to_bytes())).to_bytes()),
+        payload,
+    };
+    assert!(verify_post_farmer(&post_farmer(payload.clone(), &owner_key), &plot_nft, 5).is_ok());
+    let error_code = |request: &PostFarmerRequest, plot_nft| {
+        verify_post_farmer(request, plot_nft, 5)
+            .unwrap_err()
+            .error_code
+    };
+    //Only the singleton owner key may register the farmer
+    assert_eq!(
+        error_code(&post_farmer(payload.clone(), &auth_key), &plot_nft),
+        PoolErrorCode::InvalidSignature as u8
+    );
+    let mut other = payload.clone();
+    other.authentication_token -= 10;
+    assert_eq!(
+        error_code(&post_farmer(other, &owner_key), &plot_nft),
+        PoolErrorCode::InvalidAuthenticationToken as u8
+    );
+    let mut other = payload.clone();
+    other.launcher_id = Bytes32::new(&[2u8; 32]);
+    assert_eq!(
+        error_code(&post_farmer(other, &owner_key), &plot_nft),
+        PoolErrorCode::InvalidSingleton as u8
+    );
+    let mut short_delay = test_plot_nft(launcher_id, &owner_key);
+    short_delay.delay_time = 60;
+    assert_eq!(
+        error_code(&post_farmer(payload.clone(), &owner_key), &short_delay),
+        PoolErrorCode::DelayTimeTooShort as u8
+    );
+    //A negative delay is refused instead of wrapping around to a huge one
+    let mut negative_delay = test_plot_nft(launcher_id, &owner_key);
+    negative_delay.delay_time = -1;
+    assert_eq!(
+        error_code(&post_farmer(payload.clone(), &owner_key), &negative_delay),
+        PoolErrorCode::DelayTimeTooShort as u8
+    );
+    assert!(dg_xch_cli::wallets::plotnft_utils::plotnft_delay_time(&negative_delay).is_err());
+    assert_eq!(
+        dg_xch_cli::wallets::plotnft_utils::plotnft_delay_time(&plot_nft).unwrap(),
+        604800
+    );
+
+    let payload = PutFarmerPayload {
+        launcher_id,
+        authentication_token: get_current_authentication_token(5),
+        authentication_public_key: None,
+        payout_instructions: Some(hex::encode([16u8; 32])),
+        suggested_difficulty: None,
+    };
+    let message = hash_256(payload.to_bytes());
+    let mut request = PutFarmerRequest {
+        payload,
+        signature: Bytes96::new(&sign(&owner_key, &message).to_bytes()),
+    };
+    assert!(verify_put_farmer(&request, &plot_nft, 5).is_ok());
+    request.payload.suggested_difficulty = Some(1);
+    assert_eq!(
+        verify_put_farmer(&request, &plot_nft, 5)
+            .unwrap_err()
+            .error_code,
+        PoolErrorCode::InvalidSignature as u8
+    );
+
+    let target = plot_nft.pool_state.target_puzzle_hash;
+    assert!(is_pool_member(&plot_nft.pool_state, &target, 100));
+    assert!(!is_pool_member(&plot_nft.pool_state, &target, 32));
+    assert!(!is_pool_member(
+        &plot_nft.pool_state,
+        &Bytes32::new(&[5u8; 32]),
+        100
+    ));
+    plot_nft.pool_state.state = 2;
+    assert!(!is_pool_member(&plot_nft.pool_state, &target, 100));
+}
+
+#[test]
+pub fn test_pool_login_verification() {
+    use blst::min_pk::{AggregateSignature, SecretKey};
+    use dg_xch_clients::protocols::pool::{
+        get_current_authentication_token, AuthenticationPayload, PoolErrorCode,
+    };
+    use dg_xch_clients::protocols::pool_server::PoolLogin;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, Bytes48, SizedBytes};
+    use dg_xch_core::clvm::bls_bindings::sign;
+    use dg_xch_serialize::{hash_256, ChiaSerialize};
+
+    //Build the link the same way create_pool_login_url does
+    let target_puzzle_hash = Bytes32::new(&[4u8; 32]);
+    let token = get_current_authentication_token(5);
+    let keys = [
+        (
+            SecretKey::key_gen(&[17u8; 32], &[]).unwrap(),
+            Bytes32::new(&[1u8; 32]),
+        ),
+        (
+            SecretKey::key_gen(&[18u8; 32], &[]).unwrap(),
+            Bytes32::new(&[2u8; 32]),
+        ),
+    ];
+    let signatures: Vec<_> = keys
+        .iter()
+        .map(|(key, launcher_id)| {
+            let payload = AuthenticationPayload {
+                method_name: String::from("get_login"),
+                launcher_id: *launcher_id,
+                target_puzzle_hash,
+                authentication_token: token,
+            };
+            sign(key, &hash_256(payload.to_bytes()))
+        })
+        .collect();
+    let signature = AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>(), true)
+        .unwrap()
+        .to_signature();
+    let url = format!(
+        "https://pool.example/login?launcher_id={},{}&authentication_token={token}&signature={}",
+        hex::encode(keys[0].1.as_slice()),
+        hex::encode(keys[1].1.as_slice()),
+        hex::encode(signature.to_bytes())
+    );
+    let login = PoolLogin::from_url(&url).unwrap();
+    assert_eq!(login.launcher_ids, vec![keys[0].1, keys[1].1]);
+    assert_eq!(login.authentication_token, token);
+    let public_keys: Vec<Bytes48> = keys
+        .iter()
+        .map(|(key, _)| Bytes48::new(&key.sk_to_pk().to_bytes()))
+        .collect();
+    assert!(login.verify(&public_keys, &target_puzzle_hash, 5).is_ok());
+
+    let reversed: Vec<Bytes48> = public_keys.iter().rev().cloned().collect();
+    assert_eq!(
+        login
+            .verify(&reversed, &target_puzzle_hash, 5)
+            .unwrap_err()
+            .error_code,
+        PoolErrorCode::InvalidSignature as u8
+    );
+    assert_eq!(
+        login
+            .verify(&public_keys, &Bytes32::new(&[5u8; 32]), 5)
+            .unwrap_err()
+            .error_code,
+        PoolErrorCode::InvalidSignature as u8
+    );
+    assert_eq!(
+        login
+            .verify(&public_keys[..1], &target_puzzle_hash, 5)
+            .unwrap_err()
+            .error_code,
+        PoolErrorCode::FarmerNotKnown as u8
+    );
+    assert!(PoolLogin::from_url("https://pool.example/login?launcher_id=0x01").is_err());
+}
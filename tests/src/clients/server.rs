@@ -0,0 +1,217 @@
+#[tokio::test]
+pub async fn test_websocket_server() {
+    use async_trait::async_trait;
+    use dg_xch_clients::protocols::shared::{Handshake, CAPABILITIES};
+    use dg_xch_clients::protocols::ProtocolMessageTypes;
+    use dg_xch_clients::websocket::server::{
+        Peer, PeerMessageHandler, WebsocketServer, WebsocketServerConfig,
+    };
+    use dg_xch_clients::websocket::{
+        get_client_generated_tls, get_client_tls, oneshot, ChiaMessage, Client, ClientSSLConfig,
+        MessageHandler, NodeType, Websocket,
+    };
+    use dg_xch_core::ssl::{generate_ca_signed_cert_data, make_ca_cert, CHIA_CA_CRT, CHIA_CA_KEY};
+    use dg_xch_serialize::ChiaSerialize;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+    use std::io::Error;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::mpsc::{channel, Sender};
+    use tokio::sync::Mutex;
+    use uuid::Uuid;
+
+    struct EchoHandler;
+    #[async_trait]
+    impl PeerMessageHandler for EchoHandler {
+        async fn handle(&self, msg: Arc<ChiaMessage>, peer: Arc<Peer>) -> Result<(), Error> {
+            peer.send(ChiaMessage {
+                msg_type: ProtocolMessageTypes::RequestSignatures,
+                id: msg.id,
+                data: msg.data.clone(),
+            })
+            .await
+        }
+    }
+    struct ForwardingHandler {
+        channel: Sender<Vec<u8>>,
+    }
+    #[async_trait]
+    impl MessageHandler for ForwardingHandler {
+        async fn handle(&self, msg: Arc<ChiaMessage>) -> Result<(), Error> {
+            let _ = self.channel.send(msg.data.clone()).await;
+            Ok(())
+        }
+    }
+
+    async fn send_handshake(client: Arc<Mutex<Client>>) -> Handshake {
+        oneshot::<Handshake, _>(
+            client,
+            ChiaMessage::new(
+                ProtocolMessageTypes::Handshake,
+                &Handshake {
+                    network_id: "testnet10".to_string(),
+                    protocol_version: "0.0.34".to_string(),
+                    software_version: "test".to_string(),
+                    server_port: 8448,
+                    node_type: NodeType::Harvester as u8,
+                    capabilities: vec![],
+                },
+                None,
+            ),
+            Some(ProtocolMessageTypes::Handshake),
+            None,
+            Some(5000),
+        )
+        .await
+        .unwrap()
+    }
+
+    let server = WebsocketServer::bind(WebsocketServerConfig::new(
+        "127.0.0.1",
+        0,
+        "testnet10",
+        NodeType::Farmer,
+    ))
+    .await
+    .unwrap();
+    let port = server.local_addr().unwrap().port();
+    let (tx, mut rx) = channel(8);
+    server.add_peer_handler(
+        ProtocolMessageTypes::NewProofOfSpace,
+        Arc::new(EchoHandler {}),
+    );
+    server.add_handler(
+        ProtocolMessageTypes::RespondSignatures,
+        Arc::new(ForwardingHandler { channel: tx }),
+    );
+    let peers = server.peers();
+    let run = Arc::new(AtomicBool::new(true));
+    let server_run = run.clone();
+    let server_handle = tokio::spawn(async move { server.run(server_run).await });
+
+    let (client, mut stream) = get_client_generated_tls("127.0.0.1", port, &None)
+        .await
+        .unwrap();
+    let client_run = run.clone();
+    tokio::spawn(async move { stream.run(client_run).await });
+    let client = Arc::new(Mutex::new(client));
+    let handshake = send_handshake(client.clone()).await;
+    assert_eq!(handshake.node_type, NodeType::Farmer as u8);
+    assert_eq!(handshake.server_port, port);
+    assert_eq!(handshake.capabilities.len(), CAPABILITIES.len());
+    assert_eq!(peers.len(), 1);
+    let peer = peers.iter().next().unwrap().value().clone();
+    assert_eq!(peer.node_type().await, Some(NodeType::Harvester));
+
+    //Peer handlers can reply to the connection that sent the message
+    let echoed = oneshot::<String, _>(
+        client.clone(),
+        ChiaMessage::new(
+            ProtocolMessageTypes::NewProofOfSpace,
+            &"proof".to_string(),
+            Some(7),
+        ),
+        Some(ProtocolMessageTypes::RequestSignatures),
+        Some(7),
+        Some(5000),
+    )
+    .await
+    .unwrap();
+    assert_eq!(echoed, "proof");
+
+    //Plain message handlers are routed by message type
+    client
+        .lock()
+        .await
+        .send(
+            ChiaMessage::new(
+                ProtocolMessageTypes::RespondSignatures,
+                &"sigs".to_string(),
+                None,
+            )
+            .into(),
+        )
+        .await
+        .unwrap();
+    let data = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(data, "sigs".to_string().to_bytes());
+
+    //Certificates not signed by the Chia CA are rejected
+    let ssl_dir = std::env::temp_dir().join(format!("dg_xch_server_ssl_{}", Uuid::new_v4()));
+    create_dir_all(&ssl_dir).unwrap();
+    let ca_crt_path = ssl_dir.join("private_ca.crt");
+    let (ca_cert, ca_key) = make_ca_cert(&ca_crt_path, &ssl_dir.join("private_ca.key")).unwrap();
+    let (cert_data, key_data) = generate_ca_signed_cert_data(&ca_cert, &ca_key).unwrap();
+    let crt_path = ssl_dir.join("private_harvester.crt");
+    let key_path = ssl_dir.join("private_harvester.key");
+    write(&crt_path, cert_data).unwrap();
+    write(&key_path, key_data).unwrap();
+    let rejected = get_client_tls(
+        "127.0.0.1",
+        port,
+        ClientSSLConfig {
+            ssl_crt_path: crt_path.to_str().unwrap(),
+            ssl_key_path: key_path.to_str().unwrap(),
+            ssl_ca_crt_path: ca_crt_path.to_str().unwrap(),
+        },
+        &None,
+    )
+    .await;
+    assert!(rejected.is_err());
+    assert_eq!(peers.len(), 1);
+
+    //A node reconnecting with the same certificate replaces and closes its old connection
+    let (cert_data, key_data) = generate_ca_signed_cert_data(CHIA_CA_CRT, CHIA_CA_KEY).unwrap();
+    write(&crt_path, cert_data).unwrap();
+    write(&key_path, key_data).unwrap();
+    let ssl_info = || ClientSSLConfig {
+        ssl_crt_path: crt_path.to_str().unwrap(),
+        ssl_key_path: key_path.to_str().unwrap(),
+        ssl_ca_crt_path: ca_crt_path.to_str().unwrap(),
+    };
+    let (first, mut first_stream) = get_client_tls("127.0.0.1", port, ssl_info(), &None)
+        .await
+        .unwrap();
+    let first_run = run.clone();
+    let first_handle = tokio::spawn(async move { first_stream.run(first_run).await });
+    send_handshake(Arc::new(Mutex::new(first))).await;
+    assert_eq!(peers.len(), 2);
+    let (second, mut second_stream) = get_client_tls("127.0.0.1", port, ssl_info(), &None)
+        .await
+        .unwrap();
+    let second_run = run.clone();
+    tokio::spawn(async move { second_stream.run(second_run).await });
+    let second = Arc::new(Mutex::new(second));
+    send_handshake(second.clone()).await;
+    tokio::time::timeout(Duration::from_secs(5), first_handle)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(peers.len(), 2);
+    let echoed = oneshot::<String, _>(
+        second,
+        ChiaMessage::new(
+            ProtocolMessageTypes::NewProofOfSpace,
+            &"again".to_string(),
+            Some(8),
+        ),
+        Some(ProtocolMessageTypes::RequestSignatures),
+        Some(8),
+        Some(5000),
+    )
+    .await
+    .unwrap();
+    assert_eq!(echoed, "again");
+    remove_dir_all(&ssl_dir).unwrap();
+
+    run.store(false, Ordering::Relaxed);
+    tokio::time::timeout(Duration::from_secs(5), server_handle)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+}
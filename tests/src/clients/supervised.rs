@@ -0,0 +1,181 @@
+#[test]
+pub fn test_reconnect_backoff() {
+    use dg_xch_clients::websocket::supervised::ReconnectBackoff;
+    use std::time::Duration;
+
+    let backoff = ReconnectBackoff {
+        initial_delay: Duration::from_millis(500),
+        max_delay: Duration::from_secs(5),
+        multiplier: 2,
+        max_attempts: None,
+    };
+    assert_eq!(backoff.delay_for_attempt(1), Duration::from_millis(500));
+    assert_eq!(backoff.delay_for_attempt(2), Duration::from_secs(1));
+    assert_eq!(backoff.delay_for_attempt(4), Duration::from_secs(4));
+    assert_eq!(backoff.delay_for_attempt(5), Duration::from_secs(5));
+    assert_eq!(backoff.delay_for_attempt(64), Duration::from_secs(5));
+}
+
+#[tokio::test]
+pub async fn test_supervised_client_reconnects() {
+    use async_trait::async_trait;
+    use dg_xch_clients::protocols::shared::{
+        load_certs_from_bytes, load_private_key_from_bytes, Handshake,
+    };
+    use dg_xch_clients::protocols::ProtocolMessageTypes;
+    use dg_xch_clients::websocket::supervised::{
+        ClientConnectionMode, ConnectionState, ReconnectBackoff, SupervisedClient,
+        SupervisedClientConfig,
+    };
+    use dg_xch_clients::websocket::{
+        ChiaMessage, ChiaMessageFilter, ChiaMessageHandler, MessageHandler, NodeType, Websocket,
+    };
+    use dg_xch_core::ssl::{generate_ca_signed_cert_data, CHIA_CA_CRT, CHIA_CA_KEY};
+    use dg_xch_serialize::ChiaSerialize;
+    use futures_util::{SinkExt, StreamExt};
+    use rustls::ServerConfig;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+    use std::io::{Cursor, Error};
+    use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc::{channel, Sender};
+    use tokio_rustls::TlsAcceptor;
+    use tokio_tungstenite::tungstenite::Message;
+    use uuid::Uuid;
+
+    struct ForwardingHandler {
+        channel: Sender<Vec<u8>>,
+    }
+    #[async_trait]
+    impl MessageHandler for ForwardingHandler {
+        async fn handle(&self, msg: Arc<ChiaMessage>) -> Result<(), Error> {
+            let _ = self.channel.send(msg.data.clone()).await;
+            Ok(())
+        }
+    }
+
+    //One cert is generated up front and shared by both sides, so reconnects don't pay for it
+    let (cert_data, key_data) = generate_ca_signed_cert_data(CHIA_CA_CRT, CHIA_CA_KEY).unwrap();
+    let ssl_path = std::env::temp_dir().join(format!("dg_xch_supervised_ssl_{}", Uuid::new_v4()));
+    create_dir_all(&ssl_path).unwrap();
+    write(ssl_path.join("client.crt"), &cert_data).unwrap();
+    write(ssl_path.join("client.key"), &key_data).unwrap();
+    write(ssl_path.join("ca.crt"), CHIA_CA_CRT).unwrap();
+
+    //Answers every handshake, then sends one signage point tagged with the connection number.
+    //The first connection is dropped straight after to simulate a node restart.
+    let config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(
+            load_certs_from_bytes(cert_data.as_bytes()).unwrap(),
+            load_private_key_from_bytes(key_data.as_bytes()).unwrap(),
+        )
+        .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let connections = Arc::new(AtomicU8::new(0));
+    let server_connections = connections.clone();
+    let server = tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let connection = server_connections.fetch_add(1, Ordering::Relaxed) + 1;
+            let stream = acceptor.accept(stream).await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(Message::Binary(data))) = ws.next().await {
+                let msg = ChiaMessage::from_bytes(&mut Cursor::new(data)).unwrap();
+                if msg.msg_type == ProtocolMessageTypes::Handshake {
+                    let mut handshake = Handshake::from_bytes(&mut Cursor::new(msg.data)).unwrap();
+                    handshake.node_type = NodeType::FullNode as u8;
+                    let reply =
+                        ChiaMessage::new(ProtocolMessageTypes::Handshake, &handshake, msg.id);
+                    ws.send(reply.into()).await.unwrap();
+                    let signage_point = ChiaMessage {
+                        msg_type: ProtocolMessageTypes::NewSignagePoint,
+                        id: None,
+                        data: vec![connection],
+                    };
+                    ws.send(signage_point.into()).await.unwrap();
+                    if connection == 1 {
+                        break;
+                    }
+                }
+            }
+            if connection > 1 {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+            }
+        }
+    });
+
+    let run = Arc::new(AtomicBool::new(true));
+    let client = SupervisedClient::start(
+        SupervisedClientConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            network_id: "testnet10".to_string(),
+            node_type: NodeType::Farmer,
+            mode: ClientConnectionMode::Tls {
+                ssl_crt_path: ssl_path.join("client.crt").to_string_lossy().to_string(),
+                ssl_key_path: ssl_path.join("client.key").to_string_lossy().to_string(),
+                ssl_ca_crt_path: ssl_path.join("ca.crt").to_string_lossy().to_string(),
+            },
+            additional_headers: None,
+            backoff: ReconnectBackoff {
+                initial_delay: Duration::from_millis(100),
+                max_delay: Duration::from_secs(1),
+                multiplier: 2,
+                max_attempts: Some(5),
+            },
+        },
+        run.clone(),
+    );
+    let mut states = client.subscribe_state();
+    let (tx, mut rx) = channel(8);
+    client
+        .subscribe(
+            Uuid::new_v4(),
+            ChiaMessageHandler::new(
+                ChiaMessageFilter {
+                    msg_type: Some(ProtocolMessageTypes::NewSignagePoint),
+                    id: None,
+                },
+                Arc::new(ForwardingHandler { channel: tx }),
+            ),
+        )
+        .await;
+
+    //The subscription must survive the reconnect and see the second connection's message
+    loop {
+        let data = tokio::time::timeout(Duration::from_secs(60), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        if data[0] == 2 {
+            break;
+        }
+    }
+    assert_eq!(connections.load(Ordering::Relaxed), 2);
+    assert_eq!(client.state().await, ConnectionState::Connected);
+
+    let mut seen = vec![];
+    while let Ok(state) = states.try_recv() {
+        seen.push(state);
+    }
+    assert!(seen.contains(&ConnectionState::Disconnected));
+    assert!(seen
+        .iter()
+        .any(|s| matches!(s, ConnectionState::Reconnecting { attempt: 1, .. })));
+    assert_eq!(seen.last(), Some(&ConnectionState::Connected));
+
+    client.close(None).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(5), client.join())
+        .await
+        .unwrap()
+        .unwrap();
+    //Closing the client must not stop other tasks sharing the run flag
+    assert!(run.load(Ordering::Relaxed));
+    server.abort();
+    remove_dir_all(&ssl_path).unwrap();
+}
@@ -0,0 +1,222 @@
+#[tokio::test]
+pub async fn test_wallet_client_extended_endpoints() {
+    use crate::clients::mock_server::MockRpcServer;
+    use dg_xch_clients::api::wallet::WalletAPI;
+    use dg_xch_clients::protocols::wallet::{
+        CatSpendRequest, CreateCatWalletRequest, CreateOfferForIdsRequest, GetAllOffersRequest,
+        NftTransferRequest,
+    };
+    use dg_xch_clients::rpc::wallet::WalletClient;
+    use dg_xch_core::blockchain::sized_bytes::Bytes32;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    let asset_id = "a628c1c2c6fcb74d53746157e438e108eab5c0bb3e5c80ff9b1910b3e4832913";
+    let tx_id = "0x5e7b0f1bbd4c9a1e3f06cc20a98e4b37d7c6b4c8c8e4f2a7b58fa6f2d1c3e0a9";
+    let trade_id = "0x0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c";
+    let puzzle_hash = "0x4bc6435b409bcbabe53870dae0f03755f6aabb4594c5915ec983acf12a5d1fba";
+    let signature = format!("0xc0{}", "00".repeat(95));
+    let transaction = json!({
+        "confirmed_at_height": 0,
+        "created_at_time": 1_700_000_000u64,
+        "to_puzzle_hash": puzzle_hash,
+        "amount": 1000,
+        "fee_amount": 10,
+        "confirmed": false,
+        "sent": 0,
+        "spend_bundle": null,
+        "additions": [],
+        "removals": [],
+        "wallet_id": 2,
+        "sent_to": [],
+        "trade_id": null,
+        "type": 1,
+        "name": tx_id,
+        "memos": []
+    });
+    let trade_record = json!({
+        "trade_id": trade_id,
+        "status": "PENDING_ACCEPT",
+        "confirmed_at_index": 0,
+        "accepted_at_time": null,
+        "created_at_time": 1_700_000_000u64,
+        "is_my_offer": true,
+        "sent": 0,
+        "sent_to": [],
+        "taken_offer": null,
+        "coins_of_interest": [],
+        "summary": {
+            "offered": {"xch": 1000},
+            "requested": {asset_id: 5000},
+            "fees": 0,
+            "infos": {}
+        },
+        "pending": {"xch": 1000}
+    });
+    let mut responses = HashMap::new();
+    responses.insert(
+        "create_new_wallet".to_string(),
+        json!({"type": 6, "asset_id": asset_id, "wallet_id": 2, "success": true}),
+    );
+    responses.insert(
+        "cat_spend".to_string(),
+        json!({"transaction": transaction, "transaction_id": tx_id, "success": true}),
+    );
+    responses.insert(
+        "cat_get_asset_id".to_string(),
+        json!({"asset_id": asset_id, "wallet_id": 2, "success": true}),
+    );
+    responses.insert(
+        "create_offer_for_ids".to_string(),
+        json!({"offer": "offer1qqr83wcuu2rykcmqvps", "trade_record": trade_record, "success": true}),
+    );
+    responses.insert(
+        "get_all_offers".to_string(),
+        json!({"trade_records": [trade_record], "offers": null, "success": true}),
+    );
+    responses.insert(
+        "cancel_offer".to_string(),
+        json!({"success": false, "error": "Trade not found"}),
+    );
+    responses.insert(
+        "nft_get_nfts".to_string(),
+        json!({
+            "wallet_id": 3,
+            "nft_list": [{
+                "nft_id": "nft1z7p0w5nqwrz43f2qqj8dy5w3dwt6w9rdvv2psysxqe2ud3jmn5ssexuppl",
+                "launcher_id": trade_id,
+                "nft_coin_id": tx_id,
+                "nft_coin_confirmation_height": 4_500_000,
+                "owner_did": null,
+                "royalty_percentage": 300,
+                "royalty_puzzle_hash": puzzle_hash,
+                "data_uris": ["https://example.com/nft.png"],
+                "data_hash": "0xd4584ad463139fa8c0d9f68f4b59f185",
+                "metadata_uris": [],
+                "metadata_hash": "0x",
+                "license_uris": [],
+                "license_hash": "0x",
+                "edition_total": 1,
+                "edition_number": 1,
+                "updater_puzhash": puzzle_hash,
+                "chain_info": "((117 \"https://example.com/nft.png\"))",
+                "mint_height": 4_499_000,
+                "supports_did": true,
+                "p2_address": puzzle_hash,
+                "pending_transaction": false,
+                "minter_did": null,
+                "launcher_puzhash": puzzle_hash,
+                "off_chain_metadata": null
+            }],
+            "success": true
+        }),
+    );
+    responses.insert(
+        "nft_transfer_nft".to_string(),
+        json!({
+            "wallet_id": 3,
+            "spend_bundle": {"coin_spends": [], "aggregated_signature": signature},
+            "success": true
+        }),
+    );
+    responses.insert(
+        "did_get_did".to_string(),
+        json!({"wallet_id": 4, "my_did": "did:chia:1qqqq", "coin_id": tx_id, "success": true}),
+    );
+    responses.insert("did_set_wallet_name".to_string(), json!({"success": true}));
+    let server = MockRpcServer::start(responses).await.unwrap();
+    let client = WalletClient::new(
+        "localhost",
+        server.port,
+        Some(server.ssl_path.clone()),
+        None,
+    );
+
+    let cat_wallet = client
+        .create_cat_wallet(&CreateCatWalletRequest::existing(
+            Bytes32::from(asset_id),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(cat_wallet.wallet_id, 2);
+    assert_eq!(cat_wallet.wallet_type, 6);
+    assert_eq!(
+        server.request_for("create_new_wallet"),
+        Some(json!({
+            "wallet_type": "cat_wallet",
+            "mode": "existing",
+            "asset_id": format!("0x{asset_id}"),
+            "fee": 0
+        }))
+    );
+
+    let sent = client
+        .cat_spend(&CatSpendRequest {
+            wallet_id: 2,
+            amount: Some(1000),
+            inner_address: Some("xch1test".to_string()),
+            fee: 10,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(sent.transaction_id, Bytes32::from(tx_id));
+    assert_eq!(sent.transaction.amount, 1000);
+    assert_eq!(
+        server.request_for("cat_spend"),
+        Some(json!({"wallet_id": 2, "amount": 1000, "inner_address": "xch1test", "fee": 10}))
+    );
+    assert_eq!(
+        client.cat_get_asset_id(2).await.unwrap().asset_id,
+        Bytes32::from(asset_id)
+    );
+
+    let offer = client
+        .create_offer_for_ids(
+            &CreateOfferForIdsRequest::default()
+                .add(1, -1000)
+                .add(2, 5000),
+        )
+        .await
+        .unwrap();
+    assert_eq!(offer.trade_record.trade_id, Bytes32::from(trade_id));
+    assert_eq!(offer.trade_record.summary.offered.get("xch"), Some(&1000));
+    assert_eq!(
+        server.request_for("create_offer_for_ids").unwrap()["offer"],
+        json!({"1": -1000, "2": 5000})
+    );
+    let offers = client
+        .get_all_offers(&GetAllOffersRequest::default())
+        .await
+        .unwrap();
+    assert_eq!(offers.trade_records.len(), 1);
+    assert!(offers.offers.is_none());
+    assert!(client
+        .cancel_offer(&Bytes32::from(trade_id), true, 0)
+        .await
+        .is_err());
+
+    let nfts = client.nft_get_nfts(3, None, Some(50)).await.unwrap();
+    assert_eq!(nfts.len(), 1);
+    assert_eq!(nfts[0].royalty_percentage, Some(300));
+    assert!(nfts[0].supports_did);
+    let spend_bundle = client
+        .nft_transfer_nft(&NftTransferRequest {
+            wallet_id: 3,
+            target_address: "xch1test".to_string(),
+            nft_coin_id: nfts[0].nft_coin_id,
+            fee: 0,
+        })
+        .await
+        .unwrap();
+    assert!(spend_bundle.coin_spends.is_empty());
+
+    let did = client.did_get_did(4).await.unwrap();
+    assert_eq!(did.my_did, "did:chia:1qqqq");
+    client.did_set_wallet_name(4, "Profile 1").await.unwrap();
+    assert_eq!(
+        server.request_for("did_set_wallet_name"),
+        Some(json!({"wallet_id": 4, "name": "Profile 1"}))
+    );
+}
@@ -0,0 +1,303 @@
+#[test]
+pub fn test_coin_state_cache_rollback() {
+    use dg_xch_clients::protocols::wallet::{CoinState, CoinStateUpdate};
+    use dg_xch_clients::websocket::wallet_sync::CoinStateCache;
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+
+    let our_ph = Bytes32::new(&[1u8; 32]);
+    let coin_state = |parent: u8, created: u32, spent: Option<u32>| CoinState {
+        coin: Coin {
+            parent_coin_info: Bytes32::new(&[parent; 32]),
+            puzzle_hash: our_ph,
+            amount: parent as u64 * 100,
+        },
+        spent_height: spent,
+        created_height: Some(created),
+    };
+    let mut cache = CoinStateCache::new();
+    cache.watch_puzzle_hashes(vec![our_ph]);
+    let old = coin_state(1, 10, None);
+    let spent_late = coin_state(2, 11, Some(20));
+    let created_late = coin_state(3, 18, None);
+    let mut foreign = coin_state(4, 12, None);
+    foreign.coin.puzzle_hash = Bytes32::new(&[9u8; 32]);
+    let applied = cache.apply_update(&CoinStateUpdate {
+        height: 20,
+        fork_height: 0,
+        peak_hash: Bytes32::new(&[20u8; 32]),
+        items: vec![
+            old.clone(),
+            spent_late.clone(),
+            created_late.clone(),
+            foreign.clone(),
+        ],
+    });
+    assert_eq!(applied, 3);
+    assert_eq!(cache.height(), 20);
+    assert_eq!(cache.balance(), 400);
+    assert!(cache.get(&foreign.coin.name()).is_none());
+
+    //A reorg back to 15 drops the coin created at 18 and unspends the coin spent at 20
+    cache.apply_update(&CoinStateUpdate {
+        height: 16,
+        fork_height: 15,
+        peak_hash: Bytes32::new(&[16u8; 32]),
+        items: vec![],
+    });
+    assert_eq!(cache.height(), 16);
+    assert_eq!(cache.peak_hash(), Some(Bytes32::new(&[16u8; 32])));
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&created_late.coin.name()).is_none());
+    assert_eq!(
+        cache.get(&spent_late.coin.name()).unwrap().spent_height,
+        None
+    );
+    assert_eq!(cache.balance(), 300);
+    assert_eq!(cache.unspent_for_puzzle_hash(&our_ph).len(), 2);
+}
+
+#[tokio::test]
+pub async fn test_wallet_sync() {
+    use async_trait::async_trait;
+    use dg_xch_clients::protocols::shared::Handshake;
+    use dg_xch_clients::protocols::wallet::{
+        CoinState, CoinStateUpdate, NewPeakWallet, RegisterForPhUpdates, RejectPuzzleSolution,
+        RequestChildren, RequestPuzzleSolution, RespondChildren, RespondToPhUpdates,
+    };
+    use dg_xch_clients::protocols::ProtocolMessageTypes;
+    use dg_xch_clients::websocket::server::{
+        Peer, PeerMessageHandler, WebsocketServer, WebsocketServerConfig,
+    };
+    use dg_xch_clients::websocket::wallet_sync::WalletSync;
+    use dg_xch_clients::websocket::{get_client_generated_tls, oneshot, ChiaMessage, NodeType};
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+    use dg_xch_serialize::ChiaSerialize;
+    use std::io::{Cursor, Error, ErrorKind};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Mutex;
+
+    let our_ph = Bytes32::new(&[1u8; 32]);
+    let coin = |parent: u8, amount: u64| Coin {
+        parent_coin_info: Bytes32::new(&[parent; 32]),
+        puzzle_hash: our_ph,
+        amount,
+    };
+    let confirmed = CoinState {
+        coin: coin(1, 1_000),
+        spent_height: None,
+        created_height: Some(10),
+    };
+
+    struct FullNodeHandler {
+        coin_states: Vec<CoinState>,
+    }
+    #[async_trait]
+    impl PeerMessageHandler for FullNodeHandler {
+        async fn handle(&self, msg: Arc<ChiaMessage>, peer: Arc<Peer>) -> Result<(), Error> {
+            let mut cursor = Cursor::new(&msg.data);
+            let resp = match msg.msg_type {
+                ProtocolMessageTypes::RegisterInterestInPuzzleHash => {
+                    let req = RegisterForPhUpdates::from_bytes(&mut cursor)?;
+                    ChiaMessage::new(
+                        ProtocolMessageTypes::RespondToPhUpdate,
+                        &RespondToPhUpdates {
+                            puzzle_hashes: req.puzzle_hashes,
+                            min_height: req.min_height,
+                            coin_states: self.coin_states.clone(),
+                        },
+                        msg.id,
+                    )
+                }
+                ProtocolMessageTypes::RequestChildren => {
+                    let req = RequestChildren::from_bytes(&mut cursor)?;
+                    ChiaMessage::new(
+                        ProtocolMessageTypes::RespondChildren,
+                        &RespondChildren {
+                            coin_states: self
+                                .coin_states
+                                .iter()
+                                .filter(|c| c.coin.parent_coin_info == req.coin_name)
+                                .cloned()
+                                .collect(),
+                        },
+                        msg.id,
+                    )
+                }
+                ProtocolMessageTypes::RequestPuzzleSolution => {
+                    let req = RequestPuzzleSolution::from_bytes(&mut cursor)?;
+                    ChiaMessage::new(
+                        ProtocolMessageTypes::RejectPuzzleSolution,
+                        &RejectPuzzleSolution {
+                            coin_name: req.coin_name,
+                            height: req.height,
+                        },
+                        msg.id,
+                    )
+                }
+                _ => return Err(Error::new(ErrorKind::InvalidInput, "Unexpected message")),
+            };
+            peer.send(resp).await
+        }
+    }
+
+    let server = WebsocketServer::bind(WebsocketServerConfig::new(
+        "127.0.0.1",
+        0,
+        "testnet10",
+        NodeType::FullNode,
+    ))
+    .await
+    .unwrap();
+    let port = server.local_addr().unwrap().port();
+    let handler = Arc::new(FullNodeHandler {
+        coin_states: vec![confirmed.clone()],
+    });
+    for msg_type in [
+        ProtocolMessageTypes::RegisterInterestInPuzzleHash,
+        ProtocolMessageTypes::RequestChildren,
+        ProtocolMessageTypes::RequestPuzzleSolution,
+    ] {
+        server.add_peer_handler(msg_type, handler.clone());
+    }
+    let peers = server.peers();
+    let run = Arc::new(AtomicBool::new(true));
+    let server_run = run.clone();
+    let server_handle = tokio::spawn(async move { server.run(server_run).await });
+
+    let (client, mut stream) = get_client_generated_tls("127.0.0.1", port, &None)
+        .await
+        .unwrap();
+    let client_run = run.clone();
+    tokio::spawn(async move { stream.run(client_run).await });
+    let client = Arc::new(Mutex::new(client));
+    oneshot::<Handshake, _>(
+        client.clone(),
+        ChiaMessage::new(
+            ProtocolMessageTypes::Handshake,
+            &Handshake {
+                network_id: "testnet10".to_string(),
+                protocol_version: "0.0.34".to_string(),
+                software_version: "test".to_string(),
+                server_port: 8444,
+                node_type: NodeType::Wallet as u8,
+                capabilities: vec![],
+            },
+            None,
+        ),
+        Some(ProtocolMessageTypes::Handshake),
+        None,
+        Some(5000),
+    )
+    .await
+    .unwrap();
+    let sync = WalletSync::new(client.clone()).await.with_timeout(5000);
+    let mut updates = sync.subscribe_updates();
+    let cache = sync.cache();
+
+    let initial = sync.register_puzzle_hashes(vec![our_ph], 0).await.unwrap();
+    assert_eq!(initial, vec![confirmed.clone()]);
+    assert_eq!(cache.lock().await.balance(), 1_000);
+    let children = sync
+        .request_children(confirmed.coin.parent_coin_info)
+        .await
+        .unwrap();
+    assert_eq!(children.len(), 1);
+    let err = sync
+        .request_puzzle_solution(confirmed.coin.name(), 10)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    //Pushed updates are applied, and a reorg rolls back to the fork height first
+    let peer = peers.iter().next().unwrap().value().clone();
+    let late = CoinState {
+        coin: coin(2, 500),
+        spent_height: None,
+        created_height: Some(21),
+    };
+    let spent = CoinState {
+        spent_height: Some(21),
+        ..confirmed.clone()
+    };
+    let push = |update: CoinStateUpdate| {
+        let peer = peer.clone();
+        async move {
+            peer.send(ChiaMessage::new(
+                ProtocolMessageTypes::CoinStateUpdate,
+                &update,
+                None,
+            ))
+            .await
+            .unwrap()
+        }
+    };
+    push(CoinStateUpdate {
+        height: 21,
+        fork_height: 20,
+        peak_hash: Bytes32::new(&[21u8; 32]),
+        items: vec![late.clone(), spent],
+    })
+    .await;
+    let update = tokio::time::timeout(Duration::from_secs(5), updates.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(update.height, 21);
+    {
+        let cache = cache.lock().await;
+        assert_eq!(cache.height(), 21);
+        assert_eq!(cache.balance(), 500);
+    }
+    push(CoinStateUpdate {
+        height: 22,
+        fork_height: 19,
+        peak_hash: Bytes32::new(&[22u8; 32]),
+        items: vec![],
+    })
+    .await;
+    tokio::time::timeout(Duration::from_secs(5), updates.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    {
+        let cache = cache.lock().await;
+        assert_eq!(cache.height(), 22);
+        assert!(cache.get(&late.coin.name()).is_none());
+        assert_eq!(cache.unspent(), vec![confirmed.clone()]);
+    }
+
+    peer.send(ChiaMessage::new(
+        ProtocolMessageTypes::NewPeakWallet,
+        &NewPeakWallet {
+            header_hash: Bytes32::new(&[22u8; 32]),
+            height: 22,
+            weight: 1_000,
+            fork_point_with_previous_peak: 19,
+        },
+        None,
+    ))
+    .await
+    .unwrap();
+    let mut peak = None;
+    for _ in 0..50 {
+        peak = sync.peak().await;
+        if peak.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(peak.unwrap().height, 22);
+
+    sync.close().await;
+    run.store(false, Ordering::Relaxed);
+    tokio::time::timeout(Duration::from_secs(5), server_handle)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+}
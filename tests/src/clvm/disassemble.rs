@@ -0,0 +1,48 @@
+#[test]
+pub fn test_disassemble_round_trip() {
+    use dg_xch_core::clvm::assemble::assemble_text;
+    use dg_xch_core::clvm::disassemble::disassemble_atom;
+
+    let sources = [
+        "(a (q 2 (q 2 (i 11 (q 2 (i (= 5 (point_add 11 (pubkey_for_exp (sha256 11 (a 6 (c 2 (c 23 ()))))))) (q 2 23 47) (q 8)) 1) (q 4 (c 4 (c 5 (c (a 6 (c 2 (c 23 ()))) ()))) (a 23 47))) 1) (c (q 50 2 (i (l 5) (q 11 (q . 2) (a 6 (c 2 (c 9 ()))) (a 6 (c 2 (c 13 ())))) (q 11 (q . 1) 5)) 1) 1)) (c (q . 0x94a96f7397ff4acb08b6532fd20bb975a2c350c19216fef4ae9f64499bc59fe919bcf7b531dd80a371ad7858bfb288d2) 1))",
+        "(c (q . \"hello world\") (q . 'say \"hi\"'))",
+        "(q -128 128 0x00 -256 -1 (coinid 2 5 11) . \"tail\")",
+        "(secp256k1_verify 2 5 (g1_map 11))",
+    ];
+    for source in sources {
+        let program = assemble_text(source).unwrap();
+        assert_eq!(program.disassemble().unwrap(), source);
+        let pretty = program.disassemble_pretty(2).unwrap();
+        assert_eq!(
+            assemble_text(&pretty).unwrap().to_bytes(),
+            program.to_bytes()
+        );
+        assert_eq!(program.to_program().disassemble(), source);
+    }
+
+    assert_eq!(disassemble_atom(&[]), "()");
+    assert_eq!(disassemble_atom(&[0x80]), "-128");
+    assert_eq!(disassemble_atom(&[0x00, 0x80]), "128");
+    assert_eq!(disassemble_atom(&[0x00, 0x01]), "0x0001");
+    assert_eq!(disassemble_atom(&[0x00]), "0x00");
+    assert_eq!(disassemble_atom(b"abc"), "\"abc\"");
+    assert_eq!(disassemble_atom(b"a\\c"), "0x615c63");
+    assert_eq!(disassemble_atom(&[0xff, 0xfe, 0xfd]), "0xfffefd");
+}
+
+#[test]
+pub fn test_disassemble_pretty() {
+    use dg_xch_core::clvm::assemble::assemble_text;
+    use dg_xch_puzzles::clvm_puzzles::SINGLETON_MOD;
+
+    let flat = SINGLETON_MOD.disassemble();
+    let pretty = SINGLETON_MOD.disassemble_pretty(2);
+    assert!(pretty.lines().count() > 1);
+    assert!(pretty.lines().nth(1).unwrap().starts_with("  "));
+    assert_eq!(assemble_text(&flat).unwrap().to_program(), *SINGLETON_MOD);
+    assert_eq!(assemble_text(&pretty).unwrap().to_program(), *SINGLETON_MOD);
+
+    //Short programs stay on one line and comments are skipped by the assembler
+    let program = assemble_text("; condition list\n(q (73 0x01 1)) ; trailing").unwrap();
+    assert_eq!(program.disassemble_pretty(2).unwrap(), "(q (73 1 1))");
+}
@@ -1,2 +1,7 @@
 pub mod curry;
+pub mod disassemble;
+pub mod operators;
 pub mod program;
+pub mod serialize;
+pub mod spend_bundle_simulator;
+pub mod trace;
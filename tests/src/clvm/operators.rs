@@ -0,0 +1,173 @@
+#[cfg(test)]
+fn run(src: &str) -> Result<(u64, dg_xch_core::clvm::program::Program), std::io::Error> {
+    use dg_xch_core::clvm::assemble::assemble_text;
+    use dg_xch_core::clvm::program::Program;
+    use dg_xch_core::clvm::utils::INFINITE_COST;
+
+    assemble_text(src)?.run_with_cost(INFINITE_COST, &Program::null())
+}
+
+#[test]
+pub fn test_coinid_modpow_and_mod() {
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+    use dg_xch_core::clvm::assemble::assemble_text;
+    use dg_xch_core::clvm::dialect::{ENABLE_BLS_OPS, NO_UNKNOWN_OPS};
+    use dg_xch_core::clvm::program::Program;
+    use dg_xch_core::clvm::utils::INFINITE_COST;
+
+    let coin = Coin {
+        parent_coin_info: Bytes32::new(&[1u8; 32]),
+        puzzle_hash: Bytes32::new(&[2u8; 32]),
+        amount: 1000,
+    };
+    let src = format!(
+        "(coinid (q . 0x{}) (q . 0x{}) (q . 1000))",
+        hex::encode([1u8; 32]),
+        hex::encode([2u8; 32])
+    );
+    let (cost, result) = run(&src).unwrap();
+    assert_eq!(result.as_vec().unwrap(), coin.name().as_slice());
+    assert!(cost > 0);
+    //Amounts must be canonical
+    let bad_amount = format!(
+        "(coinid (q . 0x{}) (q . 0x{}) (q . 0x0003e8))",
+        hex::encode([1u8; 32]),
+        hex::encode([2u8; 32])
+    );
+    assert!(run(&bad_amount).is_err());
+    assert!(run("(coinid (q . 0x01) (q . 0x02) (q . 1))").is_err());
+
+    let (_, result) = run("(modpow (q . 3) (q . 4) (q . 5))").unwrap();
+    assert_eq!(result.as_int().unwrap(), 1.into());
+    assert!(run("(modpow (q . 3) (q . 4) ())").is_err());
+    let (_, result) = run("(% (q . 17) (q . 5))").unwrap();
+    assert_eq!(result.as_int().unwrap(), 2.into());
+    assert!(run("(% (q . 17) ())").is_err());
+    //% is charged like /, plus the allocation of the one byte result
+    let program = assemble_text("(% (q . 17) (q . 5))").unwrap();
+    let (_, trace) = program.run_with_trace(INFINITE_COST, ENABLE_BLS_OPS, &Program::null());
+    assert_eq!(trace.ops.len(), 1);
+    assert_eq!(trace.ops[0].cost, 988 + 2 * 4 + 10);
+
+    //Without the dialect flag the new operators are unknown operators
+    let program = assemble_text("(% (q . 17) (q . 5))").unwrap();
+    let (_, result) = program.run(INFINITE_COST, 0, &Program::null()).unwrap();
+    assert_eq!(result, Program::null());
+    assert!(program
+        .run(INFINITE_COST, NO_UNKNOWN_OPS, &Program::null())
+        .is_err());
+}
+
+#[test]
+pub fn test_bls_operators() {
+    use blst::min_pk::SecretKey;
+    use dg_xch_core::clvm::bls_bindings::sign;
+
+    let sk = SecretKey::key_gen(&[3u8; 32], &[]).unwrap();
+    let pk = hex::encode(sk.sk_to_pk().to_bytes());
+    let g1_identity = format!("c0{}", "00".repeat(47));
+    let g2_identity = format!("c0{}", "00".repeat(95));
+
+    let (_, doubled) = run(&format!("(g1_multiply (q . 0x{pk}) (q . 2))")).unwrap();
+    let (_, added) = run(&format!("(point_add (q . 0x{pk}) (q . 0x{pk}))")).unwrap();
+    assert_eq!(doubled, added);
+    let (_, result) = run(&format!("(g1_subtract (q . 0x{pk}) (q . 0x{pk}))")).unwrap();
+    assert_eq!(hex::encode(result.as_vec().unwrap()), g1_identity);
+    let (_, result) = run(&format!(
+        "(point_add (q . 0x{pk}) (g1_negate (q . 0x{pk})))"
+    ))
+    .unwrap();
+    assert_eq!(hex::encode(result.as_vec().unwrap()), g1_identity);
+    assert!(run("(g1_negate (q . 0x01))").is_err());
+
+    let (_, g2) = run("(g2_map (q . \"hello\"))").unwrap();
+    let g2 = hex::encode(g2.as_vec().unwrap());
+    assert_eq!(g2.len(), 192);
+    let (_, tripled) = run(&format!("(g2_multiply (q . 0x{g2}) (q . 3))")).unwrap();
+    let (_, summed) = run(&format!("(g2_add (q . 0x{g2}) (q . 0x{g2}) (q . 0x{g2}))")).unwrap();
+    assert_eq!(tripled, summed);
+    let (_, result) = run(&format!("(g2_subtract (q . 0x{g2}) (q . 0x{g2}))")).unwrap();
+    assert_eq!(hex::encode(result.as_vec().unwrap()), g2_identity);
+    let (_, result) = run(&format!("(g2_add (q . 0x{g2}) (g2_negate (q . 0x{g2})))")).unwrap();
+    assert_eq!(hex::encode(result.as_vec().unwrap()), g2_identity);
+    let (_, g1) =
+        run("(g1_map (q . \"hello\") (q . \"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_\"))")
+            .unwrap();
+    assert_eq!(g1.as_vec().unwrap().len(), 48);
+
+    //e(P, Q) * e(-P, Q) is the identity
+    let (_, result) = run(&format!(
+        "(bls_pairing_identity (q . 0x{pk}) (q . 0x{g2}) (g1_negate (q . 0x{pk})) (q . 0x{g2}))"
+    ))
+    .unwrap();
+    assert!(result.as_vec().unwrap().is_empty());
+    assert!(run(&format!("(bls_pairing_identity (q . 0x{pk}) (q . 0x{g2}))")).is_err());
+
+    let sig = hex::encode(sign(&sk, b"hello").to_bytes());
+    let (cost, _) = run(&format!(
+        "(bls_verify (q . 0x{sig}) (q . 0x{pk}) (q . \"hello\"))"
+    ))
+    .unwrap();
+    assert!(cost > 3_000_000);
+    assert!(run(&format!(
+        "(bls_verify (q . 0x{sig}) (q . 0x{pk}) (q . \"goodbye\"))"
+    ))
+    .is_err());
+}
+
+#[test]
+pub fn test_secp_operators() {
+    use dg_xch_core::clvm::dialect::{ENABLE_SECP_OPS, NO_UNKNOWN_OPS};
+    use dg_xch_core::clvm::program::{Program, SerializedProgram};
+    use dg_xch_core::clvm::utils::INFINITE_COST;
+    use k256::ecdsa::signature::hazmat::PrehashSigner;
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::digest(b"hello");
+    let msg = hex::encode(digest);
+
+    let k1_key = k256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+    let k1_sig: k256::ecdsa::Signature = k1_key.sign_prehash(&digest).unwrap();
+    let k1_pk = hex::encode(k1_key.verifying_key().to_encoded_point(true).as_bytes());
+    let k1_sig = hex::encode(k1_sig.to_bytes());
+    let r1_key = p256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+    let r1_sig: p256::ecdsa::Signature = r1_key.sign_prehash(&digest).unwrap();
+    let r1_pk = hex::encode(r1_key.verifying_key().to_encoded_point(true).as_bytes());
+    let r1_sig = hex::encode(r1_sig.to_bytes());
+
+    let (cost, _) = run(&format!(
+        "(secp256k1_verify (q . 0x{k1_pk}) (q . 0x{msg}) (q . 0x{k1_sig}))"
+    ))
+    .unwrap();
+    assert!(cost >= 1_300_000);
+    //Serialized with the mainnet opcodes rather than through the assembler
+    for (opcode, pk, sig) in [("13d61f00", &k1_pk, &k1_sig), ("1c3a8f00", &r1_pk, &r1_sig)] {
+        let program = SerializedProgram::from_hex(&format!(
+            "ff84{opcode}ffff01a1{pk}ffff01a0{msg}ffff01c040{sig}8080"
+        ))
+        .unwrap()
+        .to_program();
+        let flags = ENABLE_SECP_OPS | NO_UNKNOWN_OPS;
+        assert!(program.run(INFINITE_COST, flags, &Program::null()).is_ok());
+        assert!(program
+            .run(INFINITE_COST, NO_UNKNOWN_OPS, &Program::null())
+            .is_err());
+    }
+
+    assert!(run(&format!(
+        "(secp256r1_verify (q . 0x{r1_pk}) (q . 0x{msg}) (q . 0x{r1_sig}))"
+    ))
+    .is_ok());
+
+    //Signatures do not verify on the other curve or for another message
+    assert!(run(&format!(
+        "(secp256r1_verify (q . 0x{r1_pk}) (q . 0x{msg}) (q . 0x{k1_sig}))"
+    ))
+    .is_err());
+    assert!(run(&format!(
+        "(secp256k1_verify (q . 0x{k1_pk}) (q . 0x{}) (q . 0x{k1_sig}))",
+        hex::encode([0u8; 32])
+    ))
+    .is_err());
+}
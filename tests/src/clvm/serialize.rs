@@ -0,0 +1,92 @@
+#[test]
+pub fn test_backref_round_trip() {
+    use dg_xch_core::clvm::parser::{sexp_from_bytes, sexp_from_bytes_backrefs};
+    use dg_xch_core::clvm::program::Program;
+    use dg_xch_puzzles::clvm_puzzles::SINGLETON_MOD;
+    use dg_xch_puzzles::p2_delegated_puzzle_or_hidden_puzzle::MOD;
+
+    //A generator style list repeating the same puzzle reveal
+    let program = Program::to(vec![
+        MOD.clone(),
+        SINGLETON_MOD.clone(),
+        MOD.clone(),
+        Program::to(vec![MOD.clone(), SINGLETON_MOD.clone()]),
+    ]);
+    let compressed = program.to_bytes_backrefs().unwrap();
+    assert!(compressed.len() < program.serialized.len() - MOD.serialized.len());
+    assert!(sexp_from_bytes(&compressed).is_err());
+    let parsed = Program::from_bytes_backrefs(&compressed).unwrap();
+    assert_eq!(parsed, program);
+    assert_eq!(parsed.serialized, program.serialized);
+
+    //Classic serialization is valid input and programs without repeats do
+    //not change
+    let parsed = sexp_from_bytes_backrefs(&program.serialized).unwrap();
+    assert_eq!(parsed, program.sexp);
+    let small = Program::to(vec![1, 2, 3]);
+    assert_eq!(small.to_bytes_backrefs().unwrap(), small.serialized);
+    assert_eq!(
+        Program::from_bytes_backrefs(&SINGLETON_MOD.to_bytes_backrefs().unwrap()).unwrap(),
+        *SINGLETON_MOD
+    );
+}
+
+#[test]
+pub fn test_backref_parse() {
+    use dg_xch_core::clvm::parser::sexp_from_bytes_backrefs;
+    use dg_xch_core::clvm::program::SerializedProgram;
+
+    //The path 1 refers to the whole parse stack, ("foobar" "foobar")
+    let compressed = SerializedProgram::from_hex("ff86666f6f626172ff86666f6f626172fe01").unwrap();
+    let expected = "ff86666f6f626172ff86666f6f626172ff86666f6f626172ff86666f6f62617280";
+    let program = compressed.to_program_backrefs().unwrap();
+    assert_eq!(hex::encode(&program.serialized), expected);
+    let expanded = SerializedProgram::from_bytes_backrefs(&compressed.to_bytes()).unwrap();
+    assert_eq!(hex::encode(expanded.to_bytes()), expected);
+    assert_eq!(
+        expanded.to_program_backrefs().unwrap(),
+        expanded.to_program()
+    );
+    let recompressed = expanded.to_bytes_backrefs().unwrap();
+    assert!(recompressed.len() < expanded.to_bytes().len());
+    assert_eq!(
+        SerializedProgram::from_bytes_backrefs(&recompressed).unwrap(),
+        expanded
+    );
+
+    //Paths into the last value and an empty stack
+    let program = sexp_from_bytes_backrefs(hex::decode("ff86666f6f626172fe02").unwrap()).unwrap();
+    assert_eq!(program.first().unwrap(), program.rest().unwrap());
+    assert!(sexp_from_bytes_backrefs(hex::decode("fe02").unwrap()).is_err());
+    assert!(sexp_from_bytes_backrefs(hex::decode("ff01fe07").unwrap()).is_err());
+}
+
+#[test]
+pub fn test_backref_expansion_limit() {
+    use dg_xch_core::clvm::parser::{sexp_from_bytes_backrefs, sexp_to_bytes};
+    use std::io::ErrorKind;
+
+    //Each level conses the previous value with a back reference to itself,
+    //doubling the expanded size
+    let doubling = |levels: usize| {
+        let mut bytes = vec![0xff; levels];
+        bytes.extend(hex::decode("86666f6f626172").unwrap());
+        for _ in 0..levels {
+            bytes.extend([0xfe, 0x02]);
+        }
+        bytes
+    };
+    let expanded = sexp_from_bytes_backrefs(doubling(10)).unwrap();
+    assert_eq!(sexp_to_bytes(&expanded).unwrap().len(), 8 * 1024 - 1);
+    let err = sexp_from_bytes_backrefs(doubling(64)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    //References to the whole parse stack are limited the same way
+    let mut bytes = vec![0xff; 64];
+    bytes.extend(hex::decode("86666f6f626172").unwrap());
+    for _ in 0..64 {
+        bytes.extend([0xfe, 0x01]);
+    }
+    let err = sexp_from_bytes_backrefs(bytes).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
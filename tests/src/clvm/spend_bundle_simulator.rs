@@ -0,0 +1,122 @@
+#[tokio::test]
+pub async fn test_simulate_spend_bundle() {
+    use blst::min_pk::SecretKey;
+    use dg_xch_cli::wallets::coin_selection::LargestFirstSelector;
+    use dg_xch_cli::wallets::memory_wallet::{MemoryWallet, MemoryWalletConfig, MemoryWalletStore};
+    use dg_xch_cli::wallets::transaction_builder::TransactionBuilder;
+    use dg_xch_cli::wallets::{Wallet, WalletInfo, WalletStore};
+    use dg_xch_core::blockchain::coin::Coin;
+    use dg_xch_core::blockchain::coin_record::CoinRecord;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+    use dg_xch_core::blockchain::spend_bundle::SpendBundle;
+    use dg_xch_core::blockchain::spend_bundle_simulator::{simulate_spend_bundle, SimulationError};
+    use dg_xch_core::blockchain::wallet_type::WalletType;
+    use dg_xch_core::consensus::constants::{ConsensusConstants, MAINNET};
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    let master_sk = SecretKey::key_gen(&[31u8; 32], &[]).unwrap();
+    let mut store = MemoryWalletStore::new(master_sk.clone(), 0);
+    let our_ph = store
+        .get_derivation_record_at_index(0, false)
+        .await
+        .unwrap()
+        .puzzle_hash;
+    let record = |parent: u8, amount: u64| CoinRecord {
+        coin: Coin {
+            parent_coin_info: Bytes32::new(&[parent; 32]),
+            puzzle_hash: our_ph,
+            amount,
+        },
+        confirmed_block_index: 10,
+        spent_block_index: 0,
+        timestamp: 0,
+        coinbase: false,
+        spent: false,
+    };
+    store.update_coin_records(vec![record(1, 700), record(2, 600)]);
+    let wallet = MemoryWallet::create(
+        WalletInfo {
+            id: 1,
+            name: "memory_wallet".to_string(),
+            wallet_type: WalletType::StandardWallet,
+            constants: Default::default(),
+            master_sk,
+            wallet_store: Arc::new(Mutex::new(store)),
+            data: "".to_string(),
+        },
+        MemoryWalletConfig {
+            fullnode_host: "localhost".to_string(),
+            fullnode_port: 8555,
+            fullnode_ssl_path: None,
+            additional_headers: None,
+            gap_limit: 5,
+        },
+    );
+    let their_ph = Bytes32::new(&[9u8; 32]);
+    let tx = TransactionBuilder::new()
+        .add_output(their_ph, 1_000, vec![])
+        .fee(50)
+        .coin_selector(LargestFirstSelector::default())
+        .build(&wallet)
+        .await
+        .unwrap();
+    let constants: &ConsensusConstants = &MAINNET;
+
+    let report = simulate_spend_bundle(&tx.spend_bundle, constants);
+    assert!(report.is_valid(), "{:?}", report.errors);
+    assert!(report.signature_valid);
+    assert_eq!(report.spends.len(), 2);
+    assert_eq!(report.input_amount, 1_300);
+    assert_eq!(report.output_amount, 1_250);
+    assert_eq!(report.fee, 50);
+    assert_eq!(report.reserve_fee, 50);
+    assert_eq!(report.additions.len(), 2);
+    assert_eq!(report.removals, tx.removals);
+    assert!(report.execution_cost > 0);
+    assert_eq!(
+        report.total_cost,
+        report.execution_cost + report.condition_cost + report.byte_cost
+    );
+    assert_eq!(report.coin_announcements.len(), 2);
+
+    //Signing for another network's AGG_SIG_ME data is rejected
+    let mut testnet = constants.clone();
+    testnet.agg_sig_me_additional_data = vec![1u8; 32];
+    let report = simulate_spend_bundle(&tx.spend_bundle, &testnet);
+    assert!(!report.signature_valid);
+    assert_eq!(report.errors, vec![SimulationError::InvalidSignature]);
+
+    //Splitting the bundle breaks the announcement link and the signature
+    let split = SpendBundle {
+        coin_spends: vec![tx.spend_bundle.coin_spends[0].clone()],
+        aggregated_signature: tx.spend_bundle.aggregated_signature,
+    };
+    let report = simulate_spend_bundle(&split, constants);
+    assert!(!report.is_valid());
+    assert!(report
+        .errors
+        .iter()
+        .any(|e| matches!(e, SimulationError::MissingCoinAnnouncement(_))));
+    assert!(report.errors.contains(&SimulationError::InvalidSignature));
+
+    //A coin whose puzzle does not hash to its puzzle_hash
+    let mut mismatched = tx.spend_bundle.clone();
+    mismatched.coin_spends[1].coin.puzzle_hash = their_ph;
+    let report = simulate_spend_bundle(&mismatched, constants);
+    assert!(report
+        .errors
+        .iter()
+        .any(|e| matches!(e, SimulationError::PuzzleHashMismatch { expected, .. } if *expected == their_ph)));
+
+    //Outputs larger than inputs
+    let mut overspent = tx.spend_bundle.clone();
+    for spend in &mut overspent.coin_spends {
+        spend.coin.amount = 100;
+    }
+    let report = simulate_spend_bundle(&overspent, constants);
+    assert!(report
+        .errors
+        .iter()
+        .any(|e| matches!(e, SimulationError::OutputsExceedInputs { .. })));
+}
@@ -0,0 +1,64 @@
+#[test]
+pub fn test_execution_trace() {
+    use dg_xch_core::clvm::assemble::assemble_text;
+    use dg_xch_core::clvm::dialect::ChiaDialect;
+    use dg_xch_core::clvm::program::Program;
+    use dg_xch_core::clvm::run_program::run_program_with_tracer;
+    use dg_xch_core::clvm::utils::{INFINITE_COST, MEMPOOL_MODE};
+
+    let program = assemble_text("(+ 2 (* (q . 3) 5) (* 2 5))").unwrap();
+    let args = Program::to(vec![Program::to(4), Program::to(6)]);
+    let (cost, result) = program.run_with_cost(INFINITE_COST, &args).unwrap();
+    let (traced, trace) = program.run_with_trace(INFINITE_COST, MEMPOOL_MODE, &args);
+    let (traced_cost, traced_result) = traced.unwrap();
+    assert_eq!((traced_cost, &traced_result), (cost, &result));
+    assert_eq!(result.as_int().unwrap(), 46.into());
+
+    //Arguments are evaluated before the operator that consumes them
+    let names: Vec<String> = trace.ops.iter().map(|op| op.operator_name()).collect();
+    assert_eq!(names, vec!["*", "*", "+"]);
+    assert_eq!(
+        trace.ops.iter().map(|op| op.depth).collect::<Vec<_>>(),
+        vec![1, 1, 0]
+    );
+    assert_eq!(trace.ops[0].args, args.sexp);
+    assert_eq!(trace.ops[2].result.as_ref().unwrap().to_string(), "46");
+    assert!(trace.failure().is_none());
+    assert_eq!(trace.total_cost, cost);
+    assert!(trace.interpreter_cost() > 0);
+    assert_eq!(
+        trace.operator_cost() + trace.interpreter_cost(),
+        trace.total_cost
+    );
+    let breakdown = trace.cost_breakdown();
+    assert_eq!(breakdown.len(), 2);
+    assert_eq!(breakdown[0].operator, "*");
+    assert_eq!(breakdown[0].calls, 2);
+    assert!(breakdown[0].cost > breakdown[1].cost);
+
+    //A raise is recorded even though the run fails
+    let program = assemble_text("(c (q . 1) (x (q . \"boom\") 2))").unwrap();
+    let (result, trace) = program.run_with_trace(INFINITE_COST, 0, &args);
+    assert!(result.is_err());
+    let failure = trace.failure().unwrap();
+    assert_eq!(failure.operator_name(), "x");
+    assert_eq!(failure.args.first().unwrap().atom().unwrap().data, b"boom");
+    assert!(failure.is_err());
+    assert_eq!(trace.total_cost, 0);
+
+    //The tracer can also be driven directly
+    let program = assemble_text("(sha256 (q . \"abc\") (concat 2 5))").unwrap();
+    let mut seen = vec![];
+    run_program_with_tracer(
+        ChiaDialect::new(0),
+        &program.to_program().sexp,
+        &args.sexp,
+        INFINITE_COST,
+        None,
+        &mut |op| seen.push((op.operator_name(), op.cost)),
+    )
+    .unwrap();
+    assert_eq!(seen.len(), 2);
+    assert_eq!(seen[0].0, "concat");
+    assert_eq!(seen[1].0, "sha256");
+}
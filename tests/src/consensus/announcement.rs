@@ -0,0 +1,28 @@
+#[test]
+pub fn test_announcement_name() {
+    use dg_xch_core::blockchain::announcement::Announcement;
+    use dg_xch_core::blockchain::sized_bytes::{Bytes32, SizedBytes};
+    //Chia names announcements std_hash(bytes(origin_info) + message), with no length prefix on the message
+    let announcement = Announcement {
+        origin_info: Bytes32::new(&[1u8; 32]),
+        message: b"hello".to_vec(),
+    };
+    assert_eq!(
+        announcement.name(),
+        Bytes32::new(
+            &hex::decode("2e997ef63bcfa3477ae8a0625220e717c407ba0fdfd0074cc79afdfd44073a76")
+                .unwrap()
+        )
+    );
+    let empty = Announcement {
+        origin_info: Bytes32::new(&[1u8; 32]),
+        message: vec![],
+    };
+    assert_eq!(
+        empty.name(),
+        Bytes32::new(
+            &hex::decode("72cd6e8422c407fb6d098690f1130b7ded7ec2f7f5e1d30bd9d521f015363793")
+                .unwrap()
+        )
+    );
+}
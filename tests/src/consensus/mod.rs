@@ -1 +1,2 @@
+mod announcement;
 mod pot_iterations;
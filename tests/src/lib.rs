@@ -1,3 +1,5 @@
+pub mod cli;
 pub mod clients;
 pub mod clvm;
 mod consensus;
+pub mod proof_of_space;
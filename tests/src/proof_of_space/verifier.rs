@@ -0,0 +1,230 @@
+//Writes a k18 v2 plot containing only a C1 and C2 table, so the single C3
+//park holds one f7 whose proof can not be found in table 7.
+//With `tables` set, tables 1 to 7 get one readable park each, except the given
+//table whose park has an out of range deltas size. Returns the table addresses.
+#[cfg(test)]
+fn write_stub_plot(
+    tables: Option<dg_xch_core::plots::PlotTable>,
+) -> (std::path::PathBuf, u64, Vec<u64>) {
+    use dg_xch_core::plots::PlotTable;
+    use dg_xch_pos::constants::HEADER_V2_MAGIC;
+    use dg_xch_pos::entry_sizes::EntrySizes;
+    let k = 18u8;
+    let f7 = 0x2a2a2u64 >> 6;
+    let f7_bytes = (f7 << 6).to_be_bytes()[5..8].to_vec();
+    let mut header = HEADER_V2_MAGIC.to_vec();
+    header.extend(1u32.to_le_bytes());
+    header.extend([9u8; 32]);
+    header.push(k);
+    header.extend(112u16.to_be_bytes());
+    header.extend([0u8; 112]);
+    header.extend(0u32.to_le_bytes());
+    //C1 holds one entry and the trailing empty entry, C2 a single entry
+    let c1_address = 320u64;
+    let c2_address = c1_address + 6;
+    let mut parks = vec![];
+    let mut table_addresses = vec![];
+    if let Some(corrupt) = tables {
+        for table in [
+            PlotTable::Table1,
+            PlotTable::Table2,
+            PlotTable::Table3,
+            PlotTable::Table4,
+            PlotTable::Table5,
+            PlotTable::Table6,
+            PlotTable::Table7,
+        ] {
+            table_addresses.push(c2_address + 3 + parks.len() as u64);
+            if table == PlotTable::Table7 {
+                parks.extend(vec![
+                    0u8;
+                    EntrySizes::calculate_park7_size(k as u32) as usize
+                ]);
+                continue;
+            }
+            //Line point and stubs are left zeroed, the deltas are stored uncompressed and empty
+            let mut park = vec![0u8; EntrySizes::calculate_park_size(&table, k as u32) as usize];
+            let deltas_size: u16 = if table == corrupt { 0x7fff } else { 0x8000 };
+            let offset = (EntrySizes::line_point_size_bytes(k as u32)
+                + EntrySizes::calculate_stubs_size(k as u32)) as usize;
+            park[offset..offset + 2].copy_from_slice(&deltas_size.to_le_bytes());
+            parks.extend(park);
+        }
+    }
+    let plot_size = c2_address + 3 + parks.len() as u64;
+    if table_addresses.is_empty() {
+        table_addresses = vec![plot_size; 7];
+    }
+    for address in table_addresses
+        .iter()
+        .copied()
+        .chain([c1_address, c2_address, plot_size])
+    {
+        header.extend(address.to_be_bytes());
+    }
+    header.extend([0u8; 80]);
+    header.resize(320, 0);
+    header.extend(&f7_bytes);
+    header.extend([0u8; 3]);
+    header.extend(&f7_bytes);
+    header.extend(parks);
+    let path = std::env::temp_dir().join(format!("{}.plot", uuid::Uuid::new_v4()));
+    std::fs::write(&path, header).unwrap();
+    (path, f7, table_addresses)
+}
+
+#[tokio::test]
+pub async fn test_validate_plot_report() {
+    use dg_xch_core::plots::{PlotFile, PlotTable};
+    use dg_xch_pos::plots::disk_plot::DiskPlot;
+    use dg_xch_pos::verifier::{validate_plot, ValidatePlotOptions, ValidationProgress};
+    use std::sync::{Arc, Mutex};
+
+    let (path, f7, _) = write_stub_plot(None);
+    let plot = DiskPlot::new_in_ram(&path).await.unwrap();
+    assert_eq!(*plot.k(), 18);
+    assert_eq!(*plot.plot_size(), 329);
+    assert_eq!(plot.table_size(&PlotTable::C3), 0);
+
+    //Both modes report the park whose proof is missing with its offset
+    for (in_ram, unpacked) in [(false, false), (true, false), (false, true), (true, true)] {
+        let seen: Arc<Mutex<Vec<ValidationProgress>>> = Default::default();
+        let recorder = seen.clone();
+        let report = validate_plot(
+            &path,
+            ValidatePlotOptions {
+                in_ram,
+                unpacked,
+                thread_count: 1,
+                progress: Some(Arc::new(move |p| recorder.lock().unwrap().push(*p))),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!report.is_valid());
+        assert_eq!((report.proofs_checked, report.proofs_failed), (1, 1));
+        assert_eq!(report.parks_checked, 1);
+        assert_eq!(report.bad_parks.len(), 1);
+        let bad_park = &report.bad_parks[0];
+        assert_eq!(bad_park.table, PlotTable::C3);
+        assert_eq!((bad_park.park_index, bad_park.offset), (0, 329));
+        assert_eq!(bad_park.f7_index, Some(0));
+        let seen = seen.lock().unwrap();
+        let last = seen.last().unwrap();
+        assert_eq!(last.table, PlotTable::C3);
+        assert_eq!(
+            (last.parks_done, last.park_count, last.proofs_failed),
+            (1, 1, 1)
+        );
+    }
+
+    //Resuming past the end checks nothing, a single f7 checks only its proofs
+    let report = validate_plot(
+        &path,
+        ValidatePlotOptions {
+            start_offset: 1.0,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert!(report.is_valid());
+    assert_eq!(report.parks_checked, 0);
+    let report = validate_plot(
+        &path,
+        ValidatePlotOptions {
+            f7: f7 as i64,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!((report.proofs_checked, report.proofs_failed), (1, 1));
+    assert!(validate_plot(
+        &path,
+        ValidatePlotOptions {
+            use_cuda: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+pub async fn test_validate_plot_tables() {
+    use dg_xch_core::plots::PlotTable;
+    use dg_xch_pos::entry_sizes::EntrySizes;
+    use dg_xch_pos::verifier::{validate_plot, ValidatePlotOptions};
+
+    let (path, _, table_addresses) = write_stub_plot(Some(PlotTable::Table4));
+    let k = 18;
+    assert_eq!(
+        table_addresses[1] - table_addresses[0],
+        EntrySizes::calculate_park_size(&PlotTable::Table1, k) as u64
+    );
+
+    //Every table park is decoded, only the corrupt one is reported next to the C3 proof failure
+    for in_ram in [false, true] {
+        let report = validate_plot(
+            &path,
+            ValidatePlotOptions {
+                in_ram,
+                unpacked: true,
+                thread_count: 1,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.parks_checked, 8);
+        assert_eq!(report.bad_parks.len(), 2);
+        let bad_park = &report.bad_parks[0];
+        assert_eq!(bad_park.table, PlotTable::Table4);
+        assert_eq!(
+            (bad_park.park_index, bad_park.offset),
+            (0, table_addresses[3])
+        );
+        assert!(bad_park.error.contains("Invalid size for deltas"));
+        assert_eq!(report.bad_parks[1].table, PlotTable::C3);
+    }
+
+    //Without the unpacked walk the tables are not read
+    let report = validate_plot(
+        &path,
+        ValidatePlotOptions {
+            thread_count: 1,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.parks_checked, 1);
+    assert_eq!(report.bad_parks.len(), 1);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+pub fn test_park_ranges() {
+    use dg_xch_pos::plots::disk_plot::{DiskPlot, PlotBytes};
+    use dg_xch_pos::verifier::{sample_park_entries, thread_park_range};
+    use std::path::Path;
+
+    //Trailing parks go to the first threads and ranges cover every park once
+    let ranges: Vec<_> = (0..3).map(|i| thread_park_range(i, 3, 10, 0.0)).collect();
+    assert_eq!(ranges, vec![0..4, 4..7, 7..10]);
+    assert_eq!(thread_park_range(1, 3, 10, 0.5), 5..7);
+    assert_eq!(thread_park_range(2, 3, 10, 1.0), 10..10);
+    assert_eq!(thread_park_range(0, 1, 0, 0.0), 0..0);
+
+    assert_eq!(sample_park_entries(4, 0), vec![0, 1, 2, 3]);
+    assert_eq!(sample_park_entries(4, 10), vec![0, 1, 2, 3]);
+    assert_eq!(sample_park_entries(10000, 4), vec![0, 2500, 5000, 7500]);
+
+    assert!(
+        DiskPlot::from_bytes(Path::new("short.plot"), PlotBytes::from(vec![0u8; 100])).is_err()
+    );
+    assert!(DiskPlot::from_bytes(Path::new("bad.plot"), PlotBytes::from(vec![0u8; 400])).is_err());
+}